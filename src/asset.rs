@@ -0,0 +1,196 @@
+//! Loading configs through Bevy's asset server, for platforms such as WASM without a
+//! synchronous filesystem.
+
+use std::marker::PhantomData;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoadFailedEvent, AssetLoader, LoadContext};
+use bevy::prelude::*;
+use bevy::reflect::{GetTypeRegistration, Typed};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ConfigFile, JsonValue, LoadConfigError, insert_config, load_config_from_document, parse_config,
+    register_config, report, telemetry,
+};
+
+/// The folder Bevy's [`AssetPlugin`] reads assets from by default.
+const ASSET_FOLDER: &str = "assets/";
+
+/// A config loaded as an asset by [`config_file_asset_plugin`].
+///
+/// The plugin moves the config out of the asset into the `T` resource as soon as it
+/// loads, so there is rarely a reason to use this directly.
+#[derive(Asset, TypePath)]
+pub struct ConfigAsset<T: TypePath + Send + Sync>(pub T, bool);
+
+/// Message written when [`config_file_asset_plugin`] fails to load `T`.
+///
+/// The resource is left as it was: absent after a failed first load, or unchanged after a
+/// failed reload.
+#[derive(Message)]
+pub struct ConfigLoadFailed<T> {
+    /// The asset path that was loaded.
+    pub path: String,
+    /// Why the config failed to load.
+    pub error: String,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Loads `T` from its config file with the asset server.
+#[derive(TypePath)]
+struct ConfigAssetLoader<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> AssetLoader for ConfigAssetLoader<T>
+where
+    T: for<'de> Deserialize<'de> + ConfigFile + TypePath + Send + Sync + Typed,
+{
+    type Asset = ConfigAsset<T>;
+    type Settings = ();
+    type Error = LoadConfigError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<ConfigAsset<T>, LoadConfigError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(LoadConfigError::Io)?;
+        let content = String::from_utf8(bytes).map_err(|error| {
+            LoadConfigError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+        })?;
+        let path = load_context.path().path().to_string_lossy();
+        let document: JsonValue = parse_config(&path, &content)?;
+        let (config, overridden) =
+            report::tracking_overrides(|| load_config_from_document(document))?;
+        Ok(ConfigAsset(config, overridden))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["yaml", "yml", "json", "ron", "toml"]
+    }
+}
+
+/// Keeps `T`'s config asset loaded so changes to it are picked up.
+#[derive(Resource)]
+struct ConfigAssetHandle<T: TypePath + Send + Sync> {
+    _handle: Handle<ConfigAsset<T>>,
+}
+
+/// Like [`config_file_plugin`](crate::config_file_plugin), but reads `T`'s file through
+/// Bevy's [`AssetServer`] instead of `std::fs`, which makes it work on web builds.
+///
+/// The asset path is [`ConfigFile::PATH`] with its leading `assets/` removed, so
+/// `assets/config/audio.yaml` is loaded from `config/audio.yaml` in the asset folder. The
+/// file loads in the background: the resource is inserted a few frames after startup, so
+/// systems should take `Option<Res<T>>` or wait for a [`ConfigLoaded`](crate::ConfigLoaded)
+/// message. With the asset server watching for changes, edits reload the resource.
+/// Failures are logged and reported with a [`ConfigLoadFailed`] message.
+///
+/// Overrides are applied as usual. `extends:`, `includes:`, child configs and default
+/// layers are not supported. Requires the `asset` feature and Bevy's [`AssetPlugin`].
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, config_file_asset_plugin};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// App::new()
+///     .add_plugins((AssetPlugin::default(), config_file_asset_plugin::<AudioSettings>))
+///     .run();
+/// ```
+pub fn config_file_asset_plugin<T>(app: &mut App)
+where
+    T: Resource
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ConfigFile
+        + Reflect
+        + TypePath
+        + GetTypeRegistration
+        + Typed,
+{
+    if !register_config::<T>(app) {
+        return;
+    }
+    app.init_asset::<ConfigAsset<T>>()
+        .register_asset_loader(ConfigAssetLoader::<T> {
+            _marker: PhantomData,
+        })
+        .add_message::<ConfigLoadFailed<T>>()
+        .add_systems(Startup, start_loading::<T>)
+        .add_systems(
+            PreUpdate,
+            (insert_loaded_config::<T>, report_failed_load::<T>),
+        );
+}
+
+fn start_loading<T>(mut commands: Commands, asset_server: Res<AssetServer>)
+where
+    T: ConfigFile + TypePath + Send + Sync,
+{
+    let path = T::PATH.strip_prefix(ASSET_FOLDER).unwrap_or(T::PATH);
+    commands.insert_resource(ConfigAssetHandle::<T> {
+        _handle: asset_server.load(path),
+    });
+}
+
+/// Moves each newly loaded config out of its asset into the `T` resource.
+fn insert_loaded_config<T>(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<ConfigAsset<T>>>,
+    mut assets: ResMut<Assets<ConfigAsset<T>>>,
+    current: Option<Res<T>>,
+) where
+    T: Resource + Serialize + ConfigFile + TypePath + Typed,
+{
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = *event
+        else {
+            continue;
+        };
+        if let Some(ConfigAsset(config, overridden)) = assets.remove(id) {
+            insert_config(&mut commands, config, current.is_some(), overridden);
+        }
+    }
+}
+
+/// Logs each failed load of `T` and writes a [`ConfigLoadFailed`] message.
+fn report_failed_load<T>(
+    mut commands: Commands,
+    mut failures: MessageReader<AssetLoadFailedEvent<ConfigAsset<T>>>,
+    mut failed: MessageWriter<ConfigLoadFailed<T>>,
+) where
+    T: ConfigFile + TypePath + Send + Sync,
+{
+    for failure in failures.read() {
+        telemetry::record::<T>(&mut commands, |counts| counts.errors += 1);
+        let path = failure.path.to_string();
+        let error = failure.error.to_string();
+        #[cfg(feature = "logging")]
+        error!("failed to load config {}: {}", path, error);
+        failed.write(ConfigLoadFailed {
+            path,
+            error,
+            _marker: PhantomData,
+        });
+    }
+}
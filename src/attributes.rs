@@ -0,0 +1,132 @@
+//! Reflection attributes that change how individual config fields are handled.
+//!
+//! Attach them to fields of a `#[derive(Reflect)]` config type using Bevy's custom
+//! attribute syntax:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_config_file::attributes::RestartRequired;
+//!
+//! #[derive(Reflect)]
+//! struct VideoSettings {
+//!     #[reflect(@RestartRequired)]
+//!     window_mode: String,
+//!     brightness: f32,
+//! }
+//! ```
+//!
+//! The loaders read these from the type's reflection info, so they are honoured whether
+//! or not the type was added with one of the config plugins.
+
+use std::any::TypeId;
+use std::cell::RefCell;
+
+use bevy::reflect::{Reflect, TypeInfo};
+
+thread_local! {
+    /// The reflection info of the types being loaded on this thread, innermost last.
+    static LOADING: RefCell<Vec<&'static TypeInfo>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Marks a field that cannot be applied while the app is running.
+///
+/// When a reload changes this field, the old value is kept and a
+/// [`RestartRequired`](crate::RestartRequired) message is written instead.
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct RestartRequired;
+
+/// Marks a field that must never be changed by an environment, in-memory or command-line
+/// override.
+///
+/// Overrides and `CONFIG_DEFAULT_{TypeName}` values targeting this field are dropped with a
+/// warning, so security-sensitive values (such as file paths) can only come from the config
+/// file. Fields of nested structs are protected too, including from overrides that replace
+/// their parent object.
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct NoEnvOverride;
+
+/// Marks a field holding a secret, such as an API key or password.
+///
+/// The field's value is shown as `***` in every log message and in the values passed to
+/// [`ConfigFile::on_override`](crate::ConfigFile::on_override), including when the field
+/// belongs to a struct nested inside the config.
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct Secret;
+
+/// The smallest value a numeric field should take, reported by
+/// [`config_field_info`](crate::config_field_info) for settings menus.
+///
+/// This is a hint for UIs; it is not enforced when loading.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct Min(pub f64);
+
+/// The largest value a numeric field should take, reported by
+/// [`config_field_info`](crate::config_field_info) for settings menus.
+///
+/// This is a hint for UIs; it is not enforced when loading.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct Max(pub f64);
+
+/// Runs `load` with `info` available to [`type_info`], so everything `load` does honours
+/// the attributes of `info`'s type.
+pub(crate) fn with_type_info<R>(info: &'static TypeInfo, load: impl FnOnce() -> R) -> R {
+    LOADING.with(|loading| loading.borrow_mut().push(info));
+    let result = load();
+    LOADING.with(|loading| loading.borrow_mut().pop());
+    result
+}
+
+/// Returns `T`'s reflection info if `T` is being loaded inside [`with_type_info`].
+pub(crate) fn type_info<T: 'static>() -> Option<&'static TypeInfo> {
+    LOADING.with(|loading| {
+        loading
+            .borrow()
+            .iter()
+            .rev()
+            .find(|info| info.type_id() == TypeId::of::<T>())
+            .copied()
+    })
+}
+
+/// Returns the paths of `T`'s fields carrying the attribute `A`, if its reflection info is
+/// known.
+pub(crate) fn type_attribute_paths<T: 'static, A: Reflect>() -> Vec<Vec<&'static str>> {
+    type_info::<T>()
+        .map(attribute_paths::<A>)
+        .unwrap_or_default()
+}
+
+/// Returns the paths of the fields in `info` that carry the attribute `A`, descending into
+/// nested structs, such as `["database", "password"]`.
+pub(crate) fn attribute_paths<A: Reflect>(info: &TypeInfo) -> Vec<Vec<&'static str>> {
+    let Ok(info) = info.as_struct() else {
+        return Vec::new();
+    };
+    info.iter()
+        .flat_map(|field| {
+            if field.has_attribute::<A>() {
+                return vec![vec![field.name()]];
+            }
+            let mut paths = field
+                .type_info()
+                .map(attribute_paths::<A>)
+                .unwrap_or_default();
+            for path in &mut paths {
+                path.insert(0, field.name());
+            }
+            paths
+        })
+        .collect()
+}
+
+/// Returns the names of the struct fields in `info` that carry the attribute `A`.
+pub(crate) fn fields_with_attribute<A: Reflect>(info: &TypeInfo) -> Vec<&'static str> {
+    match info.as_struct() {
+        Ok(info) => info
+            .iter()
+            .filter(|field| field.has_attribute::<A>())
+            .map(|field| field.name())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
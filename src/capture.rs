@@ -0,0 +1,263 @@
+//! Capturing the inputs that produced a config so it can be reproduced elsewhere.
+//!
+//! A capture runs the loader once while recording every file, environment variable and
+//! override it reads; a replay runs the same loader with those reads answered from the
+//! capture instead.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{env, io};
+
+use bevy::reflect::Typed;
+use serde::{Deserialize, Serialize};
+
+use crate::{ConfigFile, ConfigRegistry, JsonValue, LoadConfigError, load_config_file, locale};
+
+/// The inputs captured for one config type.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapturedConfig {
+    /// The config file path, after locale substitution and [`ConfigFile::resolve_path`].
+    pub path: String,
+    /// Every file the loader read, keyed by path: the config file and its common, layer,
+    /// field, `extends:` and `includes:` files. Files that aren't UTF-8 text, such as
+    /// encrypted ones, are left out.
+    pub files: BTreeMap<String, String>,
+    /// Every environment variable the loader read that was set, such as `CONFIG_{TypeName}`,
+    /// its per-field and default variables, and the locale and variant variables.
+    pub env: BTreeMap<String, String>,
+    /// The in-memory override set with [`set_override`](crate::set_override), if any.
+    pub in_memory_override: Option<String>,
+    /// The `(field path, value)` overrides given on the command line.
+    pub cli_overrides: Vec<(String, JsonValue)>,
+}
+
+/// A serializable snapshot of the inputs configs were loaded from, for reproducible bug
+/// reports.
+///
+/// Capture the inputs on the machine where a problem occurs, attach the serialized capture
+/// to the report, and [`replay`](Self::replay) it elsewhere to get exactly the same config
+/// values regardless of the local files and environment. Capturing runs the loader, so
+/// everything it reads is recorded, and replaying runs it again with the same merging,
+/// migrations and validation.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigCapture, ConfigFile};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Debug, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// let mut capture = ConfigCapture::default();
+/// capture.capture::<AudioSettings>();
+/// let report = serde_json::to_string(&capture).unwrap();
+///
+/// // Later, on another machine:
+/// let capture: ConfigCapture = serde_json::from_str(&report).unwrap();
+/// let settings = capture.replay::<AudioSettings>().unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigCapture {
+    /// Captured configs keyed by fully qualified type name.
+    pub configs: BTreeMap<String, CapturedConfig>,
+}
+
+impl ConfigCapture {
+    /// Captures the inputs `T` is currently loaded from.
+    pub fn capture<T>(&mut self)
+    where
+        T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+    {
+        self.configs.insert(
+            std::any::type_name::<T>().to_string(),
+            capture_config::<T>(),
+        );
+    }
+
+    /// Captures every config type added to the app by a config plugin.
+    pub fn from_registry(registry: &ConfigRegistry) -> Self {
+        let mut capture = Self::default();
+        for config in registry.iter() {
+            capture
+                .configs
+                .insert(config.type_path.to_string(), config.capture());
+        }
+        capture
+    }
+
+    /// Loads `T` from the captured inputs, without touching the filesystem, environment
+    /// variables or command line.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoadConfigError::Io` with [`io::ErrorKind::NotFound`] if `T` was not
+    /// captured, and otherwise the errors [`load_config_file`] returned when capturing.
+    pub fn replay<T>(&self) -> Result<T, LoadConfigError>
+    where
+        T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+    {
+        let type_path = std::any::type_name::<T>();
+        let captured = self.configs.get(type_path).ok_or_else(|| {
+            LoadConfigError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no config captured for {}", type_path),
+            ))
+        })?;
+        let (result, _) = with_session(Session::Replaying(captured.clone()), load_config_file::<T>);
+        result
+    }
+}
+
+/// Loads `T` once, recording every input the load reads.
+pub(crate) fn capture_config<T>() -> CapturedConfig
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    let captured = CapturedConfig {
+        path: locale::config_path::<T>(),
+        ..Default::default()
+    };
+    // A config that fails to load is captured all the same, to reproduce the failure
+    let (_, session) = with_session(Session::Recording(captured), load_config_file::<T>);
+    match session {
+        Session::Recording(captured) | Session::Replaying(captured) => captured,
+    }
+}
+
+/// How the loader's inputs are read on this thread while a capture or replay runs.
+enum Session {
+    /// Inputs are read as usual and recorded.
+    Recording(CapturedConfig),
+    /// Inputs are read from the captured config only.
+    Replaying(CapturedConfig),
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+/// Runs `load` in `session`, returning its result and the session afterwards.
+fn with_session<R>(session: Session, load: impl FnOnce() -> R) -> (R, Session) {
+    let previous = SESSION.with(|current| current.borrow_mut().replace(session));
+    let result = load();
+    let session = SESSION.with(|current| std::mem::replace(&mut *current.borrow_mut(), previous));
+    (result, session.expect("the session was set above"))
+}
+
+/// Returns the captured config if a replay is running on this thread.
+fn replayed<R>(read: impl FnOnce(&CapturedConfig) -> R) -> Option<R> {
+    SESSION.with(|session| match &*session.borrow() {
+        Some(Session::Replaying(captured)) => Some(read(captured)),
+        _ => None,
+    })
+}
+
+/// Adds to the config being captured, if a capture is running on this thread.
+fn record(write: impl FnOnce(&mut CapturedConfig)) {
+    SESSION.with(|session| {
+        if let Some(Session::Recording(captured)) = &mut *session.borrow_mut() {
+            write(captured);
+        }
+    });
+}
+
+/// Reads the environment variable `name` for the loader.
+pub(crate) fn env_var(name: &str) -> Option<String> {
+    if let Some(value) = replayed(|captured| captured.env.get(name).cloned()) {
+        return value;
+    }
+    let value = env::var(name).ok()?;
+    record(|captured| {
+        captured.env.insert(name.to_string(), value.clone());
+    });
+    Some(value)
+}
+
+/// Reads every environment variable whose name starts with `prefix` for the loader.
+pub(crate) fn env_vars_with_prefix(prefix: &str) -> Vec<(String, String)> {
+    let matching = |(var, _): &(String, String)| var.starts_with(prefix);
+    if let Some(vars) = replayed(|captured| {
+        let vars = captured
+            .env
+            .iter()
+            .map(|(var, value)| (var.clone(), value.clone()));
+        vars.filter(matching).collect()
+    }) {
+        return vars;
+    }
+    let vars: Vec<(String, String)> = env::vars().filter(matching).collect();
+    record(|captured| captured.env.extend(vars.iter().cloned()));
+    vars
+}
+
+/// Returns `read()`, the bytes of the config file at `path`, or the captured file during a
+/// replay.
+pub(crate) fn read_file(
+    path: &Path,
+    read: impl FnOnce() -> Result<Vec<u8>, LoadConfigError>,
+) -> Result<Vec<u8>, LoadConfigError> {
+    let key = path.to_string_lossy();
+    if let Some(file) = replayed(|captured| captured.files.get(key.as_ref()).cloned()) {
+        return file.map(String::into_bytes).ok_or_else(|| {
+            LoadConfigError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} was not captured", key),
+            ))
+        });
+    }
+    let bytes = read()?;
+    if let Ok(text) = std::str::from_utf8(&bytes) {
+        record(|captured| {
+            captured.files.insert(key.into_owned(), text.to_string());
+        });
+    }
+    Ok(bytes)
+}
+
+/// During a replay, returns whether a file was captured at `path`.
+pub(crate) fn replayed_exists(path: &Path) -> Option<bool> {
+    replayed(|captured| captured.files.contains_key(path.to_string_lossy().as_ref()))
+}
+
+/// During a replay, returns the captured files directly inside the directory at `path`.
+pub(crate) fn replayed_dir(path: &Path) -> Option<Vec<PathBuf>> {
+    replayed(|captured| {
+        captured
+            .files
+            .keys()
+            .map(PathBuf::from)
+            .filter(|file| file.parent() == Some(path))
+            .collect()
+    })
+}
+
+/// Returns `stored`, the in-memory override, or the captured one during a replay.
+pub(crate) fn in_memory_override(stored: impl FnOnce() -> Option<String>) -> Option<String> {
+    if let Some(json) = replayed(|captured| captured.in_memory_override.clone()) {
+        return json;
+    }
+    let json = stored();
+    record(|captured| captured.in_memory_override.clone_from(&json));
+    json
+}
+
+/// Returns `given`, the command-line overrides, or the captured ones during a replay.
+pub(crate) fn cli_overrides(
+    given: impl FnOnce() -> Vec<(String, JsonValue)>,
+) -> Vec<(String, JsonValue)> {
+    if let Some(overrides) = replayed(|captured| captured.cli_overrides.clone()) {
+        return overrides;
+    }
+    let overrides = given();
+    record(|captured| captured.cli_overrides.clone_from(&overrides));
+    overrides
+}
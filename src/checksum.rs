@@ -0,0 +1,98 @@
+//! Verifying config files against `.sha256` sidecar files.
+//!
+//! The sidecar of `settings.yaml` is `settings.yaml.sha256`, holding the hex SHA-256 digest
+//! of the file. Anything after the digest is ignored, so the output of `sha256sum` works.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::{LoadConfigError, save};
+
+/// Returns the path of the sidecar file holding `path`'s checksum.
+fn sidecar_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".sha256");
+    path.with_file_name(file_name)
+}
+
+/// Returns the lowercase hex SHA-256 digest of `bytes`.
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+/// Fails with `LoadConfigError::Checksum` unless the `bytes` read from `path` match the
+/// digest in its sidecar, which is read with `read_file`.
+pub(crate) fn verify(
+    path: &Path,
+    bytes: &[u8],
+    read_file: fn(&Path) -> std::io::Result<Vec<u8>>,
+) -> Result<(), LoadConfigError> {
+    let sidecar = sidecar_path(path);
+    let contents = read_file(&sidecar).map_err(|error| {
+        LoadConfigError::Checksum(format!(
+            "can't read checksum file {}: {}",
+            sidecar.display(),
+            error
+        ))
+    })?;
+    let expected = String::from_utf8_lossy(&contents)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if expected.len() != 64 || !expected.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(LoadConfigError::Checksum(format!(
+            "{} doesn't hold a SHA-256 digest",
+            sidecar.display()
+        )));
+    }
+
+    let found = sha256_hex(bytes);
+    if found != expected {
+        return Err(LoadConfigError::Checksum(format!(
+            "{} has SHA-256 {}, but {} expects {}",
+            path.display(),
+            found,
+            sidecar.display(),
+            expected
+        )));
+    }
+    Ok(())
+}
+
+/// Writes the `.sha256` sidecar for the config file at `path`, for configs with
+/// [`ConfigFile::VERIFY_CHECKSUM`](crate::ConfigFile::VERIFY_CHECKSUM).
+///
+/// Run it from a build or packaging step whenever the shipped file changes. The sidecar
+/// holds the digest in the format written by `sha256sum`.
+///
+/// # Errors
+///
+/// Returns `LoadConfigError::Io` if the file can't be read or the sidecar can't be written.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy_config_file::write_checksum_file;
+///
+/// write_checksum_file("assets/config/settings.yaml").unwrap();
+/// ```
+pub fn write_checksum_file(path: impl AsRef<Path>) -> Result<(), LoadConfigError> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(LoadConfigError::Io)?;
+    write_sidecar(path, &bytes)
+}
+
+/// Writes the sidecar recording the checksum of `bytes`, the new contents of `path`.
+pub(crate) fn write_sidecar(path: &Path, bytes: &[u8]) -> Result<(), LoadConfigError> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let line = format!("{}  {}\n", sha256_hex(bytes), file_name);
+    save::write_atomically(&sidecar_path(path), line.as_bytes())
+}
@@ -0,0 +1,97 @@
+//! Command-line `--config KEY=VALUE` overrides.
+//!
+//! Each key is `{TypeName}.{field}`, optionally with further `.`-separated segments to reach
+//! nested fields, e.g. `--config CameraSettings.pan_speed=2000`. Values are parsed as JSON
+//! when possible (numbers, booleans, `null`, arrays, objects) and used as plain strings
+//! otherwise.
+
+use std::sync::{LazyLock, Mutex};
+
+use crate::JsonValue;
+
+static INJECTED_ARGS: LazyLock<Mutex<Option<Vec<String>>>> = LazyLock::new(Default::default);
+
+/// Replaces the arguments scanned for `--config KEY=VALUE` overrides.
+///
+/// By default [`std::env::args`] is used. This is useful for tests and for launchers that
+/// receive arguments some other way. Pass `None` to go back to the process arguments.
+pub fn set_cli_args<I, S>(args: Option<I>)
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let args = args.map(|args| args.into_iter().map(Into::into).collect());
+    *INJECTED_ARGS.lock().unwrap_or_else(|p| p.into_inner()) = args;
+}
+
+fn args() -> Vec<String> {
+    let injected = INJECTED_ARGS.lock().unwrap_or_else(|p| p.into_inner());
+    match injected.as_ref() {
+        Some(args) => args.clone(),
+        None => std::env::args().skip(1).collect(),
+    }
+}
+
+/// Returns the `(field path, value)` overrides given on the command line for `type_name`,
+/// in the order they appear.
+pub(crate) fn cli_overrides(type_name: &str) -> Vec<(String, JsonValue)> {
+    let mut overrides = Vec::new();
+    let mut args = args().into_iter();
+    while let Some(arg) = args.next() {
+        let pair = match arg.strip_prefix("--config") {
+            Some("") => match args.next() {
+                Some(pair) => pair,
+                None => break,
+            },
+            Some(rest) => match rest.strip_prefix('=') {
+                Some(pair) => pair.to_string(),
+                None => continue,
+            },
+            None => continue,
+        };
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let Some(field) = key
+            .strip_prefix(type_name)
+            .and_then(|rest| rest.strip_prefix('.'))
+        else {
+            continue;
+        };
+        overrides.push((field.to_string(), coerce_value(value)));
+    }
+    overrides
+}
+
+/// Parses `value` as JSON, falling back to a JSON string.
+pub(crate) fn coerce_value(value: &str) -> JsonValue {
+    serde_json::from_str(value).unwrap_or_else(|_| JsonValue::String(value.to_string()))
+}
+
+/// Converts the `.`-separated `path` to a JSON pointer ([RFC 6901]), escaping `~` and `/`
+/// in each segment.
+///
+/// [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+pub(crate) fn json_pointer(path: &str) -> String {
+    path.split('.')
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Sets the value at the `.`-separated `path` in `root`, creating objects as needed.
+///
+/// Returns the value that was replaced (`Null` if there was none).
+pub(crate) fn set_path(root: &mut JsonValue, path: &str, value: JsonValue) -> JsonValue {
+    let mut current = root;
+    for segment in path.split('.') {
+        if !current.is_object() {
+            *current = JsonValue::Object(Default::default());
+        }
+        current = current
+            .as_object_mut()
+            .expect("value was just made an object")
+            .entry(segment)
+            .or_insert(JsonValue::Null);
+    }
+    std::mem::replace(current, value)
+}
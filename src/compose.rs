@@ -0,0 +1,135 @@
+//! Assembling a parent config from child configs that each have their own file, or from
+//! a directory holding one file per field.
+
+use std::path::Path;
+
+use bevy::reflect::Typed;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ConfigFile, JsonValue, LoadConfigError, load_config_file, parse_config, read_config_file, vfs,
+};
+
+/// Extensions of the files read from a [`ConfigFile::FIELD_DIR`], for the formats compiled
+/// in; other files are skipped.
+const FIELD_FILE_EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "yaml")]
+    "yaml",
+    #[cfg(feature = "yaml")]
+    "yml",
+    #[cfg(feature = "json")]
+    "json",
+    #[cfg(feature = "ron")]
+    "ron",
+    #[cfg(feature = "toml")]
+    "toml",
+];
+
+/// A child config loaded from its own file and stored in a field of a parent config.
+///
+/// Declare children with [`ConfigFile::CHILDREN`]. Each child is loaded with
+/// [`load_config_file`], so it honours its own `PATH` and environment override.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ChildConfig, ConfigFile};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// #[derive(Reflect, Serialize, Deserialize)]
+/// struct GameConfig {
+///     title: String,
+///     audio: AudioSettings,
+/// }
+///
+/// impl ConfigFile for GameConfig {
+///     const PATH: &'static str = "assets/config/game.yaml";
+///     const CHILDREN: &'static [ChildConfig] = &[ChildConfig::new::<AudioSettings>("audio")];
+/// }
+/// ```
+pub struct ChildConfig {
+    /// The parent field the child config is stored in.
+    pub field: &'static str,
+    load: fn() -> Result<JsonValue, LoadConfigError>,
+}
+
+impl ChildConfig {
+    /// Declares that the parent field `field` is loaded from the child config `C`.
+    pub const fn new<C>(field: &'static str) -> Self
+    where
+        C: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+    {
+        Self {
+            field,
+            load: load_child::<C>,
+        }
+    }
+}
+
+fn load_child<C>() -> Result<JsonValue, LoadConfigError>
+where
+    C: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    let child = load_config_file::<C>()?;
+    serde_json::to_value(&child).map_err(LoadConfigError::Json)
+}
+
+/// Loads every child in `children` and stores it in the matching field of `parent`.
+pub(crate) fn assemble_children(
+    parent: &mut JsonValue,
+    children: &[ChildConfig],
+) -> Result<(), LoadConfigError> {
+    if !parent.is_object() {
+        *parent = JsonValue::Object(Default::default());
+    }
+    let JsonValue::Object(parent_map) = parent else {
+        unreachable!("parent was just made an object");
+    };
+    for child in children {
+        parent_map.insert(child.field.to_string(), (child.load)()?);
+    }
+    Ok(())
+}
+
+/// Builds an object from the files in `dir`, listed through the installed
+/// [`ConfigFs`](crate::ConfigFs), one field per file, keyed by file stem.
+pub(crate) fn load_field_dir<T: ConfigFile>(dir: &str) -> Result<JsonValue, LoadConfigError> {
+    let mut paths = vfs::read_dir(Path::new(dir)).map_err(LoadConfigError::Io)?;
+    paths.retain(|path| {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                FIELD_FILE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+            })
+    });
+    paths.sort();
+
+    let mut fields = serde_json::Map::new();
+    for path in &paths {
+        let field = path.file_stem().unwrap_or_default().to_string_lossy();
+        if fields.contains_key(field.as_ref()) {
+            return Err(LoadConfigError::DuplicateKey(format!(
+                "{} has more than one file for field {}",
+                dir, field
+            )));
+        }
+        let value = load_field_file::<T>(path)?;
+        fields.insert(field.into_owned(), value);
+    }
+    Ok(JsonValue::Object(fields))
+}
+
+fn load_field_file<T: ConfigFile>(path: &Path) -> Result<JsonValue, LoadConfigError> {
+    let content = read_config_file::<T>(path)?;
+    parse_config(&path.to_string_lossy(), &content)
+}
@@ -0,0 +1,16 @@
+//! Using a config type's [`ConfigFile::LAYER_DEFAULTS`] value as the layer beneath its file.
+
+use serde::Serialize;
+
+use crate::{ConfigFile, JsonValue, LoadConfigError};
+
+/// Returns `true` if `T` sets [`ConfigFile::LAYER_DEFAULTS`].
+pub(crate) fn has_default_layer<T: ConfigFile>() -> bool {
+    T::LAYER_DEFAULTS.is_some()
+}
+
+/// Returns `T`'s [`ConfigFile::LAYER_DEFAULTS`] value as JSON, if it sets one.
+pub(crate) fn default_layer<T: ConfigFile + Serialize>()
+-> Option<Result<JsonValue, LoadConfigError>> {
+    T::LAYER_DEFAULTS.map(|default| serde_json::to_value(default()).map_err(LoadConfigError::Json))
+}
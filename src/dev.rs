@@ -0,0 +1,101 @@
+//! Reloading configs from a hotkey during development.
+
+use bevy::input::ButtonInput;
+use bevy::input::keyboard::KeyCode;
+use bevy::prelude::*;
+use bevy::reflect::{GetTypeRegistration, Typed};
+use serde::{Deserialize, Serialize};
+
+use crate::{ConfigFile, config_file_plugin, overrides};
+
+/// Resource configuring the reload hotkey of configs added with [`config_file_plugin_dev`].
+///
+/// The hotkey is enabled by default in debug builds only. Insert this resource with
+/// `enabled: true` to use it in a release build, or with another `key` to rebind it.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigReloadHotkey {
+    /// The key that reloads every config added with [`config_file_plugin_dev`].
+    pub key: KeyCode,
+    /// Whether pressing [`key`](Self::key) reloads configs.
+    pub enabled: bool,
+}
+
+impl Default for ConfigReloadHotkey {
+    fn default() -> Self {
+        Self {
+            key: KeyCode::F5,
+            enabled: cfg!(debug_assertions),
+        }
+    }
+}
+
+/// Like [`config_file_plugin`], but also reloads `T` when the [`ConfigReloadHotkey`]
+/// (`F5` by default) is pressed, logging which fields changed.
+///
+/// A file that fails to load keeps the previous value and logs the error, so a typo doesn't
+/// end the session.
+///
+/// Keyboard input is read from the `ButtonInput<KeyCode>` resource maintained by Bevy's
+/// `InputPlugin`. In release builds the hotkey does nothing unless
+/// [`ConfigReloadHotkey::enabled`] is set.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, config_file_plugin_dev};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct EnemyTuning {
+///     speed: f32,
+/// }
+///
+/// impl ConfigFile for EnemyTuning {
+///     const PATH: &'static str = "assets/config/enemy_tuning.yaml";
+/// }
+///
+/// App::new()
+///     .add_plugins(config_file_plugin_dev::<EnemyTuning>)
+///     .run();
+/// ```
+pub fn config_file_plugin_dev<T>(app: &mut App)
+where
+    T: Resource
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ConfigFile
+        + Reflect
+        + GetTypeRegistration
+        + Typed,
+{
+    let already_added = app
+        .world()
+        .get_resource::<crate::ConfigRegistry>()
+        .is_some_and(|registry| registry.contains::<T>());
+    config_file_plugin::<T>(app);
+    if already_added {
+        return;
+    }
+
+    app.init_resource::<ConfigReloadHotkey>();
+    app.add_systems(
+        PreUpdate,
+        request_reload_on_hotkey::<T>.after(bevy::input::InputSystems),
+    );
+}
+
+/// Requests a reload of `T` when the hotkey was just pressed, which logs the fields it
+/// changes.
+fn request_reload_on_hotkey<T: ConfigFile>(
+    hotkey: Res<ConfigReloadHotkey>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+) {
+    let pressed = keys.is_some_and(|keys| keys.just_pressed(hotkey.key));
+    if !hotkey.enabled || !pressed {
+        return;
+    }
+
+    overrides::request_reload::<T>("from hotkey");
+}
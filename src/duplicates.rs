@@ -0,0 +1,142 @@
+//! Detecting duplicate mapping keys in YAML config files.
+//!
+//! The YAML parser keeps the last value when a nested mapping repeats a key, which hides
+//! copy-paste mistakes in hand-edited files. With [`ConfigFile::DENY_DUPLICATE_KEYS`], the
+//! file is scanned for repeated keys before it is parsed.
+//!
+//! [`ConfigFile::DENY_DUPLICATE_KEYS`]: crate::ConfigFile::DENY_DUPLICATE_KEYS
+
+use std::collections::HashSet;
+
+use crate::{LoadConfigError, ranges};
+
+/// The keys seen so far in one block mapping.
+struct Mapping {
+    indent: usize,
+    path: String,
+    keys: HashSet<String>,
+}
+
+/// Fails with `LoadConfigError::DuplicateKey` if a block mapping in the YAML `content` of
+/// `path` repeats a key.
+///
+/// Nesting is tracked by indentation, and each sequence item starts a new mapping. Flow
+/// mappings (`{ a: 1 }`) and block scalar contents are not scanned.
+pub(crate) fn check_duplicate_keys(path: &str, content: &str) -> Result<(), LoadConfigError> {
+    let mut mappings: Vec<Mapping> = Vec::new();
+    let mut block_scalar: Option<usize> = None;
+
+    for (number, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if let Some(scalar_indent) = block_scalar {
+            if trimmed.is_empty() || indent > scalar_indent {
+                continue;
+            }
+            block_scalar = None;
+        }
+        if trimmed.starts_with("---") {
+            mappings.clear();
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let (code, _) = ranges::split_comment(trimmed);
+        let (indent, code) = match code.strip_prefix('-') {
+            Some(item) if item.is_empty() || item.starts_with(' ') => {
+                // A sequence item ends every mapping nested in the previous item
+                while mappings
+                    .last()
+                    .is_some_and(|mapping| mapping.indent > indent)
+                {
+                    mappings.pop();
+                }
+                let item_code = item.trim_start();
+                let item_indent = indent + (code.len() - item_code.len());
+                mappings.push(Mapping {
+                    indent: item_indent,
+                    path: mappings.last().map(|m| m.path.clone()).unwrap_or_default(),
+                    keys: HashSet::new(),
+                });
+                (item_indent, item_code)
+            }
+            _ => (indent, code),
+        };
+        let Some((key, value)) = split_key(code) else {
+            continue;
+        };
+
+        while mappings
+            .last()
+            .is_some_and(|mapping| mapping.indent > indent)
+        {
+            mappings.pop();
+        }
+        if mappings
+            .last()
+            .is_none_or(|mapping| mapping.indent < indent)
+        {
+            let path = mappings.last().map(|m| m.path.clone()).unwrap_or_default();
+            mappings.push(Mapping {
+                indent,
+                path,
+                keys: HashSet::new(),
+            });
+        }
+        let mapping = mappings.last_mut().expect("a mapping was just pushed");
+        let key_path = if mapping.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", mapping.path, key)
+        };
+        if !mapping.keys.insert(key.to_string()) {
+            return Err(LoadConfigError::DuplicateKey(format!(
+                "{} (line {} of {})",
+                key_path,
+                number + 1,
+                path
+            )));
+        }
+
+        let value = value.trim();
+        if value.starts_with(['|', '>']) {
+            block_scalar = Some(indent);
+        } else if value.is_empty() {
+            // The value is a nested block, whose keys are prefixed with this one
+            mappings.push(Mapping {
+                indent: indent + 1,
+                path: key_path,
+                keys: HashSet::new(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Splits `key: value` at the colon ending the key, unquoting the key.
+///
+/// Returns `None` if `code` is not a mapping entry, e.g. a plain sequence item or a flow
+/// collection.
+pub(crate) fn split_key(code: &str) -> Option<(&str, &str)> {
+    if code.starts_with(['{', '[']) {
+        return None;
+    }
+    if let Some(quote) = code.chars().next().filter(|c| matches!(c, '"' | '\'')) {
+        let end = code[1..].find(quote)? + 1;
+        let rest = code[end + 1..].trim_start().strip_prefix(':')?;
+        return Some((&code[1..end], rest));
+    }
+    let end = code
+        .char_indices()
+        .find(|&(index, c)| {
+            c == ':'
+                && code[index + 1..]
+                    .chars()
+                    .next()
+                    .is_none_or(char::is_whitespace)
+        })?
+        .0;
+    Some((code[..end].trim_end(), &code[end + 1..]))
+}
@@ -0,0 +1,87 @@
+//! Decrypting config files encrypted at rest with AES-256-GCM.
+//!
+//! An encrypted file holds a 12-byte nonce followed by the ciphertext and its tag. The key
+//! is read from the `CONFIG_KEY` environment variable as 64 hex digits.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+
+use crate::LoadConfigError;
+
+/// The environment variable holding the hex-encoded decryption key.
+pub(crate) const KEY_VAR: &str = "CONFIG_KEY";
+
+const NONCE_LEN: usize = 12;
+
+/// Encrypts config file contents with `key`, in the format read for
+/// [`ConfigFile::ENCRYPTED`](crate::ConfigFile::ENCRYPTED) configs.
+///
+/// A fresh random nonce is used for every call. Write the returned bytes to the config file
+/// as they are.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_config_file::encrypt_config;
+///
+/// let key = [7u8; 32];
+/// let bytes = encrypt_config("port: 7777\n", &key);
+/// std::fs::write(std::env::temp_dir().join("server.yaml"), bytes).unwrap();
+/// ```
+pub fn encrypt_config(plaintext: &str, key: &[u8; 32]) -> Vec<u8> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .expect("encrypting an in-memory buffer cannot fail");
+    nonce.into_iter().chain(ciphertext).collect()
+}
+
+/// Encrypts the contents of a config file with the key from [`KEY_VAR`].
+pub(crate) fn encrypt(plaintext: &str) -> Result<Vec<u8>, LoadConfigError> {
+    Ok(encrypt_config(plaintext, &env_key()?))
+}
+
+/// Decrypts the contents of an encrypted config file with the key from [`KEY_VAR`].
+pub(crate) fn decrypt(bytes: &[u8]) -> Result<String, LoadConfigError> {
+    let key = env_key()?;
+    if bytes.len() < NONCE_LEN {
+        return Err(LoadConfigError::Decrypt(
+            "the file is too short to be encrypted".to_string(),
+        ));
+    }
+
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| {
+            LoadConfigError::Decrypt(
+                "the key is wrong or the file has been tampered with".to_string(),
+            )
+        })?;
+    String::from_utf8(plaintext)
+        .map_err(|_| LoadConfigError::Decrypt("the decrypted file is not UTF-8".to_string()))
+}
+
+/// Reads the key from [`KEY_VAR`].
+fn env_key() -> Result<[u8; 32], LoadConfigError> {
+    let key = std::env::var(KEY_VAR)
+        .map_err(|_| LoadConfigError::Decrypt(format!("{} is not set", KEY_VAR)))?;
+    parse_key(&key)
+}
+
+/// Parses a 32-byte key from 64 hex digits.
+fn parse_key(hex: &str) -> Result<[u8; 32], LoadConfigError> {
+    let invalid = || LoadConfigError::Decrypt(format!("{} must be 64 hex digits", KEY_VAR));
+    let hex = hex.trim();
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut key = [0; 32];
+    for (byte, digits) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let digits = std::str::from_utf8(digits).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| invalid())?;
+    }
+    Ok(key)
+}
@@ -0,0 +1,58 @@
+//! A project-wide table of override variable names, consulted before each type's own
+//! naming consts.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+static ENV_VAR_NAMES: LazyLock<RwLock<HashMap<TypeId, String>>> = LazyLock::new(Default::default);
+
+/// Replaces the table mapping config types to the environment variables their overrides
+/// are read from.
+///
+/// A type in the table reads its override from the mapped variable, in place of the name
+/// derived from [`ConfigFile::ENV_PREFIX`](crate::ConfigFile::ENV_PREFIX),
+/// [`ConfigFile::APP_ID_VAR`](crate::ConfigFile::APP_ID_VAR) and
+/// [`ConfigFile::ENV_VAR`](crate::ConfigFile::ENV_VAR). Per-field variables, such as
+/// `{name}__pan_speed`, follow the mapped name. This keeps the naming policy of a large
+/// project in one place; set the table before loading any config.
+///
+/// # Example
+///
+/// ```rust
+/// use std::any::TypeId;
+/// use std::collections::HashMap;
+///
+/// use bevy_config_file::set_env_var_names;
+///
+/// struct CameraSettings;
+/// struct AudioSettings;
+///
+/// set_env_var_names(HashMap::from([
+///     (TypeId::of::<CameraSettings>(), "MYGAME_CAMERA".to_string()),
+///     (TypeId::of::<AudioSettings>(), "MYGAME_AUDIO".to_string()),
+/// ]));
+/// ```
+pub fn set_env_var_names(names: HashMap<TypeId, String>) {
+    *ENV_VAR_NAMES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = names;
+}
+
+/// Maps `T` to the environment variable `name` in the table set with
+/// [`set_env_var_names`], keeping the other entries.
+pub fn set_env_var_name<T: 'static>(name: impl Into<String>) {
+    ENV_VAR_NAMES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(TypeId::of::<T>(), name.into());
+}
+
+/// Returns the variable `T` is mapped to, if it has an entry.
+pub(crate) fn mapped_env_var_name<T: 'static>() -> Option<String> {
+    ENV_VAR_NAMES
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&TypeId::of::<T>())
+        .cloned()
+}
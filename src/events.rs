@@ -0,0 +1,108 @@
+//! Events and messages describing changes to config resources.
+
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{ConfigFile, locale, short_type_name};
+
+/// Observer event triggered whenever the config resource `T` is inserted or reloaded.
+///
+/// The resource is already present in the world when observers run.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, OnConfigChanged, config_file_plugin};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct InputSettings {
+///     sensitivity: f32,
+/// }
+///
+/// impl ConfigFile for InputSettings {
+///     const PATH: &'static str = "assets/config/input.yaml";
+/// }
+///
+/// App::new()
+///     .add_plugins(config_file_plugin::<InputSettings>)
+///     .add_observer(|_: On<OnConfigChanged<InputSettings>>, settings: Res<InputSettings>| {
+///         info!("sensitivity is now {}", settings.sensitivity);
+///     })
+///     .run();
+/// ```
+#[derive(Event)]
+pub struct OnConfigChanged<T> {
+    /// The path the config was read from, after locale substitution and
+    /// [`ConfigFile::resolve_path`].
+    pub path: String,
+    /// `true` if an existing resource was reloaded, `false` on first insertion.
+    pub reloaded: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ConfigFile> OnConfigChanged<T> {
+    pub(crate) fn new(reloaded: bool) -> Self {
+        Self {
+            path: locale::config_path::<T>(),
+            reloaded,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Message written once when the config resource `T` is first inserted, for systems that
+/// wait for a config to become available.
+///
+/// Unlike [`OnConfigChanged`], it is not written again on reloads.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, ConfigLoaded, config_file_plugin};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct InputSettings {
+///     sensitivity: f32,
+/// }
+///
+/// impl ConfigFile for InputSettings {
+///     const PATH: &'static str = "assets/config/input.yaml";
+/// }
+///
+/// fn rebuild_bindings_ui(mut loaded: MessageReader<ConfigLoaded<InputSettings>>) {
+///     for message in loaded.read() {
+///         info!("{} loaded from {}", message.type_name, message.path);
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(config_file_plugin::<InputSettings>)
+///     .add_systems(Update, rebuild_bindings_ui)
+///     .run();
+/// ```
+#[derive(Message)]
+pub struct ConfigLoaded<T> {
+    /// The short type name of the config, e.g. `InputSettings`.
+    pub type_name: &'static str,
+    /// The path the config was read from, after locale substitution and
+    /// [`ConfigFile::resolve_path`].
+    pub path: String,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ConfigFile> ConfigLoaded<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            type_name: short_type_name::<T>(),
+            path: locale::config_path::<T>(),
+            _marker: PhantomData,
+        }
+    }
+}
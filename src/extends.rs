@@ -0,0 +1,110 @@
+//! Loading config files that inherit from a base file with `extends:` or pull in other
+//! files with `includes:`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::merge::merge_json;
+use crate::{JsonValue, LoadConfigError, parse_config};
+
+const EXTENDS_KEY: &str = "extends";
+const INCLUDES_KEY: &str = "includes";
+
+/// Which layering keys are honoured while loading a file.
+#[derive(Clone, Copy)]
+pub(crate) struct Layering {
+    pub(crate) extends: bool,
+    pub(crate) includes: bool,
+    /// Reads a file's contents, decrypting it if needed.
+    pub(crate) read: fn(&Path) -> Result<String, LoadConfigError>,
+}
+
+/// Loads the file at `path`, recursively loading and merging the files it extends or
+/// includes.
+///
+/// Paths are resolved relative to the directory of the file declaring them. The `extends`
+/// base is merged first, then each include in order, and finally the declaring file itself,
+/// each deep-merged on top of the previous layers. Cycles are reported as errors.
+pub(crate) fn load_layered(path: &str, layering: Layering) -> Result<JsonValue, LoadConfigError> {
+    load_chain(PathBuf::from(path), layering, &mut Vec::new())
+}
+
+fn load_chain(
+    path: PathBuf,
+    layering: Layering,
+    chain: &mut Vec<PathBuf>,
+) -> Result<JsonValue, LoadConfigError> {
+    // Compare canonical paths so `./base.yaml` and `base.yaml` are the same file. Files
+    // served by a `ConfigFs` may not exist on disk, so they are compared as written.
+    let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+    if chain.contains(&canonical) {
+        let cycle: Vec<String> = chain
+            .iter()
+            .chain([&canonical])
+            .map(|path| path.display().to_string())
+            .collect();
+        return Err(LoadConfigError::Extends(format!(
+            "cycle detected: {}",
+            cycle.join(" -> ")
+        )));
+    }
+    chain.push(canonical);
+
+    let content = (layering.read)(&path)?;
+    let mut value: JsonValue = parse_config(&path.to_string_lossy(), &content)?;
+    let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+    let mut layers = Vec::new();
+    if layering.extends {
+        if let Some(base) = take_key(&mut value, EXTENDS_KEY) {
+            let JsonValue::String(base_path) = base else {
+                return Err(invalid_key(EXTENDS_KEY, &path, "a file path"));
+            };
+            layers.push(dir.join(base_path));
+        }
+    }
+    if layering.includes {
+        if let Some(includes) = take_key(&mut value, INCLUDES_KEY) {
+            let JsonValue::Array(includes) = includes else {
+                return Err(invalid_key(INCLUDES_KEY, &path, "a list of file paths"));
+            };
+            for include in includes {
+                let JsonValue::String(include_path) = include else {
+                    return Err(invalid_key(INCLUDES_KEY, &path, "a list of file paths"));
+                };
+                layers.push(dir.join(include_path));
+            }
+        }
+    }
+
+    let mut merged: Option<JsonValue> = None;
+    for layer_path in layers {
+        let layer = load_chain(layer_path, layering, chain)?;
+        match &mut merged {
+            Some(merged) => merge_json(merged, layer),
+            None => merged = Some(layer),
+        }
+    }
+
+    chain.pop();
+    Ok(match merged {
+        Some(mut merged) => {
+            merge_json(&mut merged, value);
+            merged
+        }
+        None => value,
+    })
+}
+
+fn take_key(value: &mut JsonValue, key: &str) -> Option<JsonValue> {
+    value.as_object_mut().and_then(|map| map.remove(key))
+}
+
+fn invalid_key(key: &str, path: &Path, expected: &str) -> LoadConfigError {
+    LoadConfigError::Extends(format!(
+        "`{}` in {} must be {}",
+        key,
+        path.display(),
+        expected
+    ))
+}
@@ -0,0 +1,100 @@
+//! Adding a field's doc comment to the errors it causes.
+
+use bevy::reflect::TypeInfo;
+
+use crate::{LoadConfigError, attributes};
+
+/// Wraps `error` in `LoadConfigError::FieldHelp` if it names a missing or mistyped field of
+/// `T` that has a doc comment.
+///
+/// Only types whose reflection info is known have field docs; errors for other types are
+/// returned unchanged.
+pub(crate) fn with_field_help<T: 'static>(error: LoadConfigError) -> LoadConfigError {
+    let Some(info) = attributes::type_info::<T>() else {
+        return error;
+    };
+    let Some(field) = parse_error_message(&error) else {
+        return error;
+    };
+    let segments: Vec<&str> = field.split('.').collect();
+    let docs = field_docs(info, &segments).or_else(|| match segments[..] {
+        // Errors from JSON values don't say where the field was nested
+        [name] => find_field_docs(info, name),
+        _ => None,
+    });
+    match docs {
+        Some(help) => LoadConfigError::FieldHelp {
+            field,
+            help,
+            error: Box::new(error),
+        },
+        None => error,
+    }
+}
+
+/// Extracts the `.`-separated path of the field a deserialization error is about.
+fn parse_error_message(error: &LoadConfigError) -> Option<String> {
+    let message = match error {
+        #[cfg(feature = "yaml")]
+        LoadConfigError::Yaml(e) => e.to_string(),
+        LoadConfigError::Json(e) => e.to_string(),
+        LoadConfigError::OverrideIgnored { error, .. } => return parse_error_message(error),
+        _ => return None,
+    };
+    // YAML errors are prefixed with the path of the offending value, e.g. `camera.zoom: `
+    let (prefix, rest) = match message.split_once(": ") {
+        Some((prefix, rest)) if is_field_path(prefix) => (Some(prefix), rest),
+        _ => (None, message.as_str()),
+    };
+    if let Some(missing) = rest.strip_prefix("missing field `") {
+        let name = missing.split('`').next()?;
+        return Some(match prefix {
+            Some(prefix) => format!("{}.{}", prefix, name),
+            None => name.to_string(),
+        });
+    }
+    if rest.starts_with("invalid type") || rest.starts_with("invalid value") {
+        return prefix.map(str::to_string);
+    }
+    None
+}
+
+fn is_field_path(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.'))
+}
+
+/// Returns the doc comment of the field at `segments` in `info`, following nested structs.
+fn field_docs(info: &TypeInfo, segments: &[&str]) -> Option<String> {
+    let (name, rest) = segments.split_first()?;
+    let field = info.as_struct().ok()?.field(name)?;
+    if rest.is_empty() {
+        field.docs().map(clean_docs)
+    } else {
+        field_docs(field.type_info()?, rest)
+    }
+}
+
+/// Returns the doc comment of the first documented field called `name`, searching nested
+/// structs depth first.
+fn find_field_docs(info: &TypeInfo, name: &str) -> Option<String> {
+    let info = info.as_struct().ok()?;
+    info.iter().find_map(|field| {
+        if field.name() == name {
+            field.docs().map(clean_docs)
+        } else {
+            find_field_docs(field.type_info()?, name)
+        }
+    })
+}
+
+/// Joins the lines of a doc comment into one line.
+fn clean_docs(docs: &str) -> String {
+    docs.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,77 @@
+//! Field metadata for generating settings menus.
+
+use bevy::reflect::{Reflect, TypeInfo, Typed};
+use serde::Serialize;
+
+use crate::JsonValue;
+use crate::attributes::{Max, Min};
+
+/// Describes one field of a config, as returned by [`config_field_info`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldInfo {
+    /// The `.`-separated path to the field, e.g. `graphics.shadow_quality`.
+    pub name: String,
+    /// The fully qualified name of the field's type, e.g. `f32` or `alloc::string::String`.
+    pub type_path: &'static str,
+    /// The field's current value.
+    pub value: JsonValue,
+    /// The field's [`Min`] attribute, if any.
+    pub min: Option<f64>,
+    /// The field's [`Max`] attribute, if any.
+    pub max: Option<f64>,
+}
+
+/// Lists the fields of `value`, with their types, current values and [`Min`]/[`Max`]
+/// attributes, so a settings UI can pick a widget for each.
+///
+/// Fields whose type is itself a struct are listed by their own fields instead, so only
+/// leaf values are returned, in declaration order. Returns an empty list if `T` isn't a
+/// struct.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_config_file::attributes::{Max, Min};
+/// use bevy_config_file::config_field_info;
+/// use serde::Serialize;
+///
+/// #[derive(Reflect, Serialize)]
+/// struct AudioSettings {
+///     #[reflect(@Min(0.0), @Max(1.0))]
+///     volume: f32,
+///     muted: bool,
+/// }
+///
+/// let fields = config_field_info(&AudioSettings { volume: 0.5, muted: false });
+/// assert_eq!(fields[0].name, "volume");
+/// assert_eq!(fields[0].max, Some(1.0));
+/// ```
+pub fn config_field_info<T: Reflect + Typed + Serialize>(value: &T) -> Vec<FieldInfo> {
+    let json = serde_json::to_value(value).unwrap_or_default();
+    let mut fields = Vec::new();
+    collect_fields(T::type_info(), "", &json, &mut fields);
+    fields
+}
+
+fn collect_fields(info: &TypeInfo, prefix: &str, json: &JsonValue, fields: &mut Vec<FieldInfo>) {
+    let Ok(info) = info.as_struct() else {
+        return;
+    };
+    for field in info.iter() {
+        let name = format!("{}{}", prefix, field.name());
+        let value = json.get(field.name()).cloned().unwrap_or_default();
+        match field.type_info() {
+            Some(nested) if nested.as_struct().is_ok() => {
+                collect_fields(nested, &format!("{}.", name), &value, fields);
+            }
+            _ => fields.push(FieldInfo {
+                name,
+                type_path: field.type_path(),
+                value,
+                min: field.get_attribute::<Min>().map(|min| min.0),
+                max: field.get_attribute::<Max>().map(|max| max.0),
+            }),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Reloading configs when their files change on disk.
+
+use std::ffi::OsString;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy::reflect::{GetTypeRegistration, Typed};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::{ConfigFile, config_file_plugin, locale, overrides};
+
+/// How long a file must go without further changes before it is reloaded, since editors
+/// often write a file several times when saving.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Watches the directories of `T`'s config files.
+#[derive(Resource)]
+struct ConfigWatcher<T> {
+    _watcher: RecommendedWatcher,
+    events: Mutex<Receiver<notify::Result<notify::Event>>>,
+    file_names: Vec<OsString>,
+    /// The name of `T`'s [`ConfigFile::FIELD_DIR`], any file in which counts as a change.
+    field_dir_name: Option<OsString>,
+    changed_at: Option<Instant>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Like [`config_file_plugin`], but also reloads `T` whenever its config file changes on
+/// disk, logging which fields changed.
+///
+/// The file's directory is watched with the `notify` crate, so files replaced by an
+/// editor's atomic save are picked up too. A reload waits until the file has been quiet
+/// for 100ms, and applies overrides as usual, so they keep winning over file edits. A file
+/// that fails to load, such as a half-written save, keeps the previous value and logs the
+/// error. `extends:` and `includes:` files are not watched. With
+/// [`FALLBACK_PATHS`](ConfigFile::FALLBACK_PATHS), `T::PATH` and every fallback are watched,
+/// so creating the file at `PATH` switches to it.
+///
+/// To watch a config added by another plugin, add this plugin after it. With
+/// [`LAYER_PATHS`](ConfigFile::LAYER_PATHS), the directories of every layer are watched, so
+/// creating or deleting a layer such as `settings.local.yaml` reloads the config too. With
+/// [`FIELD_DIR`](ConfigFile::FIELD_DIR), the directory itself is watched, so adding,
+/// editing or removing any field file reloads the config.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, config_file_hot_reload_plugin};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct CameraSettings {
+///     pan_speed: f32,
+/// }
+///
+/// impl ConfigFile for CameraSettings {
+///     const PATH: &'static str = "assets/config/camera_settings.yaml";
+/// }
+///
+/// App::new()
+///     .add_plugins(config_file_hot_reload_plugin::<CameraSettings>)
+///     .run();
+/// ```
+pub fn config_file_hot_reload_plugin<T>(app: &mut App)
+where
+    T: Resource
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ConfigFile
+        + Reflect
+        + GetTypeRegistration
+        + Typed,
+{
+    let already_added = app
+        .world()
+        .get_resource::<crate::ConfigRegistry>()
+        .is_some_and(|registry| registry.contains::<T>());
+    if !already_added {
+        config_file_plugin::<T>(app);
+    }
+    if app.world().contains_resource::<ConfigWatcher<T>>() {
+        return;
+    }
+
+    match watch::<T>() {
+        Ok(watcher) => {
+            app.insert_resource(watcher);
+            app.add_systems(PreUpdate, request_reload_on_change::<T>);
+        }
+        Err(error) => {
+            #[cfg(feature = "logging")]
+            warn!(
+                "can't watch config file {} for changes: {}",
+                locale::config_path::<T>(),
+                error
+            );
+            #[cfg(not(feature = "logging"))]
+            let _ = error;
+        }
+    }
+}
+
+/// Starts watching the directories of `T`'s config files.
+fn watch<T: ConfigFile>() -> notify::Result<ConfigWatcher<T>> {
+    let paths = locale::watched_paths::<T>();
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    let mut directories: Vec<&Path> = Vec::new();
+    let mut first_error = None;
+    let mut file_names = Vec::new();
+    for path in &paths {
+        let path = Path::new(path);
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        file_names.push(path.file_name().unwrap_or_default().to_os_string());
+        if directories.contains(&directory) {
+            continue;
+        }
+        // A missing directory (such as the user's, before anything is saved) is skipped as
+        // long as another one can be watched
+        match watcher.watch(directory, RecursiveMode::NonRecursive) {
+            Ok(()) => directories.push(directory),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+    let field_dir = locale::field_dir::<T>().map(PathBuf::from);
+    if let Some(dir) = &field_dir {
+        match watcher.watch(dir, RecursiveMode::NonRecursive) {
+            Ok(()) => directories.push(dir),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+    if let Some(error) = first_error.filter(|_| directories.is_empty()) {
+        return Err(error);
+    }
+
+    Ok(ConfigWatcher {
+        _watcher: watcher,
+        events: Mutex::new(receiver),
+        file_names,
+        field_dir_name: field_dir.and_then(|dir| dir.file_name().map(OsString::from)),
+        changed_at: None,
+        _marker: PhantomData,
+    })
+}
+
+/// Requests a reload of `T` once its files have stopped changing, which logs the fields it
+/// changes.
+fn request_reload_on_change<T: ConfigFile>(mut watcher: ResMut<ConfigWatcher<T>>) {
+    let watcher = &mut *watcher;
+    let events = watcher
+        .events
+        .get_mut()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let changed = events.try_iter().fold(false, |changed, event| {
+        let touches_file = event.is_ok_and(|event| {
+            !event.kind.is_access()
+                && event.paths.iter().any(|path| {
+                    let in_field_dir = path
+                        .parent()
+                        .and_then(Path::file_name)
+                        .is_some_and(|dir| watcher.field_dir_name.as_deref() == Some(dir));
+                    let watched = path
+                        .file_name()
+                        .is_some_and(|name| watcher.file_names.iter().any(|file| file == name));
+                    in_field_dir || watched
+                })
+        });
+        changed || touches_file
+    });
+    if changed {
+        watcher.changed_at = Some(Instant::now());
+    }
+
+    if watcher
+        .changed_at
+        .is_none_or(|changed_at| changed_at.elapsed() < DEBOUNCE)
+    {
+        return;
+    }
+    watcher.changed_at = None;
+
+    overrides::request_reload::<T>("after the file changed");
+}
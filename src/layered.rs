@@ -0,0 +1,46 @@
+//! Loading a config from several files, each merged over the ones before it.
+
+use std::path::Path;
+
+use crate::{ConfigFile, JsonValue, LoadConfigError, load_base_file, locale, merge, vfs};
+
+/// Loads and merges every existing file in `T`'s [`ConfigFile::LAYER_PATHS`], in order.
+///
+/// Each path is resolved like [`ConfigFile::PATH`], ignoring the locale and variant
+/// variables unless `read_env` is set. A missing layer file is skipped, but a missing
+/// `extends:` or `includes:` target of an existing layer is an error. If none of the files
+/// exist, the error for the first one is returned.
+pub(crate) fn load_layers<T: ConfigFile>(read_env: bool) -> Result<JsonValue, LoadConfigError> {
+    let paths = if read_env {
+        locale::layer_paths::<T>()
+    } else {
+        locale::env_free_layer_paths::<T>()
+    };
+    let mut merged: Option<JsonValue> = None;
+    let mut first_missing = None;
+    for path in paths {
+        let layer = match load_base_file::<T, JsonValue>(&path) {
+            Ok(layer) => layer,
+            Err(LoadConfigError::Io(error))
+                if error.kind() == std::io::ErrorKind::NotFound
+                    && !vfs::exists(Path::new(&path)) =>
+            {
+                first_missing.get_or_insert(error);
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+        match &mut merged {
+            Some(merged) => merge::merge_json(merged, layer),
+            None => merged = Some(layer),
+        }
+    }
+    match (merged, first_missing) {
+        (Some(merged), _) => Ok(merged),
+        (None, Some(error)) => Err(LoadConfigError::Io(error)),
+        (None, None) => Err(LoadConfigError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "the config has no layered paths",
+        ))),
+    }
+}
@@ -8,18 +8,28 @@
 //!
 //! - Load configuration from YAML, JSON, or RON files at startup
 //! - Format is detected automatically from the file extension in [`ConfigFile::PATH`]
-//! - Override configuration values using environment variables (always JSON)
+//! - Override configuration values using environment variables (JSON, or YAML if enabled)
 //! - Automatic resource registration with Bevy's reflection system
 //! - Type-safe configuration with serde deserialization
 //!
 //! ## Cargo Features
 //!
-//! | Feature   | Default | Description                              |
-//! |-----------|---------|------------------------------------------|
-//! | `yaml`    | yes     | YAML config support (`.yaml`, `.yml`)    |
-//! | `json`    | no      | JSON config support (`.json`)            |
-//! | `ron`     | no      | RON config support (`.ron`)              |
-//! | `logging` | yes     | Log config loading events                |
+//! | Feature      | Default | Description                                     |
+//! |--------------|---------|-------------------------------------------------|
+//! | `yaml`       | yes     | YAML config support (`.yaml`, `.yml`)           |
+//! | `json`       | no      | JSON config support (`.json`)                   |
+//! | `ron`        | no      | RON config support (`.ron`)                     |
+//! | `toml`       | no      | TOML config support (`.toml`)                   |
+//! | `logging`    | yes     | Log config loading events                       |
+//! | `inventory`  | no      | Collect config types with `submit_config_file!` |
+//! | `rayon`      | no      | Parallel loading with `load_configs_parallel`   |
+//! | `encryption` | no      | AES-GCM encrypted files with `ENCRYPTED`        |
+//! | `checksum`   | no      | SHA-256 sidecar checks with `VERIFY_CHECKSUM`   |
+//! | `dev`        | no      | F5-to-reload with `config_file_plugin_dev`      |
+//! | `field_docs` | no      | Field doc comments in deserialization errors    |
+//! | `hot_reload` | no      | Reload when files change, via `notify`          |
+//! | `dirs`       | no      | Per-user config paths with `USE_PLATFORM_DIR`   |
+//! | `asset`      | no      | Load through the asset server, for web builds   |
 //!
 //! At least one format feature must be enabled. To use multiple formats:
 //! ```toml
@@ -73,28 +83,148 @@
 //! (pan_speed: 1000.0, zoom_speed: 1.0)
 //! ```
 //!
+//! **TOML** (`camera_settings.toml`):
+//! ```toml
+//! pan_speed = 1000.0
+//! zoom_speed = 1.0
+//! ```
+//!
 //! # Environment Variable Overrides
 //!
 //! You can override configuration values at runtime using environment variables.
-//! Overrides are **always JSON**, regardless of the config file format:
+//! Overrides are **JSON** by default, regardless of the config file format:
 //!
 //! ```bash
 //! CONFIG_CameraSettings='{"pan_speed": 2000.0}' ./game
 //! ```
 //!
 //! The environment variable name is `CONFIG_{TypeName}` where `TypeName` is the last
-//! component of the type's fully qualified name.
+//! component of the type's fully qualified name. The prefix can be changed with
+//! [`ConfigFile::ENV_PREFIX`].
+//!
+//! Single fields can be set with `CONFIG_CameraSettings__pan_speed=2000`, which wins over
+//! the whole-object variable. Individual fields can also be overridden from the command
+//! line, after the environment:
+//!
+//! ```bash
+//! ./game --config CameraSettings.pan_speed=2000
+//! ```
+//!
+//! Set [`ConfigFile::ENV_FORMAT`] to [`OverrideFormat::Yaml`] to write whole-object
+//! overrides in YAML instead, such as `CONFIG_CameraSettings='pan_speed: 2000.0'`.
+//!
+//! Where environment variables aren't available (e.g. WASM), use [`set_override`] to
+//! set the override in memory; the resource is reloaded on the next update.
 
-#[cfg(not(any(feature = "yaml", feature = "json", feature = "ron")))]
+#[cfg(not(any(feature = "yaml", feature = "json", feature = "ron", feature = "toml")))]
 compile_error!(
-    "At least one config format feature must be enabled (yaml, json, or ron). \
+    "At least one config format feature must be enabled (yaml, json, ron, or toml). \
      Enable a format in your Cargo.toml: features = [\"yaml\"]"
 );
 
-use bevy::{prelude::*, reflect::GetTypeRegistration};
+#[cfg(feature = "asset")]
+mod asset;
+pub mod attributes;
+mod capture;
+#[cfg(feature = "checksum")]
+mod checksum;
+mod cli;
+mod compose;
+mod defaults;
+#[cfg(feature = "dev")]
+mod dev;
+mod duplicates;
+#[cfg(feature = "encryption")]
+mod encryption;
+mod env_names;
+mod events;
+mod extends;
+#[cfg(feature = "field_docs")]
+mod field_docs;
+mod field_info;
+#[cfg(feature = "hot_reload")]
+mod hot_reload;
+mod layered;
+mod load_errors;
+mod locale;
+mod merge;
+mod meta;
+mod mutation;
+mod operators;
+mod overrides;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod plugins;
+mod pretty;
+mod ranges;
+mod redact;
+mod references;
+mod registry;
+mod reload;
+mod report;
+mod save;
+mod schema;
+mod sections;
+mod shape;
+#[cfg(feature = "inventory")]
+mod submit;
+mod substitution;
+mod telemetry;
+mod validated;
+mod versions;
+mod vfs;
+#[cfg(feature = "yaml")]
+mod yaml_patch;
+
+#[cfg(feature = "asset")]
+pub use asset::{ConfigAsset, ConfigLoadFailed, config_file_asset_plugin};
+pub use capture::{CapturedConfig, ConfigCapture};
+#[cfg(feature = "checksum")]
+pub use checksum::write_checksum_file;
+pub use cli::set_cli_args;
+pub use compose::ChildConfig;
+#[cfg(feature = "dev")]
+pub use dev::{ConfigReloadHotkey, config_file_plugin_dev};
+#[cfg(feature = "encryption")]
+pub use encryption::encrypt_config;
+pub use env_names::{set_env_var_name, set_env_var_names};
+pub use events::{ConfigLoaded, OnConfigChanged};
+pub use field_info::{FieldInfo, config_field_info};
+#[cfg(feature = "hot_reload")]
+pub use hot_reload::config_file_hot_reload_plugin;
+pub use load_errors::ConfigLoadErrors;
+pub use merge::{MergeStrategy, merge_config_into_resource, merge_json};
+pub use meta::ConfigMeta;
+pub use mutation::config_mutation_check_plugin;
+pub use overrides::{
+    OverrideFormat, clear_override, clear_override_field, config_as_override_string,
+    preflight_overrides, set_override,
+};
+#[cfg(feature = "rayon")]
+pub use parallel::load_configs_parallel;
+pub use plugins::ConfigFilePlugins;
+pub use pretty::config_pretty_string;
+pub use registry::{ConfigRegistry, RegisteredConfig};
+pub use reload::{
+    ConfigGroup, ReloadConfig, ReloadConfigGroup, RestartRequired, reset_config_file,
+};
+pub use report::{ConfigLoadReport, LoadMeta, load_config_file_with_meta, load_config_report};
+pub use save::save_config_file;
+pub use schema::{config_json_schema, write_config_json_schema};
+pub use sections::__App;
+#[cfg(feature = "inventory")]
+pub use submit::{__inventory, ConfigRegistration, all_config_files_plugin};
+pub use telemetry::{ConfigCounts, ConfigTelemetry};
+pub use validated::{ValidatedConfig, config_file_validated_plugin};
+pub use vfs::{ConfigFs, clear_config_fs, set_config_fs};
+
+use bevy::prelude::*;
+use bevy::reflect::{GetTypeRegistration, Typed};
 use serde::{Deserialize, Serialize};
-use serde_json::Value as JsonValue;
-use std::{env, fs};
+use std::fs;
+use std::path::Path;
+
+pub use serde_json::Value as JsonValue;
 
 /// Errors that can occur when loading configuration files.
 #[derive(Debug)]
@@ -107,10 +237,106 @@ pub enum LoadConfigError {
     /// Error parsing RON content
     #[cfg(feature = "ron")]
     Ron(ron::error::SpannedError),
+    /// Error parsing TOML content
+    #[cfg(feature = "toml")]
+    Toml(toml::de::Error),
+    /// Error serializing a config with [`save_config_file`], for formats without their own
+    /// variant
+    Serialize(String),
     /// Error reading the configuration file
     Io(std::io::Error),
     /// The file extension is not a supported config format
     UnsupportedFormat(String),
+    /// The shared environment document has no value under the configured key
+    EnvDocKeyMissing {
+        /// The environment variable holding the shared document
+        var: String,
+        /// The key that was not found
+        key: String,
+    },
+    /// The override is valid JSON but not an object, while the config is an object
+    OverrideNotObject {
+        /// Where the override came from, e.g. the environment variable name
+        override_source: String,
+        /// The kind of JSON value the override contained, e.g. "a number"
+        found: &'static str,
+    },
+    /// A `${self.path}` reference could not be resolved
+    Reference(String),
+    /// An `extends:` or `includes:` file is invalid or the chain of files is cyclic
+    Extends(String),
+    /// The config has no field at the given `.`-separated path
+    FieldNotFound(String),
+    /// A `$mul` or `$add` override could not be applied
+    InvalidOperator(String),
+    /// The config file has no value under [`ConfigFile::ROOT_KEY`]
+    RootKeyMissing {
+        /// The config file that was read
+        path: String,
+        /// The root key that was not found
+        key: String,
+    },
+    /// The config file failed to load while an override was set, so the override was ignored
+    OverrideIgnored {
+        /// Where the ignored override came from, e.g. the environment variable name
+        override_source: String,
+        /// The error that prevented the config file from loading
+        error: Box<LoadConfigError>,
+    },
+    /// A config value failed validation
+    Validation(String),
+    /// The loaded config broke one or more of its [`ConfigFile::validate_invariants`] rules
+    InvariantViolations(Vec<String>),
+    /// The override has fields that don't fit the config type
+    IncompatibleOverride {
+        /// Where the override came from, e.g. the environment variable name
+        override_source: String,
+        /// One description per incompatible field, such as an unknown field or a mismatched type
+        problems: Vec<String>,
+    },
+    /// Reading the config file took longer than [`ConfigFile::READ_TIMEOUT_MS`]
+    Timeout {
+        /// The config file that was being read
+        path: String,
+        /// The timeout that was exceeded, in milliseconds
+        timeout_ms: u64,
+    },
+    /// An encrypted config file could not be decrypted
+    #[cfg(feature = "encryption")]
+    Decrypt(String),
+    /// A config file doesn't match its `.sha256` sidecar, with [`ConfigFile::VERIFY_CHECKSUM`]
+    #[cfg(feature = "checksum")]
+    Checksum(String),
+    /// A YAML mapping repeats a key, with [`ConfigFile::DENY_DUPLICATE_KEYS`]
+    DuplicateKey(String),
+    /// The config file's schema version is outside the supported range
+    SchemaVersion {
+        /// The version recorded in the file
+        found: u32,
+        /// The oldest supported version, [`ConfigFile::MIN_SCHEMA_VERSION`]
+        min: u32,
+        /// The newest supported version, [`ConfigFile::MAX_SCHEMA_VERSION`] or
+        /// [`ConfigFile::SCHEMA_VERSION`]
+        max: u32,
+    },
+    /// A `${VAR}` token names an unset environment variable, with
+    /// [`ConfigFile::EXPAND_ENV_VARS`]
+    MissingEnvVar {
+        /// The unset environment variable
+        var: String,
+        /// The config file containing the token
+        path: String,
+    },
+    /// A field was missing or had the wrong type; `help` is the field's doc comment
+    #[cfg(feature = "field_docs")]
+    FieldHelp {
+        /// The `.`-separated path to the field
+        field: String,
+        /// The field's doc comment, joined into one line
+        help: String,
+        /// The deserialization error
+        error: Box<LoadConfigError>,
+    },
 }
 
 impl std::fmt::Display for LoadConfigError {
@@ -121,10 +347,94 @@ impl std::fmt::Display for LoadConfigError {
             LoadConfigError::Json(e) => write!(f, "JSON parsing error: {}", e),
             #[cfg(feature = "ron")]
             LoadConfigError::Ron(e) => write!(f, "RON parsing error: {}", e),
+            #[cfg(feature = "toml")]
+            LoadConfigError::Toml(e) => write!(f, "TOML parsing error: {}", e),
             LoadConfigError::Io(e) => write!(f, "IO error: {}", e),
             LoadConfigError::UnsupportedFormat(ext) => {
                 write!(f, "Unsupported config file format: .{}", ext)
             }
+            LoadConfigError::EnvDocKeyMissing { var, key } => {
+                write!(
+                    f,
+                    "Key \"{}\" not found in the JSON document in {}",
+                    key, var
+                )
+            }
+            LoadConfigError::OverrideNotObject {
+                override_source,
+                found,
+            } => write!(
+                f,
+                "Override in {} must be a JSON object of fields to override, found {}",
+                override_source, found
+            ),
+            LoadConfigError::Reference(message) => {
+                write!(f, "Invalid config reference: {}", message)
+            }
+            LoadConfigError::Extends(message) => {
+                write!(f, "Invalid config inheritance: {}", message)
+            }
+            LoadConfigError::FieldNotFound(field) => {
+                write!(f, "Config has no field \"{}\"", field)
+            }
+            LoadConfigError::InvalidOperator(message) => {
+                write!(f, "Invalid override operator: {}", message)
+            }
+            LoadConfigError::RootKeyMissing { path, key } => {
+                write!(f, "Root key \"{}\" not found in {}", key, path)
+            }
+            LoadConfigError::OverrideIgnored {
+                override_source,
+                error,
+            } => write!(
+                f,
+                "{} (note: the override in {} was ignored because the config file failed to load)",
+                error, override_source
+            ),
+            LoadConfigError::Validation(message) => {
+                write!(f, "Invalid config value: {}", message)
+            }
+            LoadConfigError::InvariantViolations(violations) => {
+                write!(f, "Invalid config: {}", violations.join("; "))
+            }
+            LoadConfigError::IncompatibleOverride {
+                override_source,
+                problems,
+            } => write!(
+                f,
+                "Override in {} doesn't fit the config: {}",
+                override_source,
+                problems.join("; ")
+            ),
+            LoadConfigError::Timeout { path, timeout_ms } => {
+                write!(f, "Timed out after {}ms reading {}", timeout_ms, path)
+            }
+            #[cfg(feature = "encryption")]
+            LoadConfigError::Decrypt(message) => {
+                write!(f, "Failed to decrypt config file: {}", message)
+            }
+            #[cfg(feature = "checksum")]
+            LoadConfigError::Checksum(message) => {
+                write!(f, "Config file failed its checksum: {}", message)
+            }
+            LoadConfigError::DuplicateKey(key) => write!(f, "Duplicate key: {}", key),
+            LoadConfigError::Serialize(message) => {
+                write!(f, "Failed to serialize config: {}", message)
+            }
+            LoadConfigError::SchemaVersion { found, min, max } => write!(
+                f,
+                "Config file has schema version {}, but only versions {} to {} are supported",
+                found, min, max
+            ),
+            LoadConfigError::MissingEnvVar { var, path } => write!(
+                f,
+                "Environment variable {} used in {} is not set",
+                var, path
+            ),
+            #[cfg(feature = "field_docs")]
+            LoadConfigError::FieldHelp { field, help, error } => {
+                write!(f, "{} (help: {}: {})", error, field, help)
+            }
         }
     }
 }
@@ -137,20 +447,105 @@ impl std::error::Error for LoadConfigError {
             LoadConfigError::Json(e) => Some(e),
             #[cfg(feature = "ron")]
             LoadConfigError::Ron(e) => Some(e),
+            #[cfg(feature = "toml")]
+            LoadConfigError::Toml(e) => Some(e),
             LoadConfigError::Io(e) => Some(e),
             LoadConfigError::UnsupportedFormat(_) => None,
+            LoadConfigError::EnvDocKeyMissing { .. } => None,
+            LoadConfigError::OverrideNotObject { .. } => None,
+            LoadConfigError::Reference(_) => None,
+            LoadConfigError::Extends(_) => None,
+            LoadConfigError::FieldNotFound(_) => None,
+            LoadConfigError::InvalidOperator(_) => None,
+            LoadConfigError::RootKeyMissing { .. } => None,
+            LoadConfigError::OverrideIgnored { error, .. } => Some(error.as_ref()),
+            LoadConfigError::Validation(_) => None,
+            LoadConfigError::InvariantViolations(_) => None,
+            LoadConfigError::IncompatibleOverride { .. } => None,
+            LoadConfigError::Timeout { .. } => None,
+            #[cfg(feature = "encryption")]
+            LoadConfigError::Decrypt(_) => None,
+            #[cfg(feature = "checksum")]
+            LoadConfigError::Checksum(_) => None,
+            LoadConfigError::DuplicateKey(_) => None,
+            LoadConfigError::Serialize(_) => None,
+            LoadConfigError::SchemaVersion { .. } => None,
+            LoadConfigError::MissingEnvVar { .. } => None,
+            #[cfg(feature = "field_docs")]
+            LoadConfigError::FieldHelp { error, .. } => Some(error.as_ref()),
+        }
+    }
+}
+
+impl LoadConfigError {
+    /// Wraps the error in a [`ConfigFileError`] naming the config type `T` and the file it
+    /// was loaded from.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use bevy_config_file::ConfigFile;
+    /// # #[derive(bevy::prelude::Reflect, serde::Serialize, serde::Deserialize)]
+    /// # struct AudioSettings { volume: f32 }
+    /// # impl ConfigFile for AudioSettings {
+    /// #     const PATH: &'static str = "assets/config/audio.yaml";
+    /// # }
+    /// use bevy_config_file::{LoadConfigError, load_config_file};
+    ///
+    /// let settings = load_config_file::<AudioSettings>()
+    ///     .map_err(LoadConfigError::with_context::<AudioSettings>)
+    ///     .unwrap();
+    /// ```
+    pub fn with_context<T: ConfigFile>(self) -> ConfigFileError {
+        ConfigFileError {
+            type_name: short_type_name::<T>(),
+            path: locale::config_path::<T>(),
+            error: self,
         }
     }
 }
 
-/// Compares two byte slices in a const context.
+/// A [`LoadConfigError`] together with the config type and file it occurred in.
+///
+/// Config plugins report load failures with this error, so with a dozen configs the
+/// message says which one failed: `failed to load CameraSettings from
+/// assets/config/camera.yaml: YAML parsing error: ...`. Create one with
+/// [`LoadConfigError::with_context`].
+#[derive(Debug)]
+pub struct ConfigFileError {
+    /// The short type name of the config, e.g. `CameraSettings`.
+    pub type_name: &'static str,
+    /// The path the config was read from, after locale substitution and
+    /// [`ConfigFile::resolve_path`].
+    pub path: String,
+    /// The underlying error.
+    pub error: LoadConfigError,
+}
+
+impl std::fmt::Display for ConfigFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed to load {} from {}: {}",
+            self.type_name, self.path, self.error
+        )
+    }
+}
+
+impl std::error::Error for ConfigFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Compares a byte slice to a lowercase byte string in a const context, ignoring ASCII case.
 const fn bytes_equal(a: &[u8], a_start: usize, a_len: usize, b: &[u8]) -> bool {
     if a_len != b.len() {
         return false;
     }
     let mut i = 0;
     while i < a_len {
-        if a[a_start + i] != b[i] {
+        if a[a_start + i].to_ascii_lowercase() != b[i] {
             return false;
         }
         i += 1;
@@ -162,7 +557,7 @@ const fn bytes_equal(a: &[u8], a_start: usize, a_len: usize, b: &[u8]) -> bool {
 ///
 /// This is evaluated at compile time via the `ConfigFile::_FORMAT_CHECK` associated constant.
 /// If the extension requires a feature that isn't enabled, compilation fails with a clear message.
-const fn validate_config_format(path: &str) {
+pub(crate) const fn validate_config_format(path: &str) {
     let bytes = path.as_bytes();
     let len = bytes.len();
 
@@ -178,7 +573,7 @@ const fn validate_config_format(path: &str) {
     }
 
     if dot_pos == len {
-        panic!("Config file path must have a file extension (.yaml, .yml, .json, or .ron)");
+        panic!("Config file path must have a file extension (.yaml, .yml, .json, .ron, or .toml)");
     }
 
     let ext_start = dot_pos + 1;
@@ -189,7 +584,9 @@ const fn validate_config_format(path: &str) {
         || bytes_equal(bytes, ext_start, ext_len, b"yml")
     {
         if !cfg!(feature = "yaml") {
-            panic!("YAML config requires the 'yaml' feature. Add features = [\"yaml\"] to your bevy_config_file dependency.");
+            panic!(
+                "YAML config requires the 'yaml' feature. Add features = [\"yaml\"] to your bevy_config_file dependency."
+            );
         }
         return;
     }
@@ -197,7 +594,9 @@ const fn validate_config_format(path: &str) {
     // Check json
     if bytes_equal(bytes, ext_start, ext_len, b"json") {
         if !cfg!(feature = "json") {
-            panic!("JSON config requires the 'json' feature. Add features = [\"json\"] to your bevy_config_file dependency.");
+            panic!(
+                "JSON config requires the 'json' feature. Add features = [\"json\"] to your bevy_config_file dependency."
+            );
         }
         return;
     }
@@ -205,12 +604,24 @@ const fn validate_config_format(path: &str) {
     // Check ron
     if bytes_equal(bytes, ext_start, ext_len, b"ron") {
         if !cfg!(feature = "ron") {
-            panic!("RON config requires the 'ron' feature. Add features = [\"ron\"] to your bevy_config_file dependency.");
+            panic!(
+                "RON config requires the 'ron' feature. Add features = [\"ron\"] to your bevy_config_file dependency."
+            );
+        }
+        return;
+    }
+
+    // Check toml
+    if bytes_equal(bytes, ext_start, ext_len, b"toml") {
+        if !cfg!(feature = "toml") {
+            panic!(
+                "TOML config requires the 'toml' feature. Add features = [\"toml\"] to your bevy_config_file dependency."
+            );
         }
         return;
     }
 
-    panic!("Unsupported config file extension. Supported: .yaml, .yml, .json, .ron");
+    panic!("Unsupported config file extension. Supported: .yaml, .yml, .json, .ron, .toml");
 }
 
 /// Trait for types that can be loaded from a configuration file.
@@ -237,19 +648,470 @@ pub trait ConfigFile: 'static {
     /// This should typically be a path relative to your game's root directory,
     /// such as `"assets/config/settings.yaml"`.
     ///
-    /// The file extension determines the format: `.yaml`/`.yml`, `.json`, or `.ron`.
+    /// The file extension determines the format: `.yaml`/`.yml`, `.json`, `.ron`, or `.toml`,
+    /// in any case.
     /// The corresponding feature must be enabled.
     const PATH: &'static str;
 
+    /// Paths tried in order when the file at [`PATH`](Self::PATH) does not exist.
+    ///
+    /// The first file that exists is loaded in place of `PATH`, so a user's copy at `PATH`
+    /// wins over a default shipped at a fallback. Saving always writes `PATH`, creating the
+    /// user's copy instead of overwriting the default, and hot reloading watches `PATH` and
+    /// every fallback. Each path is
+    /// rewritten like `PATH` (with [`resolve_path`](Self::resolve_path) and the platform
+    /// directory) but without locale or variant substitution. If none exists, loading fails
+    /// with the IO error for `PATH`; a file that exists but fails to parse is an error,
+    /// not a reason to try the next path.
+    const FALLBACK_PATHS: &'static [&'static str] = &[];
+
     /// Compile-time validation that the file extension matches an enabled format feature.
     /// Do not override this.
-    const _FORMAT_CHECK: () = validate_config_format(Self::PATH);
+    const _FORMAT_CHECK: () = {
+        validate_config_format(Self::PATH);
+        if let Some(common_path) = Self::COMMON_PATH {
+            validate_config_format(common_path);
+        }
+        let mut i = 0;
+        while i < Self::FALLBACK_PATHS.len() {
+            validate_config_format(Self::FALLBACK_PATHS[i]);
+            i += 1;
+        }
+        let mut i = 0;
+        while i < Self::LAYER_PATHS.len() {
+            validate_config_format(Self::LAYER_PATHS[i]);
+            i += 1;
+        }
+    };
+
+    /// Files loaded in place of [`PATH`](Self::PATH) as a stack, such as a checked-in
+    /// `settings.yaml` with a gitignored `settings.local.yaml` on top.
+    ///
+    /// Each file is deep-merged over the ones before it, and overrides are applied over the
+    /// result. Files that don't exist are skipped, but a file that exists and fails to parse
+    /// fails the load. Each path is resolved like `PATH`, with the locale, variant,
+    /// [`resolve_path`](Self::resolve_path) and platform directory applied. `PATH` is still
+    /// where [`save_config_file`] writes, so it is usually the first layer. Reloads use the
+    /// same layering.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_config_file::ConfigFile;
+    ///
+    /// struct Settings;
+    ///
+    /// impl ConfigFile for Settings {
+    ///     const PATH: &'static str = "settings.yaml";
+    ///     const LAYER_PATHS: &'static [&'static str] = &["settings.yaml", "settings.local.yaml"];
+    /// }
+    /// ```
+    const LAYER_PATHS: &'static [&'static str] = &[];
+
+    /// Child configs loaded from their own files into fields of this config.
+    ///
+    /// Each child value replaces the corresponding field from this config's own file.
+    /// See [`ChildConfig`] for an example.
+    const CHILDREN: &'static [ChildConfig] = &[];
+
+    /// A directory holding one file per field, read instead of [`PATH`](Self::PATH).
+    ///
+    /// Each `.yaml`, `.yml`, `.json`, `.ron` or `.toml` file in the directory holds the value
+    /// of the field named by its file stem, so `camera/pan_speed.yaml` sets `pan_speed`.
+    /// Files in formats whose feature is disabled, and other files, are skipped, and two
+    /// files for the same field fail with `LoadConfigError::DuplicateKey`. The directory is
+    /// passed through [`resolve_path`](Self::resolve_path) and listed with
+    /// [`ConfigFs::read_dir`], while its files are read with [`read_file`](Self::read_file).
+    /// Saving still writes a single file at `PATH`.
+    const FIELD_DIR: Option<&'static str> = None;
+
+    /// An environment variable holding a shared JSON document to use as the base config
+    /// instead of reading [`PATH`](Self::PATH).
+    ///
+    /// If the variable is unset, the config is loaded from `PATH` as usual. This suits
+    /// serverless deployments where all configuration arrives in a single variable.
+    const ENV_DOC_VAR: Option<&'static str> = None;
+
+    /// The key of this config's subtree within the [`ENV_DOC_VAR`](Self::ENV_DOC_VAR)
+    /// document. If `None`, the whole document is used.
+    const ENV_DOC_KEY: Option<&'static str> = None;
+
+    /// Whether to resolve `${self.path}` references to other fields after overrides are merged.
+    ///
+    /// A string that is exactly one reference, such as `max_zoom: "${self.min_zoom}"`, is
+    /// replaced by the referenced value with its type intact. References inside longer
+    /// strings, such as `"${self.name}-backup"`, are interpolated as text. Nested fields are
+    /// reached with `.`-separated paths.
+    const RESOLVE_REFERENCES: bool = false;
+
+    /// Whether to enforce `# range: 0..5000`, `# min:` and `# max:` comments in the YAML file.
+    ///
+    /// When `true`, the comments on `key: value` lines of the file at [`PATH`](Self::PATH) are
+    /// scanned before loading, and the loaded value of each annotated field (including any
+    /// override) must lie within its inclusive bounds, or loading fails with
+    /// `LoadConfigError::Validation`. Comments in `extends:` or `includes:` files are not
+    /// read.
+    const CHECK_COMMENT_RANGES: bool = false;
+
+    /// Whether to reject YAML files whose mappings repeat a key.
+    ///
+    /// By default the last of the repeated values wins. When `true`, each YAML file read for
+    /// this config (including `extends:` and `includes:` files) is scanned before parsing, and
+    /// a repeated key fails the load with `LoadConfigError::DuplicateKey`, naming the key's
+    /// path and line.
+    const DENY_DUPLICATE_KEYS: bool = false;
+
+    /// Whether [`save_config_file`] rewrites only the fields that changed.
+    ///
+    /// By default the whole file is rewritten, losing its comments and formatting. When
+    /// `true`, a YAML file on disk is compared with the value being saved, and only the
+    /// blocks of the fields that differ are rewritten: every other line, including comments
+    /// and unchanged `${VAR}` tokens, is kept byte for byte, as is the inline comment of a
+    /// changed single-line field. A file in another format, or whose text can't be matched
+    /// to its fields (such as one written as a single flow mapping), is rewritten whole.
+    const SAVE_CHANGES_ONLY: bool = false;
+
+    /// Whether to expand `${VAR}` tokens in the raw file text from the environment.
+    ///
+    /// When `true`, each file read for this config has every `${VAR}` token replaced by the
+    /// value of the environment variable `VAR` before it is parsed, so secrets and
+    /// machine-specific paths (`save_dir: ${SAVE_DIR}`) stay out of the file. Only names of
+    /// ASCII letters, digits and underscores are expanded, leaving `${self.path}`
+    /// references alone. An unset variable fails the load with
+    /// `LoadConfigError::MissingEnvVar` unless
+    /// [`ALLOW_MISSING_ENV_VARS`](Self::ALLOW_MISSING_ENV_VARS) is set.
+    const EXPAND_ENV_VARS: bool = false;
+
+    /// Whether unset variables expand to an empty string, with
+    /// [`EXPAND_ENV_VARS`](Self::EXPAND_ENV_VARS).
+    const ALLOW_MISSING_ENV_VARS: bool = false;
+
+    /// The current schema version of the config, if files are versioned.
+    ///
+    /// When set, the file's version is read from its
+    /// [`SCHEMA_VERSION_KEY`](Self::SCHEMA_VERSION_KEY) (a file without the key is version
+    /// 0). Older files are passed to [`migrate`](Self::migrate) before they are
+    /// deserialized, and newer files fail with `LoadConfigError::SchemaVersion`. Widen or
+    /// narrow the accepted band with [`MIN_SCHEMA_VERSION`](Self::MIN_SCHEMA_VERSION) and
+    /// [`MAX_SCHEMA_VERSION`](Self::MAX_SCHEMA_VERSION).
+    const SCHEMA_VERSION: Option<u32> = None;
+
+    /// The top-level key holding a file's schema version, with
+    /// [`SCHEMA_VERSION`](Self::SCHEMA_VERSION).
+    ///
+    /// The key is left in the loaded JSON, so `T` may declare it as a field. Types with
+    /// `#[serde(deny_unknown_fields)]` must.
+    const SCHEMA_VERSION_KEY: &'static str = "schema_version";
+
+    /// The oldest schema version accepted, with [`SCHEMA_VERSION`](Self::SCHEMA_VERSION).
+    ///
+    /// Files older than this fail with `LoadConfigError::SchemaVersion` instead of being
+    /// migrated, so [`migrate`](Self::migrate) can drop support for ancient versions.
+    const MIN_SCHEMA_VERSION: u32 = 0;
+
+    /// The newest schema version accepted, with [`SCHEMA_VERSION`](Self::SCHEMA_VERSION);
+    /// `None` means `SCHEMA_VERSION` itself.
+    ///
+    /// Files newer than `SCHEMA_VERSION` but within this bound are loaded as they are,
+    /// without migration, so a binary can accept files written by a slightly newer release.
+    /// Newer files fail with `LoadConfigError::SchemaVersion`.
+    const MAX_SCHEMA_VERSION: Option<u32> = None;
+
+    /// The format of whole-config overrides, from the environment variable or
+    /// [`set_override`].
+    ///
+    /// Defaults to [`OverrideFormat::Json`]. With [`OverrideFormat::Yaml`], an override can
+    /// be written like the YAML file itself, e.g. `CONFIG_CameraSettings='pan_speed: 2000.0'`.
+    const ENV_FORMAT: OverrideFormat = OverrideFormat::Json;
+
+    /// How override fields are merged into the config loaded from the file.
+    ///
+    /// Defaults to [`MergeStrategy::Deep`], where nested objects are merged key by key so
+    /// sibling fields survive. See [`MergeStrategy`] for the alternatives.
+    const MERGE_STRATEGY: MergeStrategy = MergeStrategy::Deep;
+
+    /// A top-level key of the file under which this config is stored.
+    ///
+    /// Shared files can hold several configs side by side, such as
+    /// `camera: {pan_speed: 2000}`. With `Some("camera")` the loader descends into that key
+    /// before deserializing, and overrides apply to the descended value.
+    const ROOT_KEY: Option<&'static str> = None;
+
+    /// An environment variable holding a JSON override of the whole shared file, of which
+    /// the [`ROOT_KEY`](Self::ROOT_KEY) section is applied to this config.
+    ///
+    /// It is only read when `ROOT_KEY` is set and there is no in-memory override or
+    /// `CONFIG_{TypeName}` variable for this config. [`config_sections!`] sets it for every
+    /// section it declares.
+    const DOCUMENT_OVERRIDE_VAR: Option<&'static str> = None;
+
+    /// The prefix of the environment variable holding this config's JSON override.
+    ///
+    /// Overrides are read from `{ENV_PREFIX}{TypeName}`, so with `"MYGAME_"` the variable
+    /// for `CameraSettings` is `MYGAME_CameraSettings`. Change it if another tool in the
+    /// environment also reads `CONFIG_` variables.
+    const ENV_PREFIX: &'static str = "CONFIG_";
+
+    /// The name of the environment variable holding this config's JSON override, used
+    /// verbatim in place of `{ENV_PREFIX}{TypeName}`.
+    ///
+    /// Set it when two config types share a short name, such as `audio::Settings` and
+    /// `video::Settings`, which would otherwise read the same variable, or to give the
+    /// variable a stable documented name. [`APP_ID_VAR`](Self::APP_ID_VAR) is not applied
+    /// to it.
+    const ENV_VAR: Option<&'static str> = None;
+
+    /// An environment variable holding a runtime app identifier, such as `GAME_ID`, that is
+    /// inserted into the override variable name.
+    ///
+    /// With `Some("GAME_ID")` and `GAME_ID=foo`, overrides are read from
+    /// `CONFIG_foo_{TypeName}`, so one binary can serve many tenants. If the identifier
+    /// variable is unset, `CONFIG_{TypeName}` is used.
+    const APP_ID_VAR: Option<&'static str> = None;
+
+    /// Whether the file may inherit from a base file with a top-level `extends:` key.
+    ///
+    /// With `extends: base.yaml`, the base file (resolved relative to the declaring file's
+    /// directory) is loaded first and this file is deep-merged on top of it, so its values
+    /// win. Base files may extend further files; cycles are reported as errors.
+    const EXTENDS: bool = false;
+
+    /// Whether the file may pull in other files with a top-level `includes:` list.
+    ///
+    /// With `includes: [common/base.yaml, common/audio.yaml]`, each path is resolved relative
+    /// to the including file's directory (not the current directory), and the included files
+    /// are deep-merged in order beneath this file, so its own values win. Included files may
+    /// include or extend further files; cycles are reported as errors.
+    const INCLUDES: bool = false;
+
+    /// A file of defaults shared by several config types, such as a `common.yaml` holding
+    /// `log_level`, merged beneath this config's own file.
+    ///
+    /// The common file is deep-merged under the file, so the file's own values win, and
+    /// overrides apply on top of both. Every config naming the same file sees all of its
+    /// top-level keys, so types with `#[serde(deny_unknown_fields)]` must declare them all.
+    /// The path is rewritten by [`resolve_path`](Self::resolve_path), and a missing common
+    /// file fails the load.
+    const COMMON_PATH: Option<&'static str> = None;
+
+    /// The value the file is merged on top of, usually `Some(Self::default)`.
+    ///
+    /// The value is serialized to JSON as the bottom layer, the file is deep-merged on top of
+    /// it, and overrides are applied last. Every field therefore becomes optional in the
+    /// file while the type stays strongly typed. Reloads use the same layering.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_config_file::{ConfigFile, config_file_plugin};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Resource, Reflect, Debug, Default, Serialize, Deserialize)]
+    /// #[reflect(Resource)]
+    /// struct GameSettings {
+    ///     volume: f32,
+    ///     fullscreen: bool,
+    /// }
+    ///
+    /// impl ConfigFile for GameSettings {
+    ///     // The file can set just `volume: 0.5`
+    ///     const PATH: &'static str = "assets/config/game.yaml";
+    ///     const LAYER_DEFAULTS: Option<fn() -> Self> = Some(Self::default);
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(config_file_plugin::<GameSettings>)
+    ///     .run();
+    /// ```
+    const LAYER_DEFAULTS: Option<fn() -> Self> = None;
+
+    /// A field name used to merge overridden arrays of objects entry by entry.
+    ///
+    /// If set, an override of a top-level array field (or of a config that is itself an
+    /// array) is merged into the file's array instead of replacing it: override entries whose key field matches an existing entry
+    /// update that entry's fields, and all other entries are appended. For example, with
+    /// `Some("name")` an override of `abilities: [{name: dash, cooldown: 1.0}]` changes only
+    /// the `dash` ability.
+    const ARRAY_MERGE_KEY: Option<&'static str> = None;
+
+    /// An environment variable holding the locale substituted for `{locale}` in
+    /// [`PATH`](Self::PATH).
+    ///
+    /// With `PATH = "assets/strings.{locale}.yaml"` and the variable set to `fr`, the loader
+    /// reads `assets/strings.fr.yaml`. If the variable is unset or the locale's file doesn't
+    /// exist, the [`DEFAULT_LOCALE`](Self::DEFAULT_LOCALE) file is read instead.
+    const LOCALE_VAR: Option<&'static str> = None;
+
+    /// The locale used when [`LOCALE_VAR`](Self::LOCALE_VAR) is unset or names a locale
+    /// without a file.
+    const DEFAULT_LOCALE: &'static str = "en";
+
+    /// An environment variable naming a deployment variant whose file is read in place of
+    /// [`PATH`](Self::PATH) when it exists.
+    ///
+    /// The variant is inserted before the file extension: with `Some("CONFIG_VARIANT")` and
+    /// `CONFIG_VARIANT=beta`, `settings.yaml` becomes `settings.beta.yaml`. If that file
+    /// doesn't exist, or the variable is unset, the plain path is read. Only the file path
+    /// is affected, not the override variables.
+    const VARIANT_VAR: Option<&'static str> = None;
+
+    /// Whether the config file is encrypted with [`encrypt_config`].
+    ///
+    /// When `true`, the file (and any `extends:` or `includes:` file) is decrypted with the
+    /// AES-256-GCM key in the `CONFIG_KEY` environment variable, given as 64 hex digits,
+    /// before it is parsed. Failures are reported as `LoadConfigError::Decrypt`.
+    #[cfg(feature = "encryption")]
+    const ENCRYPTED: bool = false;
+
+    /// Whether the config file must match the SHA-256 digest in its `.sha256` sidecar,
+    /// such as `settings.yaml.sha256` next to `settings.yaml`.
+    ///
+    /// The file's raw bytes are hashed before they are decrypted or parsed, and a missing
+    /// sidecar or a mismatch fails with `LoadConfigError::Checksum`. `extends:` and
+    /// `includes:` files are checked too. Create sidecars with [`write_checksum_file`].
+    #[cfg(feature = "checksum")]
+    const VERIFY_CHECKSUM: bool = false;
+
+    /// How long to wait for the config file to be read before giving up, in milliseconds.
+    ///
+    /// Set this for files on network mounts, where a read can hang indefinitely. The read
+    /// runs on a background thread and the load fails with `LoadConfigError::Timeout` once
+    /// the time is up; the thread is left to finish on its own. Requires thread support, so
+    /// leave it `None` on WASM.
+    const READ_TIMEOUT_MS: Option<u64> = None;
+
+    /// Whether to resolve a relative [`PATH`](Self::PATH) against `CARGO_MANIFEST_DIR`.
+    ///
+    /// Cargo sets `CARGO_MANIFEST_DIR` to the package directory when it runs tests, so
+    /// fixtures such as `tests/fixtures/camera.yaml` can be loaded without changing the
+    /// working directory. Outside Cargo the variable is unset and the path is used as is.
+    const TEST_RELATIVE_TO_MANIFEST: bool = false;
+
+    /// Whether to resolve a relative [`PATH`](Self::PATH) against the user's config
+    /// directory rather than the working directory.
+    ///
+    /// The directory comes from `dirs::config_dir()`, such as `~/.config` on Linux or
+    /// `%APPDATA%` on Windows, joined with [`APP_NAME`](Self::APP_NAME). Use it for settings
+    /// the game writes, since installed builds often can't write next to the executable.
+    /// Saves land in the same place. If the platform has no config directory, the path is
+    /// used as is.
+    #[cfg(feature = "dirs")]
+    const USE_PLATFORM_DIR: bool = false;
+
+    /// The directory created inside the user's config directory for
+    /// [`USE_PLATFORM_DIR`](Self::USE_PLATFORM_DIR), such as `"my_game"`.
+    #[cfg(feature = "dirs")]
+    const APP_NAME: &'static str = "";
+
+    /// Computes the path actually read, given [`PATH`](Self::PATH).
+    ///
+    /// Override this when deployments relocate config files, for example to map `assets/`
+    /// to a synced cache directory. The format is still chosen by the returned path's
+    /// extension. `default` already has any [`LOCALE_VAR`](Self::LOCALE_VAR) locale
+    /// substituted. The default implementation returns `default` unchanged.
+    fn resolve_path(default: &str) -> String {
+        default.to_string()
+    }
+
+    /// Reads the raw bytes of a config file, including any `extends:` or `includes:` file.
+    ///
+    /// The default implementation reads `path` through the [`ConfigFs`] installed with
+    /// [`set_config_fs`], or from disk if there is none. Override it to load this config
+    /// from another source, such as an archive or a test double.
+    fn read_file(path: &Path) -> std::io::Result<Vec<u8>> {
+        vfs::read(path)
+    }
+
+    /// Called once for each field replaced by an environment variable override.
+    ///
+    /// `field` is the `.`-separated path of the overridden value: under the default
+    /// [`MergeStrategy::Deep`], a nested override such as `{"audio": {"volume": 0}}` is
+    /// reported as `audio.volume`, while under [`MergeStrategy::ShallowTopLevel`] it is
+    /// reported as `audio`. `old` is the value loaded from the file
+    /// (`JsonValue::Null` if the file did not set it) and `new` is the override value.
+    /// Values of [`attributes::Secret`] fields are passed as the string `"***"`.
+    /// The default implementation does nothing; override it to audit-log config changes.
+    fn on_override(field: &str, old: &JsonValue, new: &JsonValue) {
+        let _ = (field, old, new);
+    }
+
+    /// Checks rules that span several fields, such as `min <= max`, after the config is
+    /// loaded.
+    ///
+    /// Return one message per broken rule; every message is reported together in
+    /// `LoadConfigError::InvariantViolations`, so the config is never inserted half-valid.
+    /// The default implementation accepts every config.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_config_file::ConfigFile;
+    ///
+    /// struct ZoomSettings {
+    ///     min_zoom: f32,
+    ///     max_zoom: f32,
+    /// }
+    ///
+    /// impl ConfigFile for ZoomSettings {
+    ///     const PATH: &'static str = "assets/config/zoom.yaml";
+    ///
+    ///     fn validate_invariants(&self) -> Vec<String> {
+    ///         let mut violations = Vec::new();
+    ///         if self.min_zoom > self.max_zoom {
+    ///             violations.push("min_zoom must not exceed max_zoom".to_string());
+    ///         }
+    ///         violations
+    ///     }
+    /// }
+    /// ```
+    fn validate_invariants(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Rewrites a file written for schema version `from_version` into the shape of the
+    /// current [`SCHEMA_VERSION`](Self::SCHEMA_VERSION).
+    ///
+    /// Called before deserialization, only for files older than the current version.
+    /// `value` is the whole file (below any [`ROOT_KEY`](Self::ROOT_KEY)) as JSON, and its
+    /// version key is updated afterwards. The default implementation does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_config_file::{ConfigFile, JsonValue};
+    ///
+    /// struct AudioSettings;
+    ///
+    /// impl ConfigFile for AudioSettings {
+    ///     const PATH: &'static str = "assets/config/audio.yaml";
+    ///     const SCHEMA_VERSION: Option<u32> = Some(2);
+    ///
+    ///     fn migrate(value: &mut JsonValue, from_version: u32) {
+    ///         // Version 1 called the volume `master_volume`
+    ///         if from_version < 2 {
+    ///             if let Some(map) = value.as_object_mut() {
+    ///                 if let Some(volume) = map.remove("master_volume") {
+    ///                     map.insert("volume".to_string(), volume);
+    ///                 }
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn migrate(value: &mut JsonValue, from_version: u32) {
+        let _ = (value, from_version);
+    }
 }
 
 /// Creates a Bevy plugin that loads a configuration resource from a file at startup.
 ///
 /// This function registers the type with Bevy's reflection system and adds a startup
 /// system that loads the configuration from the file specified in the `ConfigFile` trait.
+/// The resource is reloaded whenever its in-memory override changes (see [`set_override`]).
+/// Fields marked with [`attributes::RestartRequired`] are not changed by a reload; a
+/// [`RestartRequired`] message is written instead. A reload that fails keeps the existing
+/// resource and logs the error.
 ///
 /// # Type Parameters
 ///
@@ -291,98 +1153,454 @@ where
         + Serialize
         + ConfigFile
         + Reflect
-        + GetTypeRegistration,
+        + GetTypeRegistration
+        + Typed,
 {
-    // Force compile-time evaluation of format validation
-    #[allow(clippy::let_unit_value)]
-    let _ = T::_FORMAT_CHECK;
-
-    app.register_type::<T>();
+    if !register_config::<T>(app) {
+        return;
+    }
     app.add_systems(Startup, load_resource_from_config_file::<T>);
 }
 
-/// Loads a configuration resource from a file and inserts it into Bevy's ECS.
+/// Like [`config_file_plugin`], but skips loading at startup if `T` already exists.
 ///
-/// This is a lower-level function that can be called directly from a Bevy system.
-/// Most users should prefer using [`config_file_plugin`] instead, which handles
-/// the system registration automatically.
+/// This is useful in tests that insert a config resource before the app starts: the
+/// pre-inserted value is kept instead of being overwritten by the file.
 ///
-/// # Type Parameters
+/// # Example
 ///
-/// * `T` - The configuration type to load. Must implement `Resource`, `Deserialize`,
-///   `Serialize`, and `ConfigFile`.
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, config_file_plugin_if_missing};
+/// use serde::{Deserialize, Serialize};
 ///
-/// # Returns
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct GameSettings {
+///     volume: f32,
+/// }
 ///
-/// * `Ok(())` - If the configuration was successfully loaded and inserted
-/// * `Err(BevyError)` - If any error occurs during loading or parsing
+/// impl ConfigFile for GameSettings {
+///     const PATH: &'static str = "assets/config/game.yaml";
+/// }
 ///
-/// # Errors
+/// App::new()
+///     .insert_resource(GameSettings { volume: 0.0 })
+///     .add_plugins(config_file_plugin_if_missing::<GameSettings>)
+///     .run();
+/// ```
+pub fn config_file_plugin_if_missing<T>(app: &mut App)
+where
+    T: Resource
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ConfigFile
+        + Reflect
+        + GetTypeRegistration
+        + Typed,
+{
+    if !register_config::<T>(app) {
+        return;
+    }
+    app.add_systems(
+        Startup,
+        load_resource_from_config_file::<T>.run_if(not(resource_exists::<T>)),
+    );
+}
+
+/// Like [`config_file_plugin`], but inserts `T::default()` if the config file doesn't exist.
 ///
-/// Returns a Bevy error if the configuration file cannot be loaded or parsed.
-/// See [`load_config_file`] for details on the loading process and potential error conditions.
-/// The error will be handled by Bevy's error handler (by default, this will panic).
+/// This suits prototyping before any config file has been written. A missing file is
+/// logged as a warning; every other error, such as invalid YAML or a bad override, still
+/// fails the startup system.
 ///
 /// # Example
 ///
 /// ```no_run
 /// use bevy::prelude::*;
-/// use bevy_config_file::{ConfigFile, load_resource_from_config_file};
+/// use bevy_config_file::{ConfigFile, config_file_plugin_or_default};
 /// use serde::{Deserialize, Serialize};
 ///
-/// #[derive(Resource, Debug, Serialize, Deserialize)]
-/// struct Settings {
-///     value: i32,
+/// #[derive(Resource, Reflect, Debug, Default, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct GameSettings {
+///     volume: f32,
 /// }
 ///
-/// impl ConfigFile for Settings {
-///     const PATH: &'static str = "assets/config/settings.yaml";
+/// impl ConfigFile for GameSettings {
+///     const PATH: &'static str = "assets/config/game.yaml";
 /// }
 ///
-/// fn setup(commands: Commands) -> bevy::ecs::error::Result {
-///     load_resource_from_config_file::<Settings>(commands)
-/// }
+/// App::new()
+///     .add_plugins(config_file_plugin_or_default::<GameSettings>)
+///     .run();
 /// ```
-pub fn load_resource_from_config_file<T>(mut commands: Commands) -> bevy::ecs::error::Result
+pub fn config_file_plugin_or_default<T>(app: &mut App)
+where
+    T: Resource
+        + Default
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ConfigFile
+        + Reflect
+        + GetTypeRegistration
+        + Typed,
+{
+    if !register_config::<T>(app) {
+        return;
+    }
+    app.add_systems(Startup, load_resource_or_default::<T>);
+}
+
+/// Startup system for [`config_file_plugin_or_default`].
+fn load_resource_or_default<T>(mut commands: Commands) -> bevy::ecs::error::Result
 where
-    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile,
+    T: Resource + Default + for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
 {
-    match load_config_file::<T>() {
-        Ok(config) => {
+    match report::tracking_overrides(load_config_file::<T>) {
+        Ok((config, overridden)) => {
+            insert_config(&mut commands, config, false, overridden);
+            Ok(())
+        }
+        Err(LoadConfigError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
             #[cfg(feature = "logging")]
-            info!("loaded config from {}", T::PATH);
-            commands.insert_resource(config);
+            warn!(
+                "config file {} for {} not found; using the default value",
+                locale::config_path::<T>(),
+                std::any::type_name::<T>()
+            );
+            insert_config(&mut commands, T::default(), false, false);
             Ok(())
         }
-        Err(err) => Err(err.into()),
+        Err(err) => {
+            telemetry::record::<T>(&mut commands, |counts| counts.errors += 1);
+            Err(err.with_context::<T>().into())
+        }
     }
 }
 
-/// Loads configuration from a file with optional environment variable overrides.
-///
-/// The format is determined by the file extension: `.yaml`/`.yml`, `.json`, or `.ron`.
-/// The corresponding feature must be enabled.
-///
-/// This function performs a two-stage loading process:
-/// 1. Loads the base configuration from the file specified in `T::PATH`
-/// 2. Applies any overrides from an environment variable (if present)
+/// Like [`config_file_plugin`], but logs load errors at `error!` level instead of failing
+/// the app.
 ///
-/// # Environment Variable Overrides
+/// If loading fails, the resource is left absent, so systems can check for it with
+/// `Option<Res<T>>` while the file is fixed. This suits live development, where a typo in a
+/// YAML file shouldn't take the app down. Without the `logging` feature the error is
+/// dropped; use [`config_file_plugin_capture_errors`] to inspect it instead.
 ///
-/// The environment variable name is `CONFIG_{TypeName}` where `TypeName` is the last
-/// component of the type's fully qualified name. For example, for a type
-/// `my_game::config::CameraSettings`, the environment variable would be
-/// `CONFIG_CameraSettings`.
+/// # Example
 ///
-/// The environment variable should contain a JSON object with the fields to override.
-/// Only top-level fields are overridden; nested objects are replaced entirely, not merged.
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, config_file_plugin_nonfatal};
+/// use serde::{Deserialize, Serialize};
 ///
-/// # Type Parameters
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct EnemyTuning {
+///     speed: f32,
+/// }
 ///
-/// * `T` - The configuration type to load. Must implement `Deserialize`, `Serialize`,
-///   and `ConfigFile`.
+/// impl ConfigFile for EnemyTuning {
+///     const PATH: &'static str = "assets/config/enemy_tuning.yaml";
+/// }
 ///
-/// # Returns
+/// fn move_enemies(tuning: Option<Res<EnemyTuning>>) {
+///     let Some(tuning) = tuning else { return };
+///     // ...
+/// }
+///
+/// App::new()
+///     .add_plugins(config_file_plugin_nonfatal::<EnemyTuning>)
+///     .add_systems(Update, move_enemies)
+///     .run();
+/// ```
+pub fn config_file_plugin_nonfatal<T>(app: &mut App)
+where
+    T: Resource
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ConfigFile
+        + Reflect
+        + GetTypeRegistration
+        + Typed,
+{
+    if !register_config::<T>(app) {
+        return;
+    }
+    app.add_systems(Startup, load_resource_or_log_error::<T>);
+}
+
+/// Startup system for [`config_file_plugin_nonfatal`].
+fn load_resource_or_log_error<T>(mut commands: Commands)
+where
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    match report::tracking_overrides(load_config_file::<T>) {
+        Ok((config, overridden)) => insert_config(&mut commands, config, false, overridden),
+        Err(err) => {
+            #[cfg(feature = "logging")]
+            error!(
+                "failed to load config {} from {}: {}",
+                std::any::type_name::<T>(),
+                locale::config_path::<T>(),
+                err
+            );
+            #[cfg(not(feature = "logging"))]
+            let _ = err;
+            telemetry::record::<T>(&mut commands, |counts| counts.errors += 1);
+        }
+    }
+}
+
+/// Like [`config_file_plugin`], but records load errors in [`ConfigLoadErrors`] instead of
+/// failing the app.
+///
+/// If loading fails, the resource is not inserted and the app keeps running, so it can
+/// show a friendly error screen. Reload errors are logged like those of
+/// [`config_file_plugin`] (once until the error changes).
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, ConfigLoadErrors, config_file_plugin_capture_errors};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct GameSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for GameSettings {
+///     const PATH: &'static str = "assets/config/game.yaml";
+/// }
+///
+/// fn show_errors(errors: Res<ConfigLoadErrors>) {
+///     for (type_name, message) in &errors.errors {
+///         println!("{type_name} failed to load: {message}");
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(config_file_plugin_capture_errors::<GameSettings>)
+///     .add_systems(Update, show_errors)
+///     .run();
+/// ```
+pub fn config_file_plugin_capture_errors<T>(app: &mut App)
+where
+    T: Resource
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ConfigFile
+        + Reflect
+        + GetTypeRegistration
+        + Typed,
+{
+    if !register_config::<T>(app) {
+        return;
+    }
+    app.init_resource::<ConfigLoadErrors>();
+    app.add_systems(Startup, load_errors::load_resource_or_capture_error::<T>);
+}
+
+/// Registers everything a config plugin needs except the startup loading system.
+///
+/// Returns `false` (after logging a warning) if `T` was already added by another config
+/// plugin, in which case the caller should not add its systems again.
+pub(crate) fn register_config<T>(app: &mut App) -> bool
+where
+    T: Resource
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ConfigFile
+        + Reflect
+        + GetTypeRegistration
+        + Typed,
+{
+    // Force compile-time evaluation of format validation
+    #[allow(clippy::let_unit_value)]
+    let _ = T::_FORMAT_CHECK;
+
+    #[cfg(feature = "logging")]
+    if !app.world().contains_resource::<ConfigRegistry>() {
+        app.add_systems(PostStartup, registry::warn_orphan_overrides);
+    }
+    if !registry::register::<T>(app) {
+        return false;
+    }
+
+    app.init_resource::<ConfigTelemetry>();
+    app.register_type::<T>();
+    app.add_message::<RestartRequired<T>>();
+    app.add_message::<ConfigLoaded<T>>();
+    app.add_systems(
+        Update,
+        reload::reload_resource_from_config_file::<T>.run_if(overrides::override_changed::<T>),
+    );
+    true
+}
+
+/// Loads a configuration resource from a file and inserts it into Bevy's ECS.
+///
+/// This is a lower-level function that can be called directly from a Bevy system.
+/// A [`ConfigMeta`] resource is inserted alongside it, and an [`OnConfigChanged`] event is
+/// triggered once the resource is inserted. A [`ConfigLoaded`] message is also written if
+/// a config plugin registered it.
+/// Most users should prefer using [`config_file_plugin`] instead, which handles
+/// the system registration automatically.
+///
+/// # Type Parameters
+///
+/// * `T` - The configuration type to load. Must implement `Resource`, `Deserialize`,
+///   `Serialize`, and `ConfigFile`.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the configuration was successfully loaded and inserted
+/// * `Err(BevyError)` - If any error occurs during loading or parsing
+///
+/// # Errors
+///
+/// Returns a Bevy error if the configuration file cannot be loaded or parsed.
+/// See [`load_config_file`] for details on the loading process and potential error conditions.
+/// The error is a [`ConfigFileError`] naming `T` and its file, and will be handled by Bevy's
+/// error handler (by default, this will panic).
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, load_resource_from_config_file};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// struct Settings {
+///     value: i32,
+/// }
+///
+/// impl ConfigFile for Settings {
+///     const PATH: &'static str = "assets/config/settings.yaml";
+/// }
+///
+/// fn setup(commands: Commands) -> bevy::ecs::error::Result {
+///     load_resource_from_config_file::<Settings>(commands)
+/// }
+/// ```
+pub fn load_resource_from_config_file<T>(mut commands: Commands) -> bevy::ecs::error::Result
+where
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    match report::tracking_overrides(load_config_file::<T>) {
+        Ok((config, overridden)) => {
+            insert_config(&mut commands, config, false, overridden);
+            Ok(())
+        }
+        Err(err) => {
+            telemetry::record::<T>(&mut commands, |counts| counts.errors += 1);
+            Err(err.with_context::<T>().into())
+        }
+    }
+}
+
+/// Inserts a freshly loaded config resource along with its [`ConfigMeta`], then triggers
+/// [`OnConfigChanged`], counts the load in [`ConfigTelemetry`] and, on first insertion,
+/// writes [`ConfigLoaded`]. `overridden` is whether the load applied an override.
+pub(crate) fn insert_config<T>(commands: &mut Commands, config: T, reloaded: bool, overridden: bool)
+where
+    T: Resource + Serialize + ConfigFile + Typed,
+{
+    redact::log_loaded(&config, reloaded);
+    let meta = ConfigMeta::now(&config, overridden);
+    commands.insert_resource(config);
+    commands.insert_resource(meta);
+    commands.trigger(OnConfigChanged::<T>::new(reloaded));
+    if reloaded {
+        telemetry::record::<T>(commands, |counts| counts.reloads += 1);
+    } else {
+        telemetry::record::<T>(commands, |counts| counts.loads += 1);
+        // Apps that call the load system without a plugin may not have the message
+        commands.queue(|world: &mut World| {
+            if world.contains_resource::<Messages<ConfigLoaded<T>>>() {
+                world.write_message(ConfigLoaded::<T>::new());
+            }
+        });
+    }
+}
+
+/// Loads configuration from a file with optional environment variable overrides.
+///
+/// The format is determined by the file extension: `.yaml`/`.yml`, `.json`, `.ron`, or
+/// `.toml`.
+/// The corresponding feature must be enabled.
+///
+/// This function performs a two-stage loading process:
+/// 1. Loads the base configuration from the file specified in `T::PATH` (as rewritten by
+///    [`ConfigFile::resolve_path`]) or from the [`ConfigFile::ENV_DOC_VAR`] document, if set,
+///    along with any [`ConfigFile::CHILDREN`], [`ConfigFile::EXTENDS`] base files and
+///    [`ConfigFile::INCLUDES`], merged on top of the [`ConfigFile::COMMON_PATH`] file and
+///    then [`ConfigFile::LAYER_DEFAULTS`], if set. Types with
+///    [`ConfigFile::LAYER_PATHS`] merge every file in it instead of reading `T::PATH`
+/// 2. Applies any overrides from an environment variable (if present)
+/// 3. Applies any `--config {TypeName}.{field}=VALUE` command-line overrides
+/// 4. Resolves `${self.path}` references, if [`ConfigFile::RESOLVE_REFERENCES`] is set
+///
+/// When no override is present (and references are not resolved), the value deserialized
+/// from the file is returned directly: it is never serialized, so large configs don't pay
+/// for a JSON round trip.
+///
+/// # Environment Variable Overrides
+///
+/// The environment variable name is `CONFIG_{TypeName}` where `TypeName` is the last
+/// component of the type's fully qualified name. For example, for a type
+/// `my_game::config::CameraSettings`, the environment variable would be
+/// `CONFIG_CameraSettings`. The `CONFIG_` prefix is [`ConfigFile::ENV_PREFIX`], and
+/// [`ConfigFile::ENV_VAR`] replaces the whole name.
+///
+/// The environment variable should contain a JSON object with the fields to override.
+/// If the config itself is an array or scalar, the override replaces it entirely.
+///
+/// Configs that are `#[serde(untagged)]` enums are overridden like structs: the override's
+/// fields are merged into the variant loaded from the file, then the merged object picks a
+/// variant by shape again. Because the file's fields are kept, an override can adjust the
+/// current variant, but switching variants may need `#[serde(deny_unknown_fields)]` on them.
+/// An override set with [`set_override`] takes precedence over the environment variable.
+///
+/// Single fields can be set with a variable per field, named after the override variable
+/// and the field path joined by `__`: `CONFIG_CameraSettings__pan_speed=2000`, or
+/// `CONFIG_Settings__audio__volume=0.5` for a nested field. These are applied after the
+/// whole-object override, so the more specific variable wins. Values are parsed as JSON if
+/// possible and as a string otherwise.
+///
+/// Fallback values go in `CONFIG_DEFAULT_{TypeName}` instead, such as container-level
+/// defaults. That object is merged beneath the file (above
+/// [`ConfigFile::LAYER_DEFAULTS`]), so it only fills fields the file leaves out.
+///
+/// # Command-Line Overrides
+///
+/// Arguments of the form `--config {TypeName}.{field}=VALUE` (or `--config=...`) are applied
+/// after the environment overrides, so they win. Nested fields are reached with further
+/// `.`-separated segments. `VALUE` is parsed as JSON if possible and as a string otherwise,
+/// so `--config CameraSettings.pan_speed=2000` sets a number. See [`set_cli_args`] to
+/// supply the arguments explicitly.
+///
+/// A top-level field can be adjusted rather than replaced with an operator object:
+/// `{"pan_speed": {"$mul": 2}}` doubles the file value and `{"lives": {"$add": 1}}` adds one.
+///
+/// Overrides of fields marked [`attributes::NoEnvOverride`] are ignored with a warning, as are
+/// their `CONFIG_DEFAULT_{TypeName}` values.
+/// By default nested objects are merged key by key (see [`merge_json`]), so
+/// `{"audio": {"volume": 0.5}}` keeps every other field under `audio`. Arrays are replaced,
+/// unless [`ConfigFile::ARRAY_MERGE_KEY`] is set. Set [`ConfigFile::MERGE_STRATEGY`] to
+/// replace top-level fields entirely instead.
+/// [`ConfigFile::on_override`] is called for each overridden field.
+///
+/// # Type Parameters
+///
+/// * `T` - The configuration type to load. Must implement `Deserialize`, `Serialize`,
+///   and `ConfigFile`.
+///
+/// # Returns
 ///
 /// * `Ok(T)` - The loaded and potentially overridden configuration
 /// * `Err(LoadConfigError)` - If any error occurs during loading or parsing
@@ -395,16 +1613,38 @@ where
 /// - The JSON content is invalid (`LoadConfigError::Json`)
 /// - The RON content is invalid (`LoadConfigError::Ron`)
 /// - The file extension is not supported (`LoadConfigError::UnsupportedFormat`)
+/// - The [`ConfigFile::ENV_DOC_KEY`] is missing from the shared document (`LoadConfigError::EnvDocKeyMissing`)
+/// - The [`ConfigFile::ROOT_KEY`] is missing from the file (`LoadConfigError::RootKeyMissing`)
+/// - The file's schema version is outside [`ConfigFile::MIN_SCHEMA_VERSION`] to [`ConfigFile::MAX_SCHEMA_VERSION`] (`LoadConfigError::SchemaVersion`)
+/// - A YAML mapping repeats a key, with [`ConfigFile::DENY_DUPLICATE_KEYS`] (`LoadConfigError::DuplicateKey`)
+/// - A `${VAR}` token names an unset variable, with [`ConfigFile::EXPAND_ENV_VARS`] (`LoadConfigError::MissingEnvVar`)
+/// - Reading the file takes longer than [`ConfigFile::READ_TIMEOUT_MS`] (`LoadConfigError::Timeout`)
+/// - The file doesn't match its `.sha256` sidecar, with [`ConfigFile::VERIFY_CHECKSUM`] (`LoadConfigError::Checksum`)
+/// - Any of the above file errors occurs while an override is set (`LoadConfigError::OverrideIgnored`)
 /// - The environment variable contains invalid JSON (`LoadConfigError::Json`)
+/// - The override is not a JSON object but the config is (`LoadConfigError::OverrideNotObject`)
+/// - An override field is unknown or has the wrong type, for types with reflection info (`LoadConfigError::IncompatibleOverride`)
+/// - A `$mul` or `$add` override targets a non-numeric value (`LoadConfigError::InvalidOperator`)
+/// - A `${self.path}` reference is missing or cyclic (`LoadConfigError::Reference`)
+/// - A value lies outside its range comment, with [`ConfigFile::CHECK_COMMENT_RANGES`] (`LoadConfigError::Validation`)
+/// - An `extends:` or `includes:` chain is cyclic or names an invalid file (`LoadConfigError::Extends`)
 /// - The deserialization fails (`LoadConfigError::Json`)
+/// - [`ConfigFile::validate_invariants`] reports broken rules (`LoadConfigError::InvariantViolations`)
+///
+/// With the `field_docs` feature, an error about a missing or mistyped field with a doc
+/// comment is wrapped in `LoadConfigError::FieldHelp`, for types with reflection info.
+///
+/// Field [`attributes`] such as [`NoEnvOverride`](attributes::NoEnvOverride) are read from
+/// `T`'s reflection info.
 ///
 /// # Example
 ///
 /// ```no_run
+/// use bevy::prelude::*;
 /// use bevy_config_file::{ConfigFile, load_config_file};
 /// use serde::{Deserialize, Serialize};
 ///
-/// #[derive(Debug, Serialize, Deserialize)]
+/// #[derive(Reflect, Debug, Serialize, Deserialize)]
 /// struct AudioSettings {
 ///     volume: f32,
 ///     muted: bool,
@@ -421,50 +1661,808 @@ where
 /// // CONFIG_AudioSettings='{"volume": 0.5}' cargo run
 /// ```
 pub fn load_config_file<T>() -> Result<T, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    attributes::with_type_info(T::type_info(), load_config_file_untyped::<T>)
+}
+
+/// The body of [`load_config_file`], reading field attributes from the reflection info
+/// scoped by the caller.
+fn load_config_file_untyped<T>() -> Result<T, LoadConfigError>
 where
     T: for<'de> Deserialize<'de> + Serialize + ConfigFile,
 {
-    let config_path = T::PATH;
+    let (override_source, json_override) = json_override::<T>()?;
 
-    // Load file content
-    let content = fs::read_to_string(config_path).map_err(LoadConfigError::Io)?;
+    let ignore_override = |error| match json_override {
+        Some(_) => LoadConfigError::OverrideIgnored {
+            override_source: override_source.clone(),
+            error: Box::new(error),
+        },
+        None => error,
+    };
 
-    // Parse based on file extension
-    let ext = config_path.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
-    let base_config: T = match ext {
-        #[cfg(feature = "yaml")]
-        "yaml" | "yml" => serde_yml::from_str(&content).map_err(LoadConfigError::Yaml)?,
-        #[cfg(feature = "json")]
-        "json" => serde_json::from_str(&content).map_err(LoadConfigError::Json)?,
-        #[cfg(feature = "ron")]
-        "ron" => ron::from_str(&content).map_err(LoadConfigError::Ron)?,
-        other => return Err(LoadConfigError::UnsupportedFormat(other.to_string())),
+    let field_overrides = field_overrides::<T>();
+    if json_override.is_none() && field_overrides.is_empty() && !needs_json_base::<T>(true) {
+        return with_field_help(load_base_config::<T, T>(true).and_then(check_invariants));
+    }
+    let base_json = load_base_json::<T>(true).map_err(ignore_override)?;
+    let json_override = json_override.map(|json| (override_source, json));
+    let config = apply_overrides::<T>(base_json, json_override, field_overrides);
+    with_field_help(config)
+}
+
+/// Returns `T`'s whole-config override string with its source: an in-memory
+/// override, the environment variable, or a section of the document override.
+///
+/// Without an override, the source is the environment variable that would hold one.
+fn json_override<T: ConfigFile>() -> Result<(String, Option<String>), LoadConfigError> {
+    let env_var_name = env_var_name::<T>();
+    Ok(
+        match (
+            capture::in_memory_override(overrides::stored_override::<T>),
+            capture::env_var(&env_var_name),
+        ) {
+            (Some(json), _) => ("an in-memory override".to_string(), Some(json)),
+            (None, Some(json)) => (env_var_name, Some(json)),
+            (None, None) => match sections::document_override::<T>()? {
+                Some((document_source, json)) => (document_source, Some(json)),
+                None => (env_var_name, None),
+            },
+        },
+    )
+}
+
+/// Returns `T`'s `(source, field path, value)` field overrides.
+///
+/// Per-field environment overrides are applied after the whole-object override, and
+/// command-line overrides after both.
+fn field_overrides<T: ConfigFile>() -> Vec<(String, String, JsonValue)> {
+    let mut field_overrides = env_field_overrides::<T>();
+    field_overrides.extend(
+        capture::cli_overrides(|| cli::cli_overrides(short_type_name::<T>()))
+            .into_iter()
+            .map(|(field, value)| ("the command line".to_string(), field, value)),
+    );
+    field_overrides
+}
+
+/// Loads configuration from `content` like [`load_config_file`] would from `T`'s file, without
+/// touching the filesystem.
+///
+/// `content` is parsed in the format given by the extension of [`ConfigFile::PATH`], so it
+/// is YAML for a `.yaml` config. The [`ConfigFile::ROOT_KEY`] section is taken and
+/// migrated, then the environment, in-memory and command-line overrides are applied as
+/// usual. `extends:`, `includes:`, child configs, the common file and default layers are
+/// not supported, since they name other files.
+///
+/// This lets tests exercise loading without temp files or changing the working directory.
+///
+/// # Errors
+///
+/// Returns the parse, override, deserialization and invariant errors of
+/// [`load_config_file`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, load_config_from_str};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Debug, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+///     muted: bool,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// let config = load_config_from_str::<AudioSettings>("volume: 0.5\nmuted: false\n").unwrap();
+/// assert_eq!(config.volume, 0.5);
+/// ```
+pub fn load_config_from_str<T>(content: &str) -> Result<T, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + ConfigFile + Typed,
+{
+    load_config_from_document(parse_config(T::PATH, content)?)
+}
+
+/// Like [`load_config_file`], but starts from `document`, the parsed contents of `T`'s
+/// config file read by other means, such as Bevy's asset server.
+///
+/// The [`ConfigFile::ROOT_KEY`] section is taken and migrated, then overrides are applied
+/// as usual. `extends:`, `includes:`, child configs and default layers are not supported.
+pub(crate) fn load_config_from_document<T>(document: JsonValue) -> Result<T, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + ConfigFile + Typed,
+{
+    attributes::with_type_info(T::type_info(), || {
+        let mut document = descend_root_key::<T>(document, T::PATH)?;
+        versions::migrate_file::<T>(&mut document)?;
+        let (override_source, json_override) = json_override::<T>()?;
+        let json_override = json_override.map(|json| (override_source, json));
+        with_field_help(apply_overrides::<T>(
+            document,
+            json_override,
+            field_overrides::<T>(),
+        ))
+    })
+}
+
+/// Adds the doc comment of the field a deserialization error is about, with the
+/// `field_docs` feature.
+pub(crate) fn with_field_help<T: 'static>(
+    result: Result<T, LoadConfigError>,
+) -> Result<T, LoadConfigError> {
+    #[cfg(feature = "field_docs")]
+    let result = result.map_err(field_docs::with_field_help::<T>);
+    result
+}
+
+/// Loads configuration from the file like [`load_config_file`], but overrides it with a typed
+/// value instead of the `CONFIG_{TypeName}` environment variable.
+///
+/// `override_value` is serialized to JSON and merged exactly as an environment override
+/// would be, so it is typically a partial struct holding only the fields to change. The
+/// environment variable, any [`set_override`] value and command-line overrides are ignored,
+/// which makes this useful in tests and tools.
+///
+/// # Errors
+///
+/// Returns the same errors as [`load_config_file`]. File errors are wrapped in
+/// `LoadConfigError::OverrideIgnored`, and an `override_value` that fails to serialize is
+/// reported as `LoadConfigError::Json`.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, load_config_file_with_override};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Debug, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+///     muted: bool,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// #[derive(Serialize)]
+/// struct AudioOverride {
+///     muted: bool,
+/// }
+///
+/// let config =
+///     load_config_file_with_override::<AudioSettings>(&AudioOverride { muted: true }).unwrap();
+/// assert!(config.muted);
+/// ```
+pub fn load_config_file_with_override<T>(
+    override_value: &impl Serialize,
+) -> Result<T, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    let override_source = "a typed override".to_string();
+    let json_override = serde_json::to_string(override_value).map_err(LoadConfigError::Json)?;
+
+    attributes::with_type_info(T::type_info(), || {
+        let ignore_override = |error| LoadConfigError::OverrideIgnored {
+            override_source: override_source.clone(),
+            error: Box::new(error),
+        };
+        let base_json = load_base_json::<T>(true).map_err(ignore_override)?;
+        let json_override = Some((override_source, json_override));
+        with_field_help(apply_overrides::<T>(base_json, json_override, Vec::new()))
+    })
+}
+
+/// Loads configuration from the files alone, without reading any environment variable.
+///
+/// The files are loaded as [`load_config_file`] would with an empty environment: the
+/// [`ConfigFile::DEFAULT_LOCALE`] is substituted and no variant is applied, and the common
+/// file, default layer, field directory, layered paths, migrations and references are
+/// handled as usual. Overrides from the environment, [`set_override`] and the command line
+/// are skipped, as are [`ConfigFile::ENV_DOC_VAR`] and `CONFIG_DEFAULT_{TypeName}`. This
+/// keeps unit tests deterministic without scrubbing the environment.
+///
+/// # Errors
+///
+/// Returns the file, parse, migration and invariant errors of [`load_config_file`].
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, load_config_file_no_env};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Debug, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// // CONFIG_AudioSettings is ignored
+/// let config = load_config_file_no_env::<AudioSettings>().unwrap();
+/// ```
+pub fn load_config_file_no_env<T>() -> Result<T, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    attributes::with_type_info(T::type_info(), || {
+        if !needs_json_base::<T>(false) {
+            return with_field_help(load_base_config::<T, T>(false).and_then(check_invariants));
+        }
+        let config = load_base_json::<T>(false)
+            .and_then(|base_json| apply_overrides::<T>(base_json, None, Vec::new()));
+        with_field_help(config)
+    })
+}
+
+/// Whether `T`'s file must be loaded as JSON before it can be deserialized.
+///
+/// References are unresolved strings, and defaulted files and files with a
+/// [`ConfigFile::COMMON_PATH`] may omit fields, so such files can't be deserialized into
+/// `T` directly. Range comments are checked against the JSON, and versioned files may need
+/// migrating first. `CONFIG_DEFAULT_{TypeName}` is only considered if `read_env` is set.
+fn needs_json_base<T: ConfigFile>(read_env: bool) -> bool {
+    (read_env && capture::env_var(&env_default_var_name::<T>()).is_some())
+        || T::RESOLVE_REFERENCES
+        || T::CHECK_COMMENT_RANGES
+        || T::SCHEMA_VERSION.is_some()
+        || T::COMMON_PATH.is_some()
+        || defaults::has_default_layer::<T>()
+}
+
+/// Loads `T`'s base config (including any common file and default layer) as JSON, ready
+/// for overrides. Unless `read_env` is set, the environment is ignored, as described for
+/// [`load_config_file_no_env`].
+///
+/// Unless [`needs_json_base`], the file is deserialized into `T` first so serde defaults
+/// are filled in.
+pub(crate) fn load_base_json<T>(read_env: bool) -> Result<JsonValue, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile,
+{
+    if !needs_json_base::<T>(read_env) {
+        let base_config: T = load_base_config::<T, T>(read_env)?;
+        return serde_json::to_value(&base_config).map_err(LoadConfigError::Json);
+    }
+    let mut file_json = load_base_config::<T, JsonValue>(read_env)?;
+    versions::migrate_file::<T>(&mut file_json)?;
+    if let Some(common_path) = T::COMMON_PATH {
+        let common_path = T::resolve_path(common_path);
+        let content = read_config_file::<T>(Path::new(&common_path))?;
+        let mut common_json: JsonValue = parse_config(&common_path, &content)?;
+        merge::merge_json(&mut common_json, file_json);
+        file_json = common_json;
+    }
+    let env_default_var = env_default_var_name::<T>();
+    let env_default = if read_env {
+        capture::env_var(&env_default_var)
+    } else {
+        None
     };
+    if let Some(env_default) = env_default {
+        let mut env_default = overrides::parse_override::<T>(&env_default)?;
+        let protected = attributes::type_attribute_paths::<T, attributes::NoEnvOverride>();
+        strip_protected::<T>(&[], &mut env_default, &protected, &env_default_var);
+        merge::merge_json(&mut env_default, file_json);
+        file_json = env_default;
+    }
+    match defaults::default_layer::<T>().transpose()? {
+        Some(mut defaults) => {
+            merge::merge_json(&mut defaults, file_json);
+            Ok(defaults)
+        }
+        None => Ok(file_json),
+    }
+}
+
+/// Merges the JSON override (with its source) and then the `(source, field path, value)`
+/// field overrides into `base_json`, resolves references if enabled, and deserializes the
+/// result.
+pub(crate) fn apply_overrides<T>(
+    mut base_json: JsonValue,
+    json_override: Option<(String, String)>,
+    field_overrides: Vec<(String, String, JsonValue)>,
+) -> Result<T, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + ConfigFile,
+{
+    let protected = attributes::type_attribute_paths::<T, attributes::NoEnvOverride>();
+    // Restored once every override is applied, in case one replaced a whole parent object
+    let protected_values: Vec<(String, JsonValue)> = protected
+        .iter()
+        .filter_map(|path| {
+            let last = path.last()?;
+            let value = redact::parent_object(&mut base_json, path)?.get(*last)?;
+            Some((path.join("."), value.clone()))
+        })
+        .collect();
+
+    if let Some((override_source, json_override)) = json_override {
+        let mut json_override = overrides::parse_override::<T>(&json_override)?;
+        strip_protected::<T>(&[], &mut json_override, &protected, &override_source);
+
+        match (&mut base_json, json_override, T::ARRAY_MERGE_KEY) {
+            (JsonValue::Object(base_map), JsonValue::Object(override_map), _) => {
+                let problems = shape::override_problems::<T>(base_map, &override_map);
+                if !problems.is_empty() {
+                    return Err(LoadConfigError::IncompatibleOverride {
+                        override_source,
+                        problems,
+                    });
+                }
+                for (key, value) in override_map {
+                    let value = operators::apply_operator(&key, base_map.get(&key), value)?;
+                    let leaves = merge::overridden_leaves(
+                        T::MERGE_STRATEGY,
+                        &key,
+                        base_map.get(&key),
+                        &value,
+                    );
+                    for (path, old, new) in &leaves {
+                        T::on_override(
+                            path,
+                            &redact::redact_field::<T>(path, old),
+                            &redact::redact_field::<T>(path, new),
+                        );
+                        report::overridden_field(path);
+                    }
+                    merge::merge_field(T::MERGE_STRATEGY, base_map, key, value, T::ARRAY_MERGE_KEY);
+                }
+            }
+            (JsonValue::Object(_), other, _) => {
+                return Err(LoadConfigError::OverrideNotObject {
+                    override_source,
+                    found: json_kind(&other),
+                });
+            }
+            (JsonValue::Array(base_entries), JsonValue::Array(entries), Some(merge_key)) => {
+                merge::merge_array_by_key(base_entries, entries, merge_key);
+                report::override_applied();
+            }
+            // A config whose root is an array or scalar is replaced outright
+            (base, other, _) => {
+                *base = other;
+                report::override_applied();
+            }
+        }
+    }
+
+    for (override_source, field, mut value) in field_overrides {
+        let path: Vec<&str> = field.split('.').collect();
+        if !strip_protected::<T>(&path, &mut value, &protected, &override_source) {
+            continue;
+        }
+        let old = cli::set_path(&mut base_json, &field, value.clone());
+        T::on_override(
+            &field,
+            &redact::redact_field::<T>(&field, &old),
+            &redact::redact_field::<T>(&field, &value),
+        );
+        report::overridden_field(&field);
+    }
+
+    for (path, value) in protected_values {
+        cli::set_path(&mut base_json, &path, value);
+    }
+
+    if T::RESOLVE_REFERENCES {
+        references::resolve_references(&mut base_json)?;
+    }
+    if T::CHECK_COMMENT_RANGES {
+        ranges::check_comment_ranges::<T>(&base_json)?;
+    }
+
+    let config = serde_json::from_value(base_json).map_err(LoadConfigError::Json)?;
+    check_invariants(config)
+}
+
+/// Fails with `LoadConfigError::InvariantViolations` if `config` breaks any of its
+/// [`ConfigFile::validate_invariants`] rules.
+fn check_invariants<T: ConfigFile>(config: T) -> Result<T, LoadConfigError> {
+    let violations = config.validate_invariants();
+    if !violations.is_empty() {
+        return Err(LoadConfigError::InvariantViolations(violations));
+    }
+    Ok(config)
+}
+
+/// Removes the values of [`attributes::NoEnvOverride`] fields from `value`, an override of
+/// the field at `path` (empty for the whole config), warning about each one.
+///
+/// Returns `false`, after warning, if `path` lies within a protected field, in which case
+/// the whole override must be ignored.
+fn strip_protected<T>(
+    path: &[&str],
+    value: &mut JsonValue,
+    protected: &[Vec<&str>],
+    override_source: &str,
+) -> bool {
+    for protected_path in protected {
+        if path.starts_with(protected_path) {
+            warn_protected_override(short_type_name::<T>(), &path.join("."), override_source);
+            return false;
+        }
+        let Some(nested) = protected_path.strip_prefix(path) else {
+            continue;
+        };
+        let removed = nested.last().and_then(|last| {
+            redact::parent_object(value, nested).and_then(|map| map.remove(*last))
+        });
+        if removed.is_some() {
+            let field = protected_path.join(".");
+            warn_protected_override(short_type_name::<T>(), &field, override_source);
+        }
+    }
+    true
+}
+
+/// Logs that an override of a [`attributes::NoEnvOverride`] field was ignored.
+fn warn_protected_override(type_name: &str, field: &str, override_source: &str) {
+    let message = format!(
+        "ignoring override of protected field {}.{} from {}",
+        type_name, field, override_source
+    );
+    #[cfg(feature = "logging")]
+    warn!("{}", message);
+    report::warning(message);
+}
+
+/// Describes the kind of a JSON value for error messages.
+fn json_kind(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "a boolean",
+        JsonValue::Number(_) => "a number",
+        JsonValue::String(_) => "a string",
+        JsonValue::Array(_) => "an array",
+        JsonValue::Object(_) => "an object",
+    }
+}
 
-    // Apply environment variable overrides (always JSON)
-    let type_name = std::any::type_name::<T>()
+/// Returns the name of the environment variable holding `T`'s JSON override:
+/// `CONFIG_{TypeName}`, or `CONFIG_{id}_{TypeName}` with a [`ConfigFile::APP_ID_VAR`] set,
+/// where `CONFIG_` is [`ConfigFile::ENV_PREFIX`]. [`ConfigFile::ENV_VAR`] replaces the
+/// whole name, and an entry set with [`set_env_var_names`] replaces both.
+pub(crate) fn env_var_name<T: ConfigFile>() -> String {
+    if let Some(var) = env_names::mapped_env_var_name::<T>() {
+        return var;
+    }
+    if let Some(var) = T::ENV_VAR {
+        return var.to_string();
+    }
+    match T::APP_ID_VAR.and_then(capture::env_var) {
+        Some(app_id) => format!("{}{}_{}", T::ENV_PREFIX, app_id, short_type_name::<T>()),
+        None => format!("{}{}", T::ENV_PREFIX, short_type_name::<T>()),
+    }
+}
+
+/// Returns the name of the environment variable holding `T`'s fallback values, merged
+/// beneath the file: `CONFIG_DEFAULT_{TypeName}`, with any [`ConfigFile::APP_ID_VAR`] id
+/// after `DEFAULT_`, or `{name}_DEFAULT` with a [`ConfigFile::ENV_VAR`] or
+/// [`set_env_var_names`] name.
+pub(crate) fn env_default_var_name<T: ConfigFile>() -> String {
+    let verbatim = env_names::mapped_env_var_name::<T>().or(T::ENV_VAR.map(String::from));
+    if let Some(var) = verbatim {
+        return format!("{}_DEFAULT", var);
+    }
+    let name = env_var_name::<T>();
+    let unprefixed = name.strip_prefix(T::ENV_PREFIX).unwrap_or(&name);
+    format!("{}DEFAULT_{}", T::ENV_PREFIX, unprefixed)
+}
+
+/// Returns the `(variable name, field path, value)` overrides from per-field environment
+/// variables, such as `CONFIG_CameraSettings__pan_speed=2000`, sorted by variable name.
+///
+/// Each `__` after the [`env_var_name`] separates a path segment, so
+/// `CONFIG_Settings__audio__volume` sets `audio.volume`. Values are parsed as JSON when
+/// possible and used as plain strings otherwise.
+pub(crate) fn env_field_overrides<T: ConfigFile>() -> Vec<(String, String, JsonValue)> {
+    let prefix = format!("{}__", env_var_name::<T>());
+    let mut overrides: Vec<(String, String, JsonValue)> = capture::env_vars_with_prefix(&prefix)
+        .into_iter()
+        .filter_map(|(var, value)| {
+            let path = var.strip_prefix(&prefix)?.replace("__", ".");
+            let value = cli::coerce_value(&value);
+            Some((var, path, value))
+        })
+        .filter(|(_, path, _)| !path.is_empty())
+        .collect();
+    overrides.sort_by(|a, b| a.0.cmp(&b.0));
+    overrides
+}
+
+/// Returns the last component of `T`'s fully qualified type name.
+pub(crate) fn short_type_name<T>() -> &'static str {
+    std::any::type_name::<T>()
         .split("::")
         .last()
-        .expect("type name should have at least one component");
-    let env_var_name = format!("CONFIG_{type_name}");
+        .expect("type name should have at least one component")
+}
+
+/// Reads and parses the base config (from `T::ENV_DOC_VAR`, `T::FIELD_DIR`, `T::PATH` or
+/// the layered paths), assembling any child configs, without applying overrides.
+///
+/// Unless `read_env` is set, `T::ENV_DOC_VAR` is ignored and paths are resolved without
+/// the locale and variant variables.
+fn load_base_config<T, V>(read_env: bool) -> Result<V, LoadConfigError>
+where
+    T: ConfigFile,
+    V: for<'de> Deserialize<'de>,
+{
+    if let Some(var) = T::ENV_DOC_VAR.filter(|_| read_env) {
+        if let Some(document) = capture::env_var(var) {
+            let mut base_json = extract_env_doc_subtree(var, &document, T::ENV_DOC_KEY)?;
+            compose::assemble_children(&mut base_json, T::CHILDREN)?;
+            return serde_json::from_value(base_json).map_err(LoadConfigError::Json);
+        }
+    }
+    if let Some(dir) = locale::field_dir::<T>() {
+        let mut base_json = compose::load_field_dir::<T>(&dir)?;
+        compose::assemble_children(&mut base_json, T::CHILDREN)?;
+        return serde_json::from_value(base_json).map_err(LoadConfigError::Json);
+    }
+    if !T::LAYER_PATHS.is_empty() {
+        let base_json = layered::load_layers::<T>(read_env)?;
+        return serde_json::from_value(base_json).map_err(LoadConfigError::Json);
+    }
+
+    let config_path = if read_env {
+        locale::load_path::<T>()
+    } else {
+        locale::env_free_config_path::<T>()
+    };
+    load_base_file::<T, V>(&config_path)
+}
+
+/// Reads and parses the config file at `config_path` for `T`, resolving `extends:` and
+/// assembling any child configs.
+pub(crate) fn load_base_file<T, V>(config_path: &str) -> Result<V, LoadConfigError>
+where
+    T: ConfigFile,
+    V: for<'de> Deserialize<'de>,
+{
+    let layered = T::EXTENDS || T::INCLUDES;
+    if !layered && T::ROOT_KEY.is_none() && T::CHILDREN.is_empty() {
+        // Parse based on file extension straight into the target type
+        let content = read_config_file::<T>(Path::new(config_path))?;
+        return parse_config(config_path, &content);
+    }
+
+    let mut base_json: JsonValue = if layered {
+        let layering = extends::Layering {
+            extends: T::EXTENDS,
+            includes: T::INCLUDES,
+            read: read_config_file::<T>,
+        };
+        extends::load_layered(config_path, layering)?
+    } else {
+        let content = read_config_file::<T>(Path::new(config_path))?;
+        parse_config(config_path, &content)?
+    };
+
+    base_json = descend_root_key::<T>(base_json, config_path)?;
+    if !T::CHILDREN.is_empty() {
+        compose::assemble_children(&mut base_json, T::CHILDREN)?;
+    }
+    serde_json::from_value(base_json).map_err(LoadConfigError::Json)
+}
+
+/// Reads the config file at `path` for `T`, decrypting it if `T` is encrypted.
+pub(crate) fn read_config_file<T: ConfigFile>(path: &Path) -> Result<String, LoadConfigError> {
+    let content = read_raw_config_file::<T>(path)?;
+    let content = if T::EXPAND_ENV_VARS {
+        substitution::expand_env_vars::<T>(&path.to_string_lossy(), &content)?
+    } else {
+        content
+    };
 
-    if let Ok(json_override) = env::var(&env_var_name) {
-        let json_override: JsonValue =
-            serde_json::from_str(&json_override).map_err(LoadConfigError::Json)?;
+    if T::DENY_DUPLICATE_KEYS && is_yaml_path(&path.to_string_lossy()) {
+        duplicates::check_duplicate_keys(&path.to_string_lossy(), &content)?;
+    }
+    Ok(content)
+}
+
+/// Reads the text of `T`'s config file at `path` like [`read_config_file`], but without
+/// expanding `${VAR}` tokens or checking for duplicate keys.
+pub(crate) fn read_raw_config_file<T: ConfigFile>(path: &Path) -> Result<String, LoadConfigError> {
+    let bytes = read_bytes::<T>(path)?;
+    report::read_file(path);
+    #[cfg(feature = "checksum")]
+    if T::VERIFY_CHECKSUM {
+        checksum::verify(path, &bytes, T::read_file)?;
+    }
+    #[cfg(feature = "encryption")]
+    let content = if T::ENCRYPTED {
+        encryption::decrypt(&bytes)?
+    } else {
+        utf8_content(bytes)?
+    };
+    #[cfg(not(feature = "encryption"))]
+    let content = utf8_content(bytes)?;
+    Ok(content)
+}
+
+/// Converts the bytes of a plain-text config file to a string.
+fn utf8_content(bytes: Vec<u8>) -> Result<String, LoadConfigError> {
+    String::from_utf8(bytes).map_err(|error| {
+        LoadConfigError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+    })
+}
+
+/// Reads the raw bytes at `path` with [`ConfigFile::read_file`], giving up after
+/// [`ConfigFile::READ_TIMEOUT_MS`] if set.
+fn read_bytes<T: ConfigFile>(path: &Path) -> Result<Vec<u8>, LoadConfigError> {
+    capture::read_file(path, || read_bytes_uncaptured::<T>(path))
+}
+
+/// The body of [`read_bytes`], reading the file itself.
+fn read_bytes_uncaptured<T: ConfigFile>(path: &Path) -> Result<Vec<u8>, LoadConfigError> {
+    let Some(timeout_ms) = T::READ_TIMEOUT_MS else {
+        return T::read_file(path).map_err(LoadConfigError::Io);
+    };
 
-        let mut base_json =
-            serde_json::to_value(&base_config).map_err(LoadConfigError::Json)?;
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let owned_path = path.to_path_buf();
+    std::thread::spawn(move || {
+        // The receiver is gone if the read timed out, so there is no one to tell
+        let _ = sender.send(T::read_file(&owned_path));
+    });
+    match receiver.recv_timeout(std::time::Duration::from_millis(timeout_ms)) {
+        Ok(result) => result.map_err(LoadConfigError::Io),
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => Err(LoadConfigError::Timeout {
+            path: path.display().to_string(),
+            timeout_ms,
+        }),
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => Err(LoadConfigError::Io(
+            std::io::Error::other("the thread reading the config file panicked"),
+        )),
+    }
+}
 
-        if let (JsonValue::Object(base_map), JsonValue::Object(override_map)) =
-            (&mut base_json, json_override)
-        {
-            for (key, value) in override_map {
-                base_map.insert(key, value);
+/// Returns the value under [`ConfigFile::ROOT_KEY`] in the file read from `path`, or the
+/// whole file if no root key is set.
+pub(crate) fn descend_root_key<T: ConfigFile>(
+    file: JsonValue,
+    path: &str,
+) -> Result<JsonValue, LoadConfigError> {
+    let Some(key) = T::ROOT_KEY else {
+        return Ok(file);
+    };
+    match file {
+        JsonValue::Object(mut map) => map.remove(key),
+        _ => None,
+    }
+    .ok_or_else(|| LoadConfigError::RootKeyMissing {
+        path: path.to_string(),
+        key: key.to_string(),
+    })
+}
+
+/// Parses a shared JSON document from the environment variable `var` and returns the
+/// value under `key`, or the whole document if `key` is `None`.
+fn extract_env_doc_subtree(
+    var: &str,
+    document: &str,
+    key: Option<&str>,
+) -> Result<JsonValue, LoadConfigError> {
+    let document: JsonValue = serde_json::from_str(document).map_err(LoadConfigError::Json)?;
+    match key {
+        None => Ok(document),
+        Some(key) => match document {
+            JsonValue::Object(mut map) => {
+                map.remove(key)
+                    .ok_or_else(|| LoadConfigError::EnvDocKeyMissing {
+                        var: var.to_string(),
+                        key: key.to_string(),
+                    })
             }
+            _ => Err(LoadConfigError::EnvDocKeyMissing {
+                var: var.to_string(),
+                key: key.to_string(),
+            }),
+        },
+    }
+}
+
+/// Asserts that a config file deserializes into the given type, panicking with a clear
+/// message if it does not.
+///
+/// Use this in a `#[test]` to catch drift between shipped config assets and the structs
+/// that load them. The path defaults to the type's [`ConfigFile::PATH`] and is resolved
+/// against the current directory (the package root under `cargo test`). Environment
+/// overrides are not applied.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy_config_file::{ConfigFile, assert_config_parses};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct CameraSettings {
+///     pan_speed: f32,
+/// }
+///
+/// impl ConfigFile for CameraSettings {
+///     const PATH: &'static str = "assets/config/camera_settings.yaml";
+/// }
+///
+/// #[test]
+/// fn shipped_configs_parse() {
+///     assert_config_parses!(CameraSettings);
+///     assert_config_parses!(CameraSettings, "assets/config/camera_settings.dev.yaml");
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_config_parses {
+    ($ty:ty) => {
+        $crate::assert_config_parses!($ty, <$ty as $crate::ConfigFile>::PATH)
+    };
+    ($ty:ty, $path:expr) => {{
+        let path: &str = $path;
+        if let Err(error) = $crate::__parse_config_file::<$ty>(path) {
+            panic!(
+                "config file {} does not match {}: {}",
+                path,
+                stringify!($ty),
+                error
+            );
         }
+    }};
+}
 
-        serde_json::from_value(base_json).map_err(LoadConfigError::Json)
-    } else {
-        Ok(base_config)
+/// Reads and parses the config file at `path`. Used by [`assert_config_parses`].
+#[doc(hidden)]
+pub fn __parse_config_file<V>(path: &str) -> Result<V, LoadConfigError>
+where
+    V: for<'de> Deserialize<'de>,
+{
+    let content = fs::read_to_string(path).map_err(LoadConfigError::Io)?;
+    parse_config(path, &content)
+}
+
+/// Returns the extension of `path`, lowercased, so `.yml`, `.YAML` and `.yaml` select the
+/// same format.
+pub(crate) fn config_extension(path: &str) -> String {
+    path.rsplit_once('.')
+        .map(|(_, e)| e)
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+/// Returns whether `path` names a YAML file.
+pub(crate) fn is_yaml_path(path: &str) -> bool {
+    matches!(config_extension(path).as_str(), "yaml" | "yml")
+}
+
+/// Parses config file content in the format given by the extension of `path`.
+pub(crate) fn parse_config<V>(path: &str, content: &str) -> Result<V, LoadConfigError>
+where
+    V: for<'de> Deserialize<'de>,
+{
+    match config_extension(path).as_str() {
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => serde_yml::from_str(content).map_err(LoadConfigError::Yaml),
+        #[cfg(feature = "json")]
+        "json" => serde_json::from_str(content).map_err(LoadConfigError::Json),
+        #[cfg(feature = "ron")]
+        "ron" => ron::from_str(content).map_err(LoadConfigError::Ron),
+        #[cfg(feature = "toml")]
+        "toml" => toml::from_str(content).map_err(LoadConfigError::Toml),
+        other => Err(LoadConfigError::UnsupportedFormat(other.to_string())),
     }
 }
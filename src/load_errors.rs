@@ -0,0 +1,52 @@
+//! Collecting config load errors into a resource instead of failing the app.
+
+use bevy::prelude::*;
+use bevy::reflect::Typed;
+use serde::{Deserialize, Serialize};
+
+use crate::{ConfigFile, insert_config, load_config_file, report, short_type_name, telemetry};
+
+/// Errors from configs added with
+/// [`config_file_plugin_capture_errors`](crate::config_file_plugin_capture_errors).
+///
+/// Each entry is `(type_name, message)`, where `type_name` is the short name of the
+/// config type. UI systems can read this resource to show a "config failed to load" screen.
+#[derive(Resource, Debug, Default)]
+pub struct ConfigLoadErrors {
+    /// The captured `(type_name, message)` pairs, in the order they occurred.
+    pub errors: Vec<(String, String)>,
+}
+
+impl ConfigLoadErrors {
+    /// Returns `true` if no errors have been captured.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Loads `T` like [`load_resource_from_config_file`](crate::load_resource_from_config_file),
+/// but records any error in [`ConfigLoadErrors`] instead of returning it.
+pub(crate) fn load_resource_or_capture_error<T>(
+    mut commands: Commands,
+    mut errors: ResMut<ConfigLoadErrors>,
+) where
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    match report::tracking_overrides(load_config_file::<T>) {
+        Ok((config, overridden)) => {
+            insert_config(&mut commands, config, false, overridden);
+        }
+        Err(err) => {
+            telemetry::record::<T>(&mut commands, |counts| counts.errors += 1);
+            #[cfg(feature = "logging")]
+            error!(
+                "failed to load config from {}: {}",
+                crate::locale::config_path::<T>(),
+                err
+            );
+            errors
+                .errors
+                .push((short_type_name::<T>().to_string(), err.to_string()));
+        }
+    }
+}
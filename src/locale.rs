@@ -0,0 +1,191 @@
+//! Substituting a locale into config paths such as `strings.{locale}.yaml`, and picking
+//! deployment variants such as `settings.beta.yaml`.
+
+use std::path::Path;
+
+use crate::{ConfigFile, capture, report, vfs};
+
+/// The placeholder in [`ConfigFile::PATH`] replaced by the current locale.
+const PLACEHOLDER: &str = "{locale}";
+
+/// Returns the path actually read for `T`: the locale substituted into `T::PATH` (if
+/// [`ConfigFile::LOCALE_VAR`] is set), then rewritten by [`ConfigFile::resolve_path`] and
+/// anchored to the platform config directory or the manifest directory.
+///
+/// If the file for the requested locale does not exist, the
+/// [`DEFAULT_LOCALE`](ConfigFile::DEFAULT_LOCALE) file is used instead. Finally, the
+/// [`ConfigFile::VARIANT_VAR`] variant's file is preferred if it exists. If the resulting
+/// file does not exist, the first existing [`ConfigFile::FALLBACK_PATHS`] entry is used.
+pub(crate) fn config_path<T: ConfigFile>() -> String {
+    or_fallback::<T>(write_path::<T>())
+}
+
+/// Like [`config_path`], but warns if the requested locale has no file. Only the load
+/// itself resolves its path this way, so the warning is reported once per load.
+pub(crate) fn load_path<T: ConfigFile>() -> String {
+    or_fallback::<T>(localized_path::<T>(T::PATH, true))
+}
+
+/// Like [`config_path`], but without reading the environment: the
+/// [`DEFAULT_LOCALE`](ConfigFile::DEFAULT_LOCALE) is substituted and no variant is applied,
+/// while [`ConfigFile::FALLBACK_PATHS`] are still tried.
+pub(crate) fn env_free_config_path<T: ConfigFile>() -> String {
+    or_fallback::<T>(default_locale_path::<T>(T::PATH))
+}
+
+/// Returns `path` if it exists, or else the first existing [`ConfigFile::FALLBACK_PATHS`]
+/// entry, falling back to `path` itself so the error names it.
+fn or_fallback<T: ConfigFile>(path: String) -> String {
+    if T::FALLBACK_PATHS.is_empty() || vfs::exists(Path::new(&path)) {
+        return path;
+    }
+    fallback_paths::<T>()
+        .find(|fallback| vfs::exists(Path::new(fallback)))
+        .unwrap_or(path)
+}
+
+/// Returns the paths whose changes can change what is loaded for `T`: every
+/// [`ConfigFile::LAYER_PATHS`] entry, or else the path written by [`write_path`] followed by
+/// every [`ConfigFile::FALLBACK_PATHS`] entry.
+#[cfg(feature = "hot_reload")]
+pub(crate) fn watched_paths<T: ConfigFile>() -> Vec<String> {
+    if !T::LAYER_PATHS.is_empty() {
+        return T::LAYER_PATHS
+            .iter()
+            .map(|path| localized_path::<T>(path, false))
+            .collect();
+    }
+    std::iter::once(write_path::<T>())
+        .chain(fallback_paths::<T>())
+        .collect()
+}
+
+/// Returns `T`'s [`ConfigFile::LAYER_PATHS`], each with the locale and variant applied like
+/// `T::PATH`, warning like [`load_path`] for a missing locale.
+pub(crate) fn layer_paths<T: ConfigFile>() -> Vec<String> {
+    T::LAYER_PATHS
+        .iter()
+        .map(|path| localized_path::<T>(path, true))
+        .collect()
+}
+
+/// Returns `T`'s [`ConfigFile::LAYER_PATHS`] like [`layer_paths`], but with the
+/// [`DEFAULT_LOCALE`](ConfigFile::DEFAULT_LOCALE) substituted and no variant applied.
+pub(crate) fn env_free_layer_paths<T: ConfigFile>() -> Vec<String> {
+    T::LAYER_PATHS
+        .iter()
+        .map(|path| default_locale_path::<T>(path))
+        .collect()
+}
+
+/// Returns `T`'s [`ConfigFile::FIELD_DIR`], resolved like `T::PATH`.
+pub(crate) fn field_dir<T: ConfigFile>() -> Option<String> {
+    T::FIELD_DIR.map(resolve::<T>)
+}
+
+/// Returns `T`'s [`ConfigFile::FALLBACK_PATHS`], each resolved like `T::PATH`.
+fn fallback_paths<T: ConfigFile>() -> impl Iterator<Item = String> {
+    T::FALLBACK_PATHS
+        .iter()
+        .map(|fallback| resolve::<T>(fallback))
+}
+
+/// Returns the path `T` is saved to: like [`config_path`], but never a
+/// [`ConfigFile::FALLBACK_PATHS`] entry, so saving creates the file at `T::PATH` rather
+/// than overwriting a shipped default.
+pub(crate) fn write_path<T: ConfigFile>() -> String {
+    localized_path::<T>(T::PATH, false)
+}
+
+/// Returns `template` with the locale substituted and the variant's file preferred if it
+/// exists, then resolved like `T::PATH`.
+fn localized_path<T: ConfigFile>(template: &str, warn: bool) -> String {
+    let path = locale_path::<T>(template, warn);
+    let Some(variant) = T::VARIANT_VAR
+        .and_then(capture::env_var)
+        .filter(|variant| !variant.is_empty())
+    else {
+        return path;
+    };
+    let variant_path = variant_path(&path, &variant);
+    if vfs::exists(Path::new(&variant_path)) {
+        variant_path
+    } else {
+        path
+    }
+}
+
+/// Inserts `variant` before the extension of `path`, e.g. `settings.beta.yaml`.
+fn variant_path(path: &str, variant: &str) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, variant, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, variant),
+    };
+    path.with_file_name(file_name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Returns `template` with the [`DEFAULT_LOCALE`](ConfigFile::DEFAULT_LOCALE)
+/// substituted, ignoring [`ConfigFile::LOCALE_VAR`] and [`ConfigFile::VARIANT_VAR`].
+fn default_locale_path<T: ConfigFile>(template: &str) -> String {
+    resolve::<T>(&template.replace(PLACEHOLDER, T::DEFAULT_LOCALE))
+}
+
+/// Returns `template` with the locale substituted, before any variant is applied. If `warn`
+/// is set, falling back to the default locale is reported.
+fn locale_path<T: ConfigFile>(template: &str, warn: bool) -> String {
+    let Some(var) = T::LOCALE_VAR else {
+        return resolve::<T>(template);
+    };
+    let default_path = default_locale_path::<T>(template);
+    match capture::env_var(var) {
+        Some(locale) if !locale.is_empty() && locale != T::DEFAULT_LOCALE => {
+            let path = resolve::<T>(&template.replace(PLACEHOLDER, &locale));
+            if vfs::exists(Path::new(&path)) {
+                path
+            } else if !warn {
+                default_path
+            } else {
+                let message = format!(
+                    "no config for locale {} at {}; falling back to {}",
+                    locale, path, default_path
+                );
+                #[cfg(feature = "logging")]
+                bevy::log::warn!("{}", message);
+                report::warning(message);
+                default_path
+            }
+        }
+        _ => default_path,
+    }
+}
+
+/// Applies [`ConfigFile::resolve_path`] to `path`, then joins a relative result onto the
+/// platform config directory if [`ConfigFile::USE_PLATFORM_DIR`] is set, or onto
+/// `CARGO_MANIFEST_DIR` if [`ConfigFile::TEST_RELATIVE_TO_MANIFEST`] is set and Cargo
+/// provided the variable.
+fn resolve<T: ConfigFile>(path: &str) -> String {
+    let path = T::resolve_path(path);
+    #[cfg(feature = "dirs")]
+    let path = match dirs::config_dir() {
+        Some(config_dir) if T::USE_PLATFORM_DIR && Path::new(&path).is_relative() => config_dir
+            .join(T::APP_NAME)
+            .join(&path)
+            .to_string_lossy()
+            .into_owned(),
+        _ => path,
+    };
+    if !T::TEST_RELATIVE_TO_MANIFEST || Path::new(&path).is_absolute() {
+        return path;
+    }
+    match capture::env_var("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) => Path::new(&manifest_dir)
+            .join(&path)
+            .to_string_lossy()
+            .into_owned(),
+        None => path,
+    }
+}
@@ -0,0 +1,268 @@
+//! Strategies for merging overrides and layered files into a config.
+
+use bevy::ecs::component::Mutable;
+use bevy::prelude::*;
+use bevy::reflect::{PartialReflect, ReflectMut, ReflectRef};
+use serde_json::Map;
+
+use crate::JsonValue;
+
+/// How an override's fields are merged into the config loaded from the file.
+///
+/// Select one with [`ConfigFile::MERGE_STRATEGY`](crate::ConfigFile::MERGE_STRATEGY).
+/// Under every strategy, protected fields and [`$mul`/`$add`
+/// operators](crate::load_config_file) work per top-level field. Under [`Deep`](Self::Deep)
+/// and [`Patch`](Self::Patch), [`ConfigFile::on_override`](crate::ConfigFile::on_override)
+/// is called once per leaf value the override sets, with its `.`-separated path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Each top-level field in the override replaces the file's field entirely, so nested
+    /// objects are not merged.
+    ShallowTopLevel,
+    /// Objects are merged recursively with [`merge_json`]; any other value replaces the
+    /// file's value. A `null` override sets the field to `null`. This is the default.
+    ///
+    /// Objects that are missing or `null` in the file are created from the override, so an
+    /// override can fill in an `Option` field the file leaves out.
+    #[default]
+    Deep,
+    /// JSON Merge Patch ([RFC 7386]): like [`Deep`](Self::Deep), but a `null` override
+    /// removes the field, so it falls back to its serde default.
+    ///
+    /// [RFC 7386]: https://www.rfc-editor.org/rfc/rfc7386
+    Patch,
+}
+
+/// Merges the override `value` for `key` into `map` using `strategy`.
+///
+/// Under [`MergeStrategy::ShallowTopLevel`] and [`MergeStrategy::Deep`], top-level arrays
+/// are merged entry by entry when `array_merge_key` is set.
+pub(crate) fn merge_field(
+    strategy: MergeStrategy,
+    map: &mut Map<String, JsonValue>,
+    key: String,
+    value: JsonValue,
+    array_merge_key: Option<&str>,
+) {
+    match (strategy, map.get_mut(&key), value) {
+        (
+            MergeStrategy::ShallowTopLevel | MergeStrategy::Deep,
+            Some(JsonValue::Array(base_entries)),
+            JsonValue::Array(entries),
+        ) => match array_merge_key {
+            Some(merge_key) => merge_array_by_key(base_entries, entries, merge_key),
+            None => *base_entries = entries,
+        },
+        (MergeStrategy::ShallowTopLevel, _, value) => {
+            map.insert(key, value);
+        }
+        (MergeStrategy::Deep, Some(existing), value) => merge_json(existing, value),
+        (MergeStrategy::Deep, None, value) => {
+            map.insert(key, value);
+        }
+        (MergeStrategy::Patch, _, JsonValue::Null) => {
+            map.remove(&key);
+        }
+        (MergeStrategy::Patch, Some(existing), value) => merge_patch(existing, value),
+        (MergeStrategy::Patch, None, mut value) => {
+            remove_nulls(&mut value);
+            map.insert(key, value);
+        }
+    }
+}
+
+/// Returns the `(path, old, new)` of each value that the override `value` for `key` sets
+/// when merged over `old` with `strategy`.
+///
+/// Under [`MergeStrategy::Deep`] and [`MergeStrategy::Patch`], override objects that merge
+/// into an object (or into nothing) are walked down to their leaves, so
+/// `{"audio": {"volume": 0}}` yields `audio.volume`. Otherwise the whole value replaces the
+/// field and `key` itself is returned.
+pub(crate) fn overridden_leaves(
+    strategy: MergeStrategy,
+    key: &str,
+    old: Option<&JsonValue>,
+    value: &JsonValue,
+) -> Vec<(String, JsonValue, JsonValue)> {
+    let mut leaves = Vec::new();
+    collect_leaves(strategy, key.to_string(), old, value, &mut leaves);
+    leaves
+}
+
+fn collect_leaves(
+    strategy: MergeStrategy,
+    path: String,
+    old: Option<&JsonValue>,
+    value: &JsonValue,
+    leaves: &mut Vec<(String, JsonValue, JsonValue)>,
+) {
+    let merges = matches!(strategy, MergeStrategy::Deep | MergeStrategy::Patch)
+        && matches!(old, None | Some(JsonValue::Null | JsonValue::Object(_)));
+    match value {
+        JsonValue::Object(map) if merges && !map.is_empty() => {
+            let old_map = old.and_then(JsonValue::as_object);
+            for (key, value) in map {
+                let old = old_map.and_then(|old_map| old_map.get(key));
+                collect_leaves(strategy, format!("{}.{}", path, key), old, value, leaves);
+            }
+        }
+        _ => leaves.push((path, old.cloned().unwrap_or(JsonValue::Null), value.clone())),
+    }
+}
+
+/// Merges `overlay` into `base`, recursing into objects key by key; scalars and arrays in
+/// `overlay` replace those in `base`.
+///
+/// Keys missing from `base` are inserted, and a `null` (or other non-object) `base` is
+/// replaced by an object `overlay` as a whole, so intermediate objects never need to exist
+/// in the file. This is how [`MergeStrategy::Deep`] overrides, default layers and
+/// `extends:` files are merged.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_config_file::merge_json;
+/// use serde_json::json;
+///
+/// let mut config = json!({"audio": {"volume": 1.0, "muted": false}});
+/// merge_json(&mut config, json!({"audio": {"volume": 0.5}}));
+/// assert_eq!(config, json!({"audio": {"volume": 0.5, "muted": false}}));
+/// ```
+pub fn merge_json(base: &mut JsonValue, overlay: JsonValue) {
+    match (base, overlay) {
+        (JsonValue::Object(base_map), JsonValue::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Merges `new` into the `T` resource of `world` through reflection, instead of replacing
+/// it, so configs can be built up incrementally, e.g. one mod pack at a time.
+///
+/// Struct fields are merged recursively, lists are appended to, and maps and sets gain the
+/// new entries, merging those whose keys already exist. Any other value, including an enum
+/// such as `Option`, is replaced by the new one. If `T` is not in `world` yet, `new` is
+/// inserted as is.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_config_file::merge_config_into_resource;
+///
+/// #[derive(Resource, Reflect, Default)]
+/// struct UnitRoster {
+///     units: Vec<String>,
+/// }
+///
+/// let mut world = World::new();
+/// merge_config_into_resource(&mut world, UnitRoster { units: vec!["knight".into()] });
+/// merge_config_into_resource(&mut world, UnitRoster { units: vec!["archer".into()] });
+/// assert_eq!(world.resource::<UnitRoster>().units, ["knight", "archer"]);
+/// ```
+pub fn merge_config_into_resource<T>(world: &mut World, new: T)
+where
+    T: Resource<Mutability = Mutable> + Reflect,
+{
+    match world.get_resource_mut::<T>() {
+        Some(mut existing) => {
+            merge_reflect(existing.as_partial_reflect_mut(), new.as_partial_reflect())
+        }
+        None => world.insert_resource(new),
+    }
+}
+
+/// Merges the reflected `overlay` into `base`, as described for
+/// [`merge_config_into_resource`].
+fn merge_reflect(base: &mut dyn PartialReflect, overlay: &dyn PartialReflect) {
+    match (base.reflect_mut(), overlay.reflect_ref()) {
+        (ReflectMut::Struct(base), ReflectRef::Struct(overlay)) => {
+            for (name, value) in overlay.iter_fields() {
+                if let Some(field) = base.field_mut(name) {
+                    merge_reflect(field, value);
+                }
+            }
+        }
+        (ReflectMut::TupleStruct(base), ReflectRef::TupleStruct(overlay)) => {
+            for (index, value) in overlay.iter_fields().enumerate() {
+                if let Some(field) = base.field_mut(index) {
+                    merge_reflect(field, value);
+                }
+            }
+        }
+        (ReflectMut::List(base), ReflectRef::List(overlay)) => {
+            for value in overlay.iter() {
+                base.push(value.to_dynamic());
+            }
+        }
+        (ReflectMut::Map(base), ReflectRef::Map(overlay)) => {
+            for (key, value) in overlay.iter() {
+                match base.get_mut(key) {
+                    Some(existing) => merge_reflect(existing, value),
+                    None => {
+                        base.insert_boxed(key.to_dynamic(), value.to_dynamic());
+                    }
+                }
+            }
+        }
+        (ReflectMut::Set(base), ReflectRef::Set(overlay)) => {
+            for value in overlay.iter() {
+                if base.get(value).is_none() {
+                    base.insert_boxed(value.to_dynamic());
+                }
+            }
+        }
+        _ => base.apply(overlay),
+    }
+}
+
+/// Applies `patch` to `base` as a JSON Merge Patch.
+fn merge_patch(base: &mut JsonValue, patch: JsonValue) {
+    match (base, patch) {
+        (JsonValue::Object(base_map), JsonValue::Object(patch_map)) => {
+            for (key, value) in patch_map {
+                merge_field(MergeStrategy::Patch, base_map, key, value, None);
+            }
+        }
+        (base, mut patch) => {
+            remove_nulls(&mut patch);
+            *base = patch;
+        }
+    }
+}
+
+/// Removes `null` members from every object in `value`, as a merge patch does when adding
+/// a new object.
+fn remove_nulls(value: &mut JsonValue) {
+    if let JsonValue::Object(map) = value {
+        map.retain(|_, member| !member.is_null());
+        map.values_mut().for_each(remove_nulls);
+    }
+}
+
+/// Merges `entries` into `base` by [`ConfigFile::ARRAY_MERGE_KEY`](crate::ConfigFile::ARRAY_MERGE_KEY).
+///
+/// An object entry whose `key` field equals that of an object in `base` has its fields
+/// merged into it; every other entry is appended.
+pub(crate) fn merge_array_by_key(base: &mut Vec<JsonValue>, entries: Vec<JsonValue>, key: &str) {
+    for entry in entries {
+        let existing = entry.get(key).and_then(|id| {
+            base.iter_mut()
+                .find(|candidate| candidate.get(key) == Some(id))
+        });
+        match (existing, entry) {
+            (Some(JsonValue::Object(existing)), JsonValue::Object(fields)) => {
+                existing.extend(fields);
+            }
+            (_, entry) => base.push(entry),
+        }
+    }
+}
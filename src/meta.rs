@@ -0,0 +1,90 @@
+//! Metadata about when and from where a config resource was loaded.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::{ConfigFile, locale, vfs};
+
+/// Resource describing the most recent load of the config resource `T`.
+///
+/// Config plugins insert it alongside `T` and update it on every reload, so hot-reload
+/// consumers and caches can tell when the underlying file was last written, and debug
+/// overlays can show where a config came from.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, ConfigMeta};
+///
+/// #[derive(Resource)]
+/// struct InputSettings;
+///
+/// impl ConfigFile for InputSettings {
+///     const PATH: &'static str = "assets/config/input.yaml";
+/// }
+///
+/// fn report(meta: Res<ConfigMeta<InputSettings>>) {
+///     info!("{} last written at {:?}", meta.path, meta.file_mtime);
+///     if meta.overridden {
+///         info!("overrides were applied on top of {}", meta.path);
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug)]
+pub struct ConfigMeta<T> {
+    /// The file path that was read, after locale substitution and [`ConfigFile::resolve_path`].
+    pub path: String,
+    /// When the config was loaded.
+    pub loaded_at: SystemTime,
+    /// The last modification time of the file at [`path`](Self::path) on disk, or `None`
+    /// if it could not be read (for example when the config came from
+    /// [`ConfigFile::ENV_DOC_VAR`]).
+    ///
+    /// Only `path` is checked, not common, layer, field, `extends:` or `includes:` files.
+    /// It is also `None` while a [`ConfigFs`](crate::ConfigFs) is installed, and doesn't
+    /// describe configs that override [`ConfigFile::read_file`] to read from elsewhere.
+    pub file_mtime: Option<SystemTime>,
+    /// A hash of the loaded config's content, after overrides, or `None` if the config
+    /// could not be serialized.
+    ///
+    /// Reloads that produce the same content are skipped, so rewriting a file with identical
+    /// content (or touching it) doesn't trigger [`OnConfigChanged`](crate::OnConfigChanged).
+    /// The hash is only meaningful within one run of the app.
+    pub content_hash: Option<u64>,
+    /// Whether an override changed the config on top of the file: an in-memory or
+    /// environment override, a per-field variable or a command-line `--config` argument.
+    ///
+    /// Overrides of protected fields that were ignored don't count.
+    pub overridden: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ConfigFile + Serialize> ConfigMeta<T> {
+    /// Describes a load of `config` happening now, which applied an override if
+    /// `overridden`.
+    pub(crate) fn now(config: &T, overridden: bool) -> Self {
+        let path = locale::config_path::<T>();
+        let file_mtime = vfs::disk_mtime(Path::new(&path));
+        Self {
+            path,
+            loaded_at: SystemTime::now(),
+            file_mtime,
+            content_hash: content_hash(config),
+            overridden,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Hashes the serialized content of `config`, or returns `None` if it can't be serialized.
+pub(crate) fn content_hash<T: Serialize>(config: &T) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_vec(config).ok()?.hash(&mut hasher);
+    Some(hasher.finish())
+}
@@ -0,0 +1,79 @@
+//! Detecting config resources that are mutated outside the loading and reloading API.
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::{ConfigFile, ConfigMeta, JsonValue};
+
+/// Adds a debug-build check that warns when the config resource `T` is changed by anything
+/// other than this crate's loading and reloading.
+///
+/// After every load the resource is snapshotted; at the end of each frame, if `T` was
+/// mutated and no longer matches the snapshot, a warning is logged. Add it alongside one of
+/// the config plugins. In release builds, or without the `logging` feature, it does nothing.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, config_file_plugin, config_mutation_check_plugin};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct GameSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for GameSettings {
+///     const PATH: &'static str = "assets/config/game.yaml";
+/// }
+///
+/// App::new()
+///     .add_plugins(config_file_plugin::<GameSettings>)
+///     .add_plugins(config_mutation_check_plugin::<GameSettings>)
+///     .run();
+/// ```
+pub fn config_mutation_check_plugin<T>(app: &mut App)
+where
+    T: Resource + Serialize + ConfigFile,
+{
+    #[cfg(all(debug_assertions, feature = "logging"))]
+    app.add_systems(Last, warn_on_unexpected_mutation::<T>);
+    #[cfg(not(all(debug_assertions, feature = "logging")))]
+    let _ = app;
+}
+
+#[cfg_attr(not(all(debug_assertions, feature = "logging")), allow(dead_code))]
+fn warn_on_unexpected_mutation<T>(
+    config: Option<Res<T>>,
+    meta: Option<Res<ConfigMeta<T>>>,
+    mut snapshot: Local<Option<JsonValue>>,
+) where
+    T: Resource + Serialize + ConfigFile,
+{
+    let Some(config) = config else {
+        return;
+    };
+    // A changed ConfigMeta means the crate itself (re)loaded the config this frame
+    let loaded = meta.is_some_and(|meta| meta.is_changed());
+    if !loaded && snapshot.is_some() && !config.is_changed() {
+        return;
+    }
+
+    // Compared unredacted, so mutations of secret fields are caught too
+    let current = serde_json::to_value(config.as_ref()).unwrap_or(JsonValue::Null);
+    if loaded || snapshot.is_none() {
+        *snapshot = Some(current);
+        return;
+    }
+
+    if snapshot.as_ref() != Some(&current) {
+        #[cfg(feature = "logging")]
+        warn!(
+            "config {} was mutated outside the config reload API; treat config resources as read-only",
+            std::any::type_name::<T>()
+        );
+        *snapshot = Some(current);
+    }
+}
@@ -0,0 +1,56 @@
+//! Arithmetic operators in overrides, such as `{"pan_speed": {"$mul": 2}}`.
+
+use crate::{JsonValue, LoadConfigError};
+
+const MUL: &str = "$mul";
+const ADD: &str = "$add";
+
+/// Evaluates `value` against the existing value of `field` if it is an operator object,
+/// returning the value to store. Any other value is returned unchanged.
+///
+/// `{"$mul": n}` multiplies the existing number by `n` and `{"$add": n}` adds `n` to it.
+/// Integers stay integers when both operands are integers.
+pub(crate) fn apply_operator(
+    field: &str,
+    existing: Option<&JsonValue>,
+    value: JsonValue,
+) -> Result<JsonValue, LoadConfigError> {
+    let (operator, operand) = match &value {
+        JsonValue::Object(map) if map.len() == 1 => match map.iter().next() {
+            Some((key, operand)) if key == MUL || key == ADD => (key.as_str(), operand),
+            _ => return Ok(value),
+        },
+        _ => return Ok(value),
+    };
+
+    let invalid = |reason: &str| {
+        LoadConfigError::InvalidOperator(format!("{} on field \"{}\": {}", operator, field, reason))
+    };
+    let operand = operand
+        .as_number()
+        .ok_or_else(|| invalid("the operand is not a number"))?;
+    let existing = existing
+        .and_then(JsonValue::as_number)
+        .ok_or_else(|| invalid("the existing value is not a number"))?;
+
+    // Keep integer arithmetic exact where possible
+    if let (Some(a), Some(b)) = (existing.as_i64(), operand.as_i64()) {
+        let result = if operator == MUL {
+            a.checked_mul(b)
+        } else {
+            a.checked_add(b)
+        };
+        return result
+            .map(JsonValue::from)
+            .ok_or_else(|| invalid("the result overflows"));
+    }
+
+    let (a, b) = (
+        existing.as_f64().unwrap_or_default(),
+        operand.as_f64().unwrap_or_default(),
+    );
+    let result = if operator == MUL { a * b } else { a + b };
+    serde_json::Number::from_f64(result)
+        .map(JsonValue::Number)
+        .ok_or_else(|| invalid("the result is not a finite number"))
+}
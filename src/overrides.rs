@@ -0,0 +1,310 @@
+//! In-memory environment override store, and parsing of override strings.
+//!
+//! On platforms without real environment variables (such as WASM), overrides can be set
+//! programmatically instead. Stored overrides take precedence over the `CONFIG_{TypeName}`
+//! environment variable and trigger a reload of the resource on the next update.
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+
+use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ConfigFile, ConfigGroup, JsonValue, LoadConfigError, cli, env_var_name, load_base_json, redact,
+    references,
+};
+
+#[derive(Default)]
+struct OverrideStore {
+    values: HashMap<TypeId, String>,
+    pending: HashSet<TypeId>,
+    /// Why a reload was requested with [`request_reload`], such as `"from hotkey"`.
+    reload_causes: HashMap<TypeId, &'static str>,
+}
+
+static STORE: LazyLock<Mutex<OverrideStore>> = LazyLock::new(Default::default);
+
+/// The format whole-config override strings are written in.
+///
+/// Select one with [`ConfigFile::ENV_FORMAT`](crate::ConfigFile::ENV_FORMAT).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverrideFormat {
+    /// Overrides are JSON, such as `{"pan_speed": 2000.0}`. This is the default.
+    #[default]
+    Json,
+    /// Overrides are YAML, such as `pan_speed: 2000.0`. JSON overrides keep working, since
+    /// JSON is valid YAML. Parse errors are reported as `LoadConfigError::Yaml`.
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+/// Parses the whole-config override string `value` for `T`, in its
+/// [`ConfigFile::ENV_FORMAT`].
+pub(crate) fn parse_override<T: ConfigFile>(value: &str) -> Result<JsonValue, LoadConfigError> {
+    match T::ENV_FORMAT {
+        OverrideFormat::Json => serde_json::from_str(value).map_err(LoadConfigError::Json),
+        #[cfg(feature = "yaml")]
+        OverrideFormat::Yaml => serde_yml::from_str(value).map_err(LoadConfigError::Yaml),
+    }
+}
+
+fn with_store<R>(f: impl FnOnce(&mut OverrideStore) -> R) -> R {
+    let mut store = STORE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut store)
+}
+
+/// Sets an in-memory JSON override for `T`, used in place of the `CONFIG_{TypeName}`
+/// environment variable.
+///
+/// If `T` was added with [`config_file_plugin`](crate::config_file_plugin), the resource is
+/// reloaded with the new override on the next app update. The JSON is not validated until then.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_config_file::{ConfigFile, set_override};
+///
+/// struct GameSettings;
+///
+/// impl ConfigFile for GameSettings {
+///     const PATH: &'static str = "assets/config/game_settings.yaml";
+/// }
+///
+/// set_override::<GameSettings>(r#"{"difficulty": "hard"}"#);
+/// ```
+pub fn set_override<T: ConfigFile>(json: &str) {
+    with_store(|store| {
+        store.values.insert(TypeId::of::<T>(), json.to_string());
+        store.pending.insert(TypeId::of::<T>());
+    });
+}
+
+/// Removes the in-memory override for `T`, falling back to the environment variable (if any).
+///
+/// Like [`set_override`], this triggers a reload on the next app update.
+pub fn clear_override<T: ConfigFile>() {
+    with_store(|store| {
+        store.values.remove(&TypeId::of::<T>());
+        store.pending.insert(TypeId::of::<T>());
+    });
+}
+
+/// Restores a single field of `resource` to the value from its config file, undoing any
+/// override of that field.
+///
+/// `field` may be a `.`-separated path to a nested field. The file (with its default layer,
+/// `extends:` files and children) is re-read, but no overrides are applied to it. The
+/// override itself is left in place, so a later reload applies it again; use
+/// [`clear_override`] to remove it.
+///
+/// # Errors
+///
+/// Returns an error if the config file fails to load, if the file has no value at `field`
+/// (`LoadConfigError::FieldNotFound`), or if the restored value doesn't fit `T`.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, clear_override_field, load_config_file};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// // Loaded with CONFIG_AudioSettings='{"volume": 0.0}'
+/// let mut settings = load_config_file::<AudioSettings>().unwrap();
+/// clear_override_field(&mut settings, "volume").unwrap();
+/// ```
+pub fn clear_override_field<T>(resource: &mut T, field: &str) -> Result<(), LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile,
+{
+    let mut file_json = load_base_json::<T>(true)?;
+    if T::RESOLVE_REFERENCES {
+        references::resolve_references(&mut file_json)?;
+    }
+    let file_value = file_json
+        .pointer(&cli::json_pointer(field))
+        .cloned()
+        .ok_or_else(|| LoadConfigError::FieldNotFound(field.to_string()))?;
+
+    let mut current = serde_json::to_value(&*resource).map_err(LoadConfigError::Json)?;
+    cli::set_path(&mut current, field, file_value);
+    *resource = serde_json::from_value(current).map_err(LoadConfigError::Json)?;
+    Ok(())
+}
+
+/// Returns a shell assignment, `CONFIG_{TypeName}='{...}'`, that reproduces `current` as an
+/// override of `T`'s config file.
+///
+/// The file (with its default layer) is loaded without overrides and compared to `current`
+/// field by field, and only the top-level fields that differ are included. Fields marked
+/// [`Secret`](crate::attributes::Secret), including those of nested structs, are always left out, so the
+/// string is safe to share.
+///
+/// # Errors
+///
+/// Returns an error if the config file fails to load or `current` fails to serialize.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, config_as_override_string};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+///     muted: bool,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// let current = AudioSettings { volume: 0.2, muted: false };
+/// // e.g. CONFIG_AudioSettings='{"volume":0.2}'
+/// println!("{}", config_as_override_string(&current).unwrap());
+/// ```
+pub fn config_as_override_string<T>(current: &T) -> Result<String, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Reflect,
+{
+    let mut file_json = load_base_json::<T>(true)?;
+    if T::RESOLVE_REFERENCES {
+        references::resolve_references(&mut file_json)?;
+    }
+    let mut current_json = serde_json::to_value(current).map_err(LoadConfigError::Json)?;
+    for path in redact::secret_paths(current.reflect_type_info()) {
+        for json in [&mut current_json, &mut file_json] {
+            if let (Some(map), Some(last)) = (redact::parent_object(json, &path), path.last()) {
+                map.remove(*last);
+            }
+        }
+    }
+
+    let changed = match (current_json, file_json) {
+        (JsonValue::Object(current_map), JsonValue::Object(file_map)) => JsonValue::Object(
+            current_map
+                .into_iter()
+                .filter(|(key, value)| file_map.get(key) != Some(value))
+                .collect(),
+        ),
+        // Non-object configs are overridden as a whole
+        (current_json, _) => current_json,
+    };
+
+    let json = serde_json::to_string(&changed).map_err(LoadConfigError::Json)?;
+    Ok(format!(
+        "{}='{}'",
+        env_var_name::<T>(),
+        json.replace('\'', r"'\''")
+    ))
+}
+
+/// Checks that the whole-config override environment variable of each config in the
+/// group `G` parses, reporting every malformed override at once.
+///
+/// `G` is a tuple of config types, such as `(CameraSettings, AudioSettings)`. Each variable
+/// is named as the loader names it (see [`ConfigFile::ENV_VAR`](crate::ConfigFile::ENV_VAR))
+/// and parsed in the type's [`ConfigFile::ENV_FORMAT`](crate::ConfigFile::ENV_FORMAT).
+/// Unset variables are skipped. This suits batch runners that want to validate all
+/// overrides before starting the app, instead of failing on the first bad one at load time.
+///
+/// # Errors
+///
+/// Returns one `(variable name, error)` pair per variable that fails to parse, in the order
+/// of the types in `G`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, preflight_overrides};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Serialize, Deserialize)]
+/// struct CameraSettings {
+///     pan_speed: f32,
+/// }
+///
+/// impl ConfigFile for CameraSettings {
+///     const PATH: &'static str = "assets/config/camera.yaml";
+/// }
+///
+/// #[derive(Resource, Reflect, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// if let Err(errors) = preflight_overrides::<(CameraSettings, AudioSettings)>() {
+///     for (var, error) in &errors {
+///         eprintln!("{var}: {error}");
+///     }
+/// }
+/// ```
+pub fn preflight_overrides<G: ConfigGroup>() -> Result<(), Vec<(String, LoadConfigError)>> {
+    let mut errors = Vec::new();
+    G::preflight(&mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns `T`'s whole-config override variable with its parse error, if it is set and fails
+/// to parse.
+pub(crate) fn preflight_override<T: ConfigFile>() -> Option<(String, LoadConfigError)> {
+    let var = env_var_name::<T>();
+    let value = std::env::var(&var).ok()?;
+    let error = parse_override::<T>(&value).err()?;
+    Some((var, error))
+}
+
+/// Returns the in-memory override for `T`, if one has been set.
+pub(crate) fn stored_override<T: ConfigFile>() -> Option<String> {
+    stored_override_by_id(TypeId::of::<T>())
+}
+
+/// Returns the in-memory override for the type with the given [`TypeId`], if one has been set.
+pub(crate) fn stored_override_by_id(type_id: TypeId) -> Option<String> {
+    with_store(|store| store.values.get(&type_id).cloned())
+}
+
+/// Reloads `T` on the next app update, as if its in-memory override had changed, logging
+/// the fields it changes along with `cause`, such as `"from hotkey"`.
+#[cfg(any(feature = "dev", feature = "hot_reload"))]
+pub(crate) fn request_reload<T: ConfigFile>(cause: &'static str) {
+    with_store(|store| {
+        store.pending.insert(TypeId::of::<T>());
+        store.reload_causes.insert(TypeId::of::<T>(), cause);
+    });
+}
+
+/// Takes the cause passed to [`request_reload`] for `T`, if a requested reload is due.
+pub(crate) fn take_reload_cause<T: ConfigFile>() -> Option<&'static str> {
+    with_store(|store| store.reload_causes.remove(&TypeId::of::<T>()))
+}
+
+/// Run condition that is true once after the in-memory override for `T` changes, or after
+/// [`request_reload`].
+pub(crate) fn override_changed<T: ConfigFile>() -> bool {
+    with_store(|store| store.pending.remove(&TypeId::of::<T>()))
+}
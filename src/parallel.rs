@@ -0,0 +1,54 @@
+//! Loading many config files of the same type in parallel.
+
+use rayon::prelude::*;
+use serde::Deserialize;
+
+use crate::{ConfigFile, JsonValue, LoadConfigError, load_base_file, references};
+
+/// Loads a config of type `T` from each of `paths` in parallel, returning one result per
+/// path in the same order as `paths`.
+///
+/// This suits games with many small per-item files sharing one type. Each file is parsed
+/// by its extension and honours [`ConfigFile::EXTENDS`], [`ConfigFile::INCLUDES`],
+/// [`ConfigFile::CHILDREN`] and [`ConfigFile::RESOLVE_REFERENCES`]. Overrides are not applied, since a single
+/// `CONFIG_{TypeName}` value would apply to every file alike, and `T::PATH` is not read.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy_config_file::{ConfigFile, load_configs_parallel};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct ItemConfig {
+///     name: String,
+///     price: u32,
+/// }
+///
+/// impl ConfigFile for ItemConfig {
+///     const PATH: &'static str = "assets/items/default.yaml";
+/// }
+///
+/// let items = load_configs_parallel::<ItemConfig>(&[
+///     "assets/items/sword.yaml",
+///     "assets/items/shield.yaml",
+/// ]);
+/// ```
+pub fn load_configs_parallel<T>(paths: &[&str]) -> Vec<Result<T, LoadConfigError>>
+where
+    T: for<'de> Deserialize<'de> + ConfigFile + Send,
+{
+    paths.par_iter().map(|path| load_one::<T>(path)).collect()
+}
+
+fn load_one<T>(path: &str) -> Result<T, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + ConfigFile,
+{
+    if !T::RESOLVE_REFERENCES {
+        return load_base_file::<T, T>(path);
+    }
+    let mut json: JsonValue = load_base_file::<T, JsonValue>(path)?;
+    references::resolve_references(&mut json)?;
+    serde_json::from_value(json).map_err(LoadConfigError::Json)
+}
@@ -0,0 +1,191 @@
+//! Adding many config types with one plugin group.
+
+use bevy::app::{PluginGroup, PluginGroupBuilder};
+use bevy::prelude::*;
+use bevy::reflect::{GetTypeRegistration, Typed};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ConfigFile, ConfigLoadErrors, config_file_plugin, config_file_plugin_capture_errors,
+    short_type_name,
+};
+
+/// A plugin group adding [`config_file_plugin`] for every config type given to
+/// [`add`](Self::add), in order.
+///
+/// By default the first config that fails to load fails the app. With
+/// [`collect_errors`](Self::collect_errors), every config is loaded first and all the
+/// failures are reported together.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, ConfigFilePlugins};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct CameraSettings {
+///     pan_speed: f32,
+/// }
+///
+/// impl ConfigFile for CameraSettings {
+///     const PATH: &'static str = "assets/config/camera.yaml";
+/// }
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// App::new()
+///     .add_plugins(
+///         ConfigFilePlugins::default()
+///             .add::<CameraSettings>()
+///             .add::<AudioSettings>(),
+///     )
+///     .run();
+/// ```
+#[derive(Default)]
+pub struct ConfigFilePlugins {
+    plugins: Vec<GroupEntry>,
+    collect_errors: Option<bool>,
+}
+
+/// Adds one config type to a group, failing on its error or capturing it.
+struct GroupEntry {
+    type_name: &'static str,
+    fail: fn(PluginGroupBuilder) -> PluginGroupBuilder,
+    capture: fn(PluginGroupBuilder) -> PluginGroupBuilder,
+}
+
+impl ConfigFilePlugins {
+    /// Adds `T` to the group, loaded like [`config_file_plugin`] would.
+    pub fn add<T>(mut self) -> Self
+    where
+        T: Resource
+            + for<'de> Deserialize<'de>
+            + Serialize
+            + ConfigFile
+            + Reflect
+            + GetTypeRegistration
+            + Typed,
+    {
+        self.plugins.push(GroupEntry {
+            type_name: short_type_name::<T>(),
+            fail: |group| group.add(config_file_plugin::<T>),
+            capture: |group| group.add(config_file_plugin_capture_errors::<T>),
+        });
+        self
+    }
+
+    /// Loads every config in the group before reporting failures, instead of failing on
+    /// the first one.
+    ///
+    /// Each config is loaded like
+    /// [`config_file_plugin_capture_errors`](crate::config_file_plugin_capture_errors), so
+    /// every error is recorded in [`ConfigLoadErrors`]. After startup a summary of the
+    /// group's failures is logged and, if `fail_app` is `true`, returned to Bevy's error handler as
+    /// one error. With `fail_app` set to `false` the app keeps running without the failed
+    /// configs' resources.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_config_file::{ConfigFile, ConfigFilePlugins};
+    /// # use serde::{Deserialize, Serialize};
+    /// # #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    /// # #[reflect(Resource)]
+    /// # struct CameraSettings { pan_speed: f32 }
+    /// # impl ConfigFile for CameraSettings { const PATH: &'static str = "camera.yaml"; }
+    /// # #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    /// # #[reflect(Resource)]
+    /// # struct AudioSettings { volume: f32 }
+    /// # impl ConfigFile for AudioSettings { const PATH: &'static str = "audio.yaml"; }
+    /// App::new()
+    ///     .add_plugins(
+    ///         ConfigFilePlugins::default()
+    ///             .add::<CameraSettings>()
+    ///             .add::<AudioSettings>()
+    ///             .collect_errors(true),
+    ///     )
+    ///     .run();
+    /// ```
+    pub fn collect_errors(mut self, fail_app: bool) -> Self {
+        self.collect_errors = Some(fail_app);
+        self
+    }
+}
+
+impl PluginGroup for ConfigFilePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let group = PluginGroupBuilder::start::<Self>();
+        let Some(fail_app) = self.collect_errors else {
+            return self
+                .plugins
+                .into_iter()
+                .fold(group, |group, entry| (entry.fail)(group));
+        };
+        let type_names = self.plugins.iter().map(|entry| entry.type_name).collect();
+        self.plugins
+            .into_iter()
+            .fold(group, |group, entry| (entry.capture)(group))
+            .add(ErrorSummaryPlugin {
+                fail_app,
+                type_names,
+            })
+    }
+}
+
+/// Reports the errors captured by a [`ConfigFilePlugins::collect_errors`] group after
+/// startup.
+///
+/// Each group adds its own summary, covering only the config types it added.
+struct ErrorSummaryPlugin {
+    fail_app: bool,
+    type_names: Vec<&'static str>,
+}
+
+impl Plugin for ErrorSummaryPlugin {
+    fn build(&self, app: &mut App) {
+        let fail_app = self.fail_app;
+        let type_names = self.type_names.clone();
+        app.init_resource::<ConfigLoadErrors>();
+        app.add_systems(
+            PostStartup,
+            move |errors: Res<ConfigLoadErrors>| -> Result {
+                let failures: Vec<String> = errors
+                    .errors
+                    .iter()
+                    .filter(|(type_name, _)| type_names.contains(&type_name.as_str()))
+                    .map(|(type_name, message)| format!("{}: {}", type_name, message))
+                    .collect();
+                if failures.is_empty() {
+                    return Ok(());
+                }
+                let summary = format!(
+                    "{} config(s) failed to load: {}",
+                    failures.len(),
+                    failures.join("; ")
+                );
+                #[cfg(feature = "logging")]
+                error!("{}", summary);
+                if fail_app {
+                    return Err(summary.into());
+                }
+                Ok(())
+            },
+        );
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
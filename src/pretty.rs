@@ -0,0 +1,80 @@
+//! Rendering a config as an aligned text block for debug overlays.
+
+use std::fmt::Write;
+
+use bevy::reflect::{PartialReflect, Reflect, ReflectRef};
+
+use crate::attributes::{self, Secret};
+use crate::redact::REDACTED;
+
+/// Renders `config` as aligned `key: value` lines, with nested structs indented beneath their
+/// field name.
+///
+/// This is meant for on-screen debug overlays and logs, not for round-tripping: strings are
+/// shown without quotes and other values use their `Debug` form. Values of
+/// [`Secret`](attributes::Secret) fields are shown as `***`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_config_file::config_pretty_string;
+///
+/// #[derive(Reflect)]
+/// struct AudioSettings {
+///     volume: f32,
+///     device: String,
+/// }
+///
+/// let settings = AudioSettings { volume: 0.5, device: "default".to_string() };
+/// assert_eq!(config_pretty_string(&settings), "volume: 0.5\ndevice: default\n");
+/// ```
+pub fn config_pretty_string<T: Reflect>(config: &T) -> String {
+    let mut text = String::new();
+    match config.reflect_ref() {
+        ReflectRef::Struct(_) => write_struct(config.as_partial_reflect(), 0, &mut text),
+        _ => {
+            let _ = writeln!(text, "{}", leaf_text(config.as_partial_reflect()));
+        }
+    }
+    text
+}
+
+fn write_struct(value: &dyn PartialReflect, indent: usize, text: &mut String) {
+    let ReflectRef::Struct(fields) = value.reflect_ref() else {
+        return;
+    };
+    let secrets = value
+        .get_represented_type_info()
+        .map(attributes::fields_with_attribute::<Secret>)
+        .unwrap_or_default();
+    let width = (0..fields.field_len())
+        .filter_map(|index| fields.name_at(index))
+        .map(|name| name.len() + 1)
+        .max()
+        .unwrap_or_default();
+
+    for index in 0..fields.field_len() {
+        let (Some(name), Some(field)) = (fields.name_at(index), fields.field_at(index)) else {
+            continue;
+        };
+        let shown = if secrets.contains(&name) {
+            REDACTED.to_string()
+        } else if matches!(field.reflect_ref(), ReflectRef::Struct(_)) {
+            let _ = writeln!(text, "{:indent$}{}:", "", name);
+            write_struct(field, indent + 2, text);
+            continue;
+        } else {
+            leaf_text(field)
+        };
+        let label = format!("{}:", name);
+        let _ = writeln!(text, "{:indent$}{:width$} {}", "", label, shown);
+    }
+}
+
+fn leaf_text(value: &dyn PartialReflect) -> String {
+    match value.try_downcast_ref::<String>() {
+        Some(string) => string.clone(),
+        None => format!("{:?}", value),
+    }
+}
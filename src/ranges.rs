@@ -0,0 +1,160 @@
+//! Enforcing `# range:`, `# min:` and `# max:` comments in YAML config files.
+//!
+//! Designers can annotate numeric values inline:
+//!
+//! ```yaml
+//! pan_speed: 2000 # range: 0..5000
+//! camera:
+//!   zoom: 1.5 # min: 0.5, max: 4
+//! ```
+//!
+//! Bounds are inclusive, and `0..=5000` is accepted as well as `0..5000`.
+
+use std::path::Path;
+
+use crate::{ConfigFile, JsonValue, LoadConfigError, cli, is_yaml_path, locale, read_config_file};
+
+/// Inclusive bounds commented on the field at `path`.
+#[derive(Debug, PartialEq)]
+struct CommentRange {
+    path: String,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+/// Checks the values in `config` against the range comments in `T`'s YAML file.
+///
+/// With a [`ConfigFile::ROOT_KEY`], `config` is that section of the file, and only the
+/// comments inside it apply.
+pub(crate) fn check_comment_ranges<T: ConfigFile>(
+    config: &JsonValue,
+) -> Result<(), LoadConfigError> {
+    let path = locale::config_path::<T>();
+    if !is_yaml_path(&path) {
+        return Ok(());
+    }
+    // The file was just loaded, so it only goes missing if it came from ENV_DOC_VAR
+    let Ok(content) = read_config_file::<T>(Path::new(&path)) else {
+        return Ok(());
+    };
+
+    for range in comment_ranges(&content) {
+        let field = match T::ROOT_KEY {
+            Some(key) => range
+                .path
+                .strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix('.')),
+            None => Some(range.path.as_str()),
+        };
+        let Some(value) = field.and_then(|field| config.pointer(&cli::json_pointer(field))) else {
+            continue;
+        };
+        let Some(number) = value.as_f64() else {
+            return Err(LoadConfigError::Validation(format!(
+                "{} has a range comment in {} but is not a number",
+                range.path, path
+            )));
+        };
+        let below = range.min.is_some_and(|min| number < min);
+        let above = range.max.is_some_and(|max| number > max);
+        if below || above {
+            return Err(LoadConfigError::Validation(format!(
+                "{} = {} is outside the range {}..={} commented in {}",
+                range.path,
+                value,
+                range.min.map(|min| min.to_string()).unwrap_or_default(),
+                range.max.map(|max| max.to_string()).unwrap_or_default(),
+                path
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Scans YAML `content` for `key: value` lines with range comments.
+///
+/// Nesting is tracked by indentation, so keys of nested mappings get `.`-separated paths.
+/// Sequence items and flow mappings are not descended into.
+fn comment_ranges(content: &str) -> Vec<CommentRange> {
+    let mut ranges = Vec::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        let (code, comment) = split_comment(trimmed);
+        let Some((key, value)) = code.split_once(':') else {
+            continue;
+        };
+
+        while parents.last().is_some_and(|(parent, _)| *parent >= indent) {
+            parents.pop();
+        }
+        let key = key.trim().trim_matches(['"', '\'']);
+        let path = parents
+            .iter()
+            .map(|(_, parent)| parent.as_str())
+            .chain([key])
+            .collect::<Vec<_>>()
+            .join(".");
+
+        if let Some(comment) = comment {
+            let (min, max) = parse_bounds(comment);
+            if min.is_some() || max.is_some() {
+                ranges.push(CommentRange {
+                    path: path.clone(),
+                    min,
+                    max,
+                });
+            }
+        }
+        if value.trim().is_empty() {
+            parents.push((indent, key.to_string()));
+        }
+    }
+    ranges
+}
+
+/// Splits a line at the first `#` outside quotes that starts a comment.
+pub(crate) fn split_comment(line: &str) -> (&str, Option<&str>) {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            (None, '#') if previous.is_whitespace() => {
+                return (&line[..index], Some(&line[index + 1..]));
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+    (line, None)
+}
+
+/// Parses `range: a..b`, `min: a` and `max: b` entries, separated by commas, from a comment.
+fn parse_bounds(comment: &str) -> (Option<f64>, Option<f64>) {
+    let (mut min, mut max) = (None, None);
+    for entry in comment.split(',') {
+        let Some((name, bound)) = entry.split_once(':') else {
+            continue;
+        };
+        let bound = bound.trim();
+        match name.trim() {
+            "range" => {
+                if let Some((low, high)) = bound.split_once("..") {
+                    min = low.trim().parse().ok();
+                    max = high.trim_start_matches('=').trim().parse().ok();
+                }
+            }
+            "min" => min = bound.parse().ok(),
+            "max" => max = bound.parse().ok(),
+            _ => {}
+        }
+    }
+    (min, max)
+}
@@ -0,0 +1,97 @@
+//! Redacting [`attributes::Secret`] fields wherever config values are reported.
+//!
+//! Every place that logs config values or hands them to user callbacks goes through these
+//! helpers, so new log sites get redaction by using them rather than formatting values
+//! directly.
+
+use std::borrow::Cow;
+
+use bevy::reflect::{TypeInfo, Typed};
+use serde::Serialize;
+use serde_json::Map;
+
+use crate::{ConfigFile, JsonValue, attributes};
+
+/// The text that replaces the value of a secret field.
+pub(crate) const REDACTED: &str = "***";
+
+/// Returns the paths of the secret fields in `info`, descending into nested structs, such
+/// as `["database", "password"]`.
+pub(crate) fn secret_paths(info: &TypeInfo) -> Vec<Vec<&'static str>> {
+    attributes::attribute_paths::<attributes::Secret>(info)
+}
+
+/// Returns the paths of `T`'s secret fields, if its reflection info is in scope.
+fn type_secret_paths<T: 'static>() -> Vec<Vec<&'static str>> {
+    attributes::type_info::<T>()
+        .map(secret_paths)
+        .unwrap_or_default()
+}
+
+/// Returns the object holding the last segment of `path` in `json`, if there is one.
+pub(crate) fn parent_object<'a>(
+    json: &'a mut JsonValue,
+    path: &[&str],
+) -> Option<&'a mut Map<String, JsonValue>> {
+    let (_, parents) = path.split_last()?;
+    parents
+        .iter()
+        .try_fold(json, |json, segment| json.get_mut(*segment))?
+        .as_object_mut()
+}
+
+/// Replaces the value at `path` in `json`, if present, with [`REDACTED`].
+fn redact_path(json: &mut JsonValue, path: &[&str]) {
+    let Some(last) = path.last() else {
+        return;
+    };
+    if let Some(value) = parent_object(json, path).and_then(|map| map.get_mut(*last)) {
+        *value = JsonValue::String(REDACTED.to_string());
+    }
+}
+
+/// Returns `value` with the secret fields of `T` at or below `field` replaced by
+/// [`REDACTED`].
+///
+/// `field` is a `.`-separated path; it is redacted as a whole if it lies within a secret
+/// field, and only the secrets nested inside it otherwise.
+pub(crate) fn redact_field<'a, T: 'static>(
+    field: &str,
+    value: &'a JsonValue,
+) -> Cow<'a, JsonValue> {
+    let segments: Vec<&str> = field.split('.').collect();
+    let mut redacted = Cow::Borrowed(value);
+    for path in type_secret_paths::<T>() {
+        if segments.starts_with(&path) {
+            return Cow::Owned(JsonValue::String(REDACTED.to_string()));
+        }
+        if let Some(nested) = path.strip_prefix(segments.as_slice()) {
+            redact_path(redacted.to_mut(), nested);
+        }
+    }
+    redacted
+}
+
+/// Serializes `config` to JSON with every secret field replaced by [`REDACTED`].
+#[cfg(feature = "logging")]
+pub(crate) fn redacted_json<T: Serialize + Typed>(config: &T) -> JsonValue {
+    let mut json = serde_json::to_value(config).unwrap_or(JsonValue::Null);
+    for path in secret_paths(T::type_info()) {
+        redact_path(&mut json, &path);
+    }
+    json
+}
+
+/// Logs that `config` was loaded (or reloaded) from `T::PATH`, with its resolved values at
+/// debug level.
+pub(crate) fn log_loaded<T: Serialize + ConfigFile + Typed>(config: &T, reloaded: bool) {
+    #[cfg(feature = "logging")]
+    {
+        let action = if reloaded { "reloaded" } else { "loaded" };
+        let path = crate::locale::config_path::<T>();
+        bevy::log::info!("{} config from {}", action, path);
+        bevy::log::debug!("resolved config {}: {}", path, redacted_json(config));
+    }
+    #[cfg(not(feature = "logging"))]
+    let _ = (config, reloaded);
+}
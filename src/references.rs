@@ -0,0 +1,101 @@
+//! Resolving `${self.path}` references between fields of the same config.
+
+use crate::{JsonValue, LoadConfigError};
+
+const PREFIX: &str = "${self.";
+
+/// Replaces every `${self.path}` reference in `root` with the value it points to.
+///
+/// A string that consists of a single reference takes the referenced value as-is (so a
+/// number stays a number); references embedded in longer strings are interpolated as text.
+/// Referenced values may themselves contain references; cycles are reported as errors.
+pub(crate) fn resolve_references(root: &mut JsonValue) -> Result<(), LoadConfigError> {
+    let source = root.clone();
+    resolve_in_place(root, &source, &mut Vec::new())
+}
+
+fn resolve_in_place(
+    value: &mut JsonValue,
+    source: &JsonValue,
+    stack: &mut Vec<String>,
+) -> Result<(), LoadConfigError> {
+    match value {
+        JsonValue::String(text) if text.contains(PREFIX) => {
+            *value = resolve_string(text, source, stack)?;
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                resolve_in_place(item, source, stack)?;
+            }
+        }
+        JsonValue::Object(map) => {
+            for item in map.values_mut() {
+                resolve_in_place(item, source, stack)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_string(
+    text: &str,
+    source: &JsonValue,
+    stack: &mut Vec<String>,
+) -> Result<JsonValue, LoadConfigError> {
+    // A whole-string reference keeps the referenced value's type
+    if let Some(path) = text
+        .strip_prefix(PREFIX)
+        .and_then(|rest| rest.strip_suffix('}'))
+        .filter(|path| !path.contains('}'))
+    {
+        return resolve_path(path, source, stack);
+    }
+
+    let mut resolved = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(PREFIX) {
+        resolved.push_str(&rest[..start]);
+        let after = &rest[start + PREFIX.len()..];
+        let end = after.find('}').ok_or_else(|| {
+            LoadConfigError::Reference(format!("unterminated reference in \"{}\"", text))
+        })?;
+        match resolve_path(&after[..end], source, stack)? {
+            JsonValue::String(s) => resolved.push_str(&s),
+            other => resolved.push_str(&other.to_string()),
+        }
+        rest = &after[end + 1..];
+    }
+    resolved.push_str(rest);
+    Ok(JsonValue::String(resolved))
+}
+
+fn resolve_path(
+    path: &str,
+    source: &JsonValue,
+    stack: &mut Vec<String>,
+) -> Result<JsonValue, LoadConfigError> {
+    if stack.iter().any(|visited| visited == path) {
+        return Err(LoadConfigError::Reference(format!(
+            "cycle in references: {} -> {}",
+            stack.join(" -> "),
+            path
+        )));
+    }
+
+    let mut target = source;
+    for segment in path.split('.') {
+        target = match target {
+            JsonValue::Object(map) => map.get(segment),
+            JsonValue::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        }
+        .ok_or_else(|| LoadConfigError::Reference(format!("${{self.{}}} does not exist", path)))?;
+    }
+
+    stack.push(path.to_string());
+    let mut resolved = target.clone();
+    let result = resolve_in_place(&mut resolved, source, stack);
+    stack.pop();
+    result.map(|()| resolved)
+}
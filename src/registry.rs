@@ -0,0 +1,212 @@
+//! Tracking which config types have been added to an app.
+
+use std::any::TypeId;
+
+use bevy::prelude::*;
+
+use bevy::reflect::Typed;
+use serde::{Deserialize, Serialize};
+
+use crate::capture::{self, CapturedConfig};
+use crate::{ConfigFile, env_var_name, locale, short_type_name};
+
+/// A config type added to the app by one of the config plugins.
+#[derive(Debug, Clone)]
+pub struct RegisteredConfig {
+    /// The [`TypeId`] of the config type.
+    pub type_id: TypeId,
+    /// The fully qualified type name, e.g. `my_game::config::CameraSettings`.
+    pub type_path: &'static str,
+    /// The last component of the type name, used for `CONFIG_{TypeName}` overrides.
+    pub short_name: &'static str,
+    /// The config file path, [`ConfigFile::PATH`].
+    pub path: &'static str,
+    /// The prefix of the override variable, [`ConfigFile::ENV_PREFIX`].
+    pub env_prefix: &'static str,
+    config_path: fn() -> String,
+    env_var_name: fn() -> String,
+    capture: fn() -> CapturedConfig,
+    /// Other environment variables the config reads, such as its locale variable.
+    #[cfg(feature = "logging")]
+    other_vars: Vec<&'static str>,
+    #[cfg(feature = "logging")]
+    env_default_var_name: fn() -> String,
+}
+
+impl RegisteredConfig {
+    /// Returns the path actually read for this config, after locale substitution and
+    /// [`ConfigFile::resolve_path`].
+    pub fn resolved_path(&self) -> String {
+        (self.config_path)()
+    }
+
+    /// Returns the name of the environment variable this config reads its override from,
+    /// usually `CONFIG_{TypeName}`.
+    pub fn env_var(&self) -> String {
+        (self.env_var_name)()
+    }
+
+    /// Captures the inputs this config is currently loaded from.
+    pub(crate) fn capture(&self) -> CapturedConfig {
+        (self.capture)()
+    }
+}
+
+/// Resource listing every config type added by a config plugin, in registration order.
+#[derive(Resource, Debug, Default)]
+pub struct ConfigRegistry {
+    configs: Vec<RegisteredConfig>,
+}
+
+impl ConfigRegistry {
+    /// Returns the registered config types in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredConfig> {
+        self.configs.iter()
+    }
+
+    /// Returns `true` if `T` has been added by a config plugin.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.get(TypeId::of::<T>()).is_some()
+    }
+
+    /// Returns the registration for the type with the given [`TypeId`].
+    pub fn get(&self, type_id: TypeId) -> Option<&RegisteredConfig> {
+        self.configs.iter().find(|config| config.type_id == type_id)
+    }
+}
+
+/// Records `T` in the app's [`ConfigRegistry`].
+///
+/// Returns `false` if `T` was already registered. Warns if `T`'s resolved path looks invalid,
+/// and if another registered type reads the same override variable, as happens when two
+/// types share a short name.
+pub(crate) fn register<T>(app: &mut App) -> bool
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    let mut registry = app.world_mut().get_resource_or_init::<ConfigRegistry>();
+
+    if registry.contains::<T>() {
+        #[cfg(feature = "logging")]
+        warn!(
+            "config type {} was added more than once; ignoring the duplicate plugin",
+            std::any::type_name::<T>()
+        );
+        return false;
+    }
+
+    let config = RegisteredConfig {
+        type_id: TypeId::of::<T>(),
+        type_path: std::any::type_name::<T>(),
+        short_name: short_type_name::<T>(),
+        path: T::PATH,
+        env_prefix: T::ENV_PREFIX,
+        config_path: locale::config_path::<T>,
+        env_var_name: env_var_name::<T>,
+        capture: capture::capture_config::<T>,
+        #[cfg(feature = "logging")]
+        other_vars: [
+            T::ENV_DOC_VAR,
+            T::DOCUMENT_OVERRIDE_VAR,
+            T::LOCALE_VAR,
+            T::VARIANT_VAR,
+            T::APP_ID_VAR,
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        #[cfg(feature = "logging")]
+        env_default_var_name: crate::env_default_var_name::<T>,
+    };
+
+    #[cfg(feature = "logging")]
+    if let Some(problem) = path_problem(&config.resolved_path()) {
+        warn!(
+            "config type {} has a suspicious config file path {:?}: {}; loading it will likely fail",
+            config.type_path,
+            config.resolved_path(),
+            problem
+        );
+    }
+
+    #[cfg(feature = "logging")]
+    for other in registry
+        .iter()
+        .filter(|other| other.env_var() == config.env_var())
+    {
+        warn!(
+            "config types {} and {} both read overrides from {}; set ConfigFile::ENV_VAR on one of them",
+            other.type_path,
+            config.type_path,
+            config.env_var()
+        );
+    }
+
+    registry.configs.push(config);
+    true
+}
+
+/// Describes why `path` is unlikely to be a usable config file path, if it is.
+///
+/// `PATH` itself is checked for an extension at compile time, but
+/// [`ConfigFile::resolve_path`] can still produce an unusable path.
+#[cfg(feature = "logging")]
+fn path_problem(path: &str) -> Option<&'static str> {
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+    if path.is_empty() {
+        Some("the path is empty")
+    } else if path.trim() != path {
+        Some("the path has leading or trailing whitespace")
+    } else if file_name.is_empty() {
+        Some("the path names a directory, not a file")
+    } else if file_name.starts_with('.') && !file_name[1..].contains('.') {
+        Some("the file name is only an extension")
+    } else {
+        None
+    }
+}
+
+/// Warns about each `CONFIG_*` environment variable that no registered config reads, such
+/// as a misspelled `CONFIG_CamraSettings`, since its override would silently do nothing.
+///
+/// Every [`ConfigFile::ENV_PREFIX`] in use is checked, not just `CONFIG_`, so configs with
+/// a custom prefix don't draw warnings about another tool's `CONFIG_*` variables.
+#[cfg(feature = "logging")]
+pub(crate) fn warn_orphan_overrides(registry: Res<ConfigRegistry>) {
+    let prefixes: Vec<&str> = registry
+        .iter()
+        .map(|config| config.env_prefix)
+        .filter(|prefix| !prefix.is_empty())
+        .collect();
+    let field_prefixes: Vec<String> = registry
+        .iter()
+        .map(|config| format!("{}__", config.env_var()))
+        .collect();
+    let known: Vec<String> = registry
+        .iter()
+        .flat_map(|config| {
+            [config.env_var(), (config.env_default_var_name)()]
+                .into_iter()
+                .chain(config.other_vars.iter().map(|var| var.to_string()))
+        })
+        .collect();
+
+    for (var, _) in std::env::vars_os() {
+        let Some(var) = var.to_str() else {
+            continue;
+        };
+        #[cfg(feature = "encryption")]
+        if var == crate::encryption::KEY_VAR {
+            continue;
+        }
+        let prefixed = prefixes.iter().any(|prefix| var.starts_with(prefix));
+        let is_known = known.iter().any(|known| known == var)
+            || field_prefixes.iter().any(|prefix| var.starts_with(prefix));
+        if prefixed && !is_known {
+            warn!(
+                "environment variable {} doesn't match any registered config type, so it is ignored",
+                var
+            );
+        }
+    }
+}
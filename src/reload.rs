@@ -0,0 +1,494 @@
+//! Reloading config resources that are already present in the world.
+
+use std::marker::PhantomData;
+
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::reflect::{ReflectMut, ReflectRef, Typed};
+use serde::{Deserialize, Serialize};
+
+use crate::validated::ConfigValidator;
+use crate::{
+    ConfigFile, LoadConfigError, apply_overrides, attributes, insert_config, load_base_json,
+    load_config_file, meta, overrides, report, telemetry, validated, with_field_help,
+};
+
+/// Message written when a reload changes fields marked with
+/// [`attributes::RestartRequired`].
+///
+/// The changed fields keep their previous values until the app is restarted;
+/// every other field is applied as usual.
+#[derive(Message)]
+pub struct RestartRequired<T> {
+    /// Names of the restart-required fields that changed on disk or via overrides.
+    pub fields: Vec<&'static str>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Reloads `T` from its config file, replacing the existing resource.
+///
+/// The resource is re-inserted, so it reads as changed to `resource_changed::<T>`.
+/// Changes to restart-required fields are held back and reported via [`RestartRequired`].
+/// A reload whose content hashes the same as the current resource is skipped, leaving the
+/// resource and its [`ConfigMeta`](crate::ConfigMeta) untouched.
+/// A failed reload keeps the existing resource and logs its error at `error!` level, only
+/// when it differs from the previous reload's error, so a broken file is reported once
+/// rather than on every attempt.
+pub(crate) fn reload_resource_from_config_file<T>(
+    mut commands: Commands,
+    current: Option<Res<T>>,
+    validator: Option<Res<ConfigValidator<T>>>,
+    mut last_error: Local<Option<String>>,
+) where
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Reflect + Typed,
+{
+    let cause = overrides::take_reload_cause::<T>();
+    match reload::<T>(
+        &mut commands,
+        current.as_deref(),
+        validator.as_deref(),
+        cause,
+    ) {
+        Ok(()) => *last_error = None,
+        Err(err) => {
+            let message = err.to_string();
+            if last_error.as_ref() == Some(&message) {
+                #[cfg(feature = "logging")]
+                debug!("config {} still fails to reload: {}", T::PATH, message);
+                return;
+            }
+            *last_error = Some(message);
+            #[cfg(feature = "logging")]
+            error!("{}", err.with_context::<T>());
+            #[cfg(not(feature = "logging"))]
+            let _ = err;
+        }
+    }
+}
+
+/// Command that reloads `T` from its config file, overwriting the existing resource.
+///
+/// Unlike the reload requested by hot reloading or the dev hotkey, it runs when the command
+/// is applied and doesn't need `T` to be added with a plugin. It behaves the same way
+/// otherwise: restart-required fields are held back, an unchanged file is skipped, and a
+/// failed load keeps the existing resource and returns the `LoadConfigError`, which is
+/// passed to Bevy's error handler when queued with [`Commands::queue`].
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, ReloadConfig};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// fn reload_audio(mut commands: Commands) {
+///     commands.queue(ReloadConfig::<AudioSettings>::new());
+/// }
+/// ```
+pub struct ReloadConfig<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ReloadConfig<T> {
+    /// Creates the command.
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ReloadConfig<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Command for ReloadConfig<T>
+where
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Reflect + Typed,
+{
+    type Out = Result<(), LoadConfigError>;
+
+    fn apply(self, world: &mut World) -> Self::Out {
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        let result = reload::<T>(
+            &mut commands,
+            world.get_resource::<T>(),
+            world.get_resource::<ConfigValidator<T>>(),
+            None,
+        );
+        queue.apply(world);
+        result
+    }
+}
+
+/// Restores `T` to the values in its config file, discarding runtime edits and overrides.
+///
+/// The file (with its common file and default layers) is read now, without merging any
+/// environment, in-memory or command-line override, and the resource is re-inserted when
+/// the commands are applied, as a reload. The overrides themselves are left in place, so a
+/// later reload applies them again; use [`clear_override`](crate::clear_override) to remove
+/// an in-memory one. Pairs with [`save_config_file`](crate::save_config_file) for a
+/// settings menu's "reset to defaults" button.
+///
+/// # Errors
+///
+/// Returns an error if the config file fails to load, leaving the resource untouched.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, reset_config_file};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// fn on_reset_pressed(mut commands: Commands) -> Result {
+///     reset_config_file::<AudioSettings>(&mut commands)?;
+///     Ok(())
+/// }
+/// ```
+pub fn reset_config_file<T>(commands: &mut Commands) -> Result<(), LoadConfigError>
+where
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    let config = attributes::with_type_info(T::type_info(), || {
+        load_base_json::<T>(true).and_then(|base_json| {
+            with_field_help(apply_overrides::<T>(base_json, None, Vec::new()))
+        })
+    })?;
+    insert_config(commands, config, true, false);
+    Ok(())
+}
+
+/// Loads `T` and queues it to replace `current`, unless its content is unchanged or
+/// `validator` rejects it. The changed fields are logged if the reload has a `cause`.
+fn reload<T>(
+    commands: &mut Commands,
+    current: Option<&T>,
+    validator: Option<&ConfigValidator<T>>,
+    cause: Option<&str>,
+) -> Result<(), LoadConfigError>
+where
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Reflect + Typed,
+{
+    let (config, overridden) = load_for_reload::<T>(commands, validator)?;
+    swap_in(commands, current, config, overridden, cause);
+    Ok(())
+}
+
+/// Loads `T` and checks it with `validator`, also returning whether an override was
+/// applied, and counts a failure in its telemetry.
+fn load_for_reload<T>(
+    commands: &mut Commands,
+    validator: Option<&ConfigValidator<T>>,
+) -> Result<(T, bool), LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    report::tracking_overrides(load_config_file::<T>)
+        .and_then(|(config, overridden)| Ok((validated::check(validator, config)?, overridden)))
+        .inspect_err(|_| {
+            telemetry::record::<T>(commands, |counts| counts.errors += 1);
+        })
+}
+
+/// Queues the freshly loaded `config` to replace `current`, keeping its restart-required
+/// fields and skipping it if its content is unchanged. `overridden` is whether the load
+/// applied an override, and the changed fields are logged with `cause` if there is one.
+fn swap_in<T>(
+    commands: &mut Commands,
+    current: Option<&T>,
+    mut config: T,
+    overridden: bool,
+    cause: Option<&str>,
+) where
+    T: Resource + Serialize + ConfigFile + Reflect + Typed,
+{
+    if let Some(current) = current {
+        let fields = keep_restart_required_fields(current, &mut config);
+        if !fields.is_empty() {
+            #[cfg(feature = "logging")]
+            warn!(
+                "config {} changed restart-required fields {:?}; they will apply after a restart",
+                T::PATH,
+                fields
+            );
+            // Apps that reload without a plugin may not have the message
+            commands.queue(move |world: &mut World| {
+                if world.contains_resource::<Messages<RestartRequired<T>>>() {
+                    world.write_message(RestartRequired::<T> {
+                        fields,
+                        _marker: PhantomData,
+                    });
+                }
+            });
+        }
+    }
+
+    #[cfg(feature = "logging")]
+    if let Some(cause) = cause {
+        log_reload_diff(current, &config, cause);
+    }
+    #[cfg(not(feature = "logging"))]
+    let _ = cause;
+
+    let unchanged = current
+        .and_then(meta::content_hash)
+        .is_some_and(|current_hash| meta::content_hash(&config) == Some(current_hash));
+    if unchanged {
+        #[cfg(feature = "logging")]
+        debug!("config {} is unchanged; skipping reload", T::PATH);
+        return;
+    }
+
+    insert_config(commands, config, current.is_some(), overridden);
+}
+
+/// A tuple of config types reloaded together by [`ReloadConfigGroup`] or checked together
+/// by [`preflight_overrides`](crate::preflight_overrides), such as `(Economy, Shop)`.
+///
+/// Implemented for tuples of up to eight config resources.
+pub trait ConfigGroup: Sized + Send + 'static {
+    /// Loads every config in the group, failing on the first config that can't be loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `LoadConfigError` of the first config that fails to load.
+    fn load_all() -> Result<Self, LoadConfigError>;
+
+    #[doc(hidden)]
+    fn stage(commands: &mut Commands, world: &World) -> Result<(Self, Vec<bool>), LoadConfigError>;
+
+    #[doc(hidden)]
+    fn swap_in(self, commands: &mut Commands, world: &World, overridden: Vec<bool>);
+
+    #[doc(hidden)]
+    fn preflight(errors: &mut Vec<(String, LoadConfigError)>);
+}
+
+macro_rules! impl_config_group {
+    ($($ty:ident $config:ident),+) => {
+        impl<$($ty),+> ConfigGroup for ($($ty,)+)
+        where
+            $($ty: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Reflect + Typed,)+
+        {
+            fn load_all() -> Result<Self, LoadConfigError> {
+                Ok(($(load_config_file::<$ty>()?,)+))
+            }
+
+            fn stage(
+                commands: &mut Commands,
+                world: &World,
+            ) -> Result<(Self, Vec<bool>), LoadConfigError> {
+                let mut overridden = Vec::new();
+                $(let ($config, applied) = load_for_reload::<$ty>(
+                    commands,
+                    world.get_resource::<ConfigValidator<$ty>>(),
+                )?;
+                overridden.push(applied);)+
+                Ok((($($config,)+), overridden))
+            }
+
+            fn swap_in(self, commands: &mut Commands, world: &World, overridden: Vec<bool>) {
+                let ($($config,)+) = self;
+                let mut overridden = overridden.into_iter();
+                $(let current = world.get_resource::<$ty>();
+                let applied = overridden.next().unwrap_or_default();
+                swap_in(commands, current, $config, applied, None);)+
+            }
+
+            fn preflight(errors: &mut Vec<(String, LoadConfigError)>) {
+                errors.extend([$(overrides::preflight_override::<$ty>(),)+].into_iter().flatten());
+            }
+        }
+    };
+}
+
+impl_config_group!(A a);
+impl_config_group!(A a, B b);
+impl_config_group!(A a, B b, C c);
+impl_config_group!(A a, B b, C c, D d);
+impl_config_group!(A a, B b, C c, D d, E e);
+impl_config_group!(A a, B b, C c, D d, E e, F f);
+impl_config_group!(A a, B b, C c, D d, E e, F f, G g);
+impl_config_group!(A a, B b, C c, D d, E e, F f, G g, H h);
+
+/// Command that reloads several configs together, replacing all of them or none.
+///
+/// Every config in the group `G` is loaded into staging first, then checked with the
+/// cross-config rule given to [`with_check`](Self::with_check), if any. Only when every
+/// load and the check succeed are the resources replaced, so related configs such as an
+/// `Economy` and a `Shop` referencing its item IDs never disagree. Otherwise the existing
+/// resources are all kept and the `LoadConfigError` is returned; a failed check is
+/// reported as `LoadConfigError::Validation`. Each replacement behaves like
+/// [`ReloadConfig`].
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, ReloadConfigGroup};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct Economy {
+///     items: Vec<String>,
+/// }
+///
+/// impl ConfigFile for Economy {
+///     const PATH: &'static str = "assets/config/economy.yaml";
+/// }
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct Shop {
+///     stock: Vec<String>,
+/// }
+///
+/// impl ConfigFile for Shop {
+///     const PATH: &'static str = "assets/config/shop.yaml";
+/// }
+///
+/// fn reload_economy(mut commands: Commands) {
+///     commands.queue(ReloadConfigGroup::<(Economy, Shop)>::new().with_check(
+///         |(economy, shop)| match shop.stock.iter().find(|item| !economy.items.contains(item)) {
+///             Some(item) => Err(format!("the shop stocks unknown item {}", item)),
+///             None => Ok(()),
+///         },
+///     ));
+/// }
+/// ```
+pub struct ReloadConfigGroup<G> {
+    check: Option<GroupCheck<G>>,
+}
+
+type GroupCheck<G> = fn(&G) -> Result<(), String>;
+
+impl<G> ReloadConfigGroup<G> {
+    /// Creates the command, without a cross-config check.
+    pub fn new() -> Self {
+        Self { check: None }
+    }
+
+    /// Rejects the reload unless `check` accepts the staged configs, returning a
+    /// description of the problem otherwise.
+    pub fn with_check(mut self, check: fn(&G) -> Result<(), String>) -> Self {
+        self.check = Some(check);
+        self
+    }
+}
+
+impl<G> Default for ReloadConfigGroup<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: ConfigGroup> Command for ReloadConfigGroup<G> {
+    type Out = Result<(), LoadConfigError>;
+
+    fn apply(self, world: &mut World) -> Self::Out {
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        let result = G::stage(&mut commands, world).and_then(|(staged, overridden)| {
+            if let Some(check) = self.check {
+                check(&staged).map_err(LoadConfigError::Validation)?;
+            }
+            staged.swap_in(&mut commands, world, overridden);
+            Ok(())
+        });
+        queue.apply(world);
+        result
+    }
+}
+
+/// Copies restart-required fields from `current` into `new`, returning the names of those that differed.
+fn keep_restart_required_fields<T: Reflect>(current: &T, new: &mut T) -> Vec<&'static str> {
+    let Some(info) = current.get_represented_type_info() else {
+        return Vec::new();
+    };
+    let (ReflectRef::Struct(current), ReflectMut::Struct(new)) =
+        (current.reflect_ref(), new.reflect_mut())
+    else {
+        return Vec::new();
+    };
+
+    attributes::fields_with_attribute::<attributes::RestartRequired>(info)
+        .into_iter()
+        .filter(|name| {
+            let (Some(old_value), Some(new_value)) = (current.field(name), new.field_mut(name))
+            else {
+                return false;
+            };
+            if old_value.reflect_partial_eq(new_value) == Some(true) {
+                return false;
+            }
+            let _ = new_value.try_apply(old_value);
+            true
+        })
+        .collect()
+}
+
+/// Logs the top-level fields of `current` that `reloaded`, the config a reload requested
+/// for `cause` (such as `"from hotkey"`) loaded, will change.
+#[cfg(feature = "logging")]
+fn log_reload_diff<T>(current: Option<&T>, reloaded: &T, cause: &str)
+where
+    T: Serialize + ConfigFile + Typed,
+{
+    use crate::{JsonValue, locale, redact};
+
+    let path = locale::config_path::<T>();
+    let Some(current) = current else {
+        info!("reloading config {} {}", path, cause);
+        return;
+    };
+
+    let old = redact::redacted_json(current);
+    let new = redact::redacted_json(reloaded);
+    let changes: Vec<String> = match (&old, &new) {
+        (JsonValue::Object(old), JsonValue::Object(new)) => new
+            .iter()
+            .filter(|(key, value)| old.get(*key) != Some(value))
+            .map(|(key, value)| {
+                let previous = old.get(key).unwrap_or(&JsonValue::Null);
+                format!("{}: {} -> {}", key, previous, value)
+            })
+            .collect(),
+        _ if old != new => vec![format!("{} -> {}", old, new)],
+        _ => Vec::new(),
+    };
+
+    if changes.is_empty() {
+        info!("reloading config {} {}; nothing changed", path, cause);
+    } else {
+        info!(
+            "reloading config {} {}; changed {}",
+            path,
+            cause,
+            changes.join(", ")
+        );
+    }
+}
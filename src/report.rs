@@ -0,0 +1,195 @@
+//! Collecting what happened during a load, for showing it in an editor or debug panel.
+
+use std::cell::RefCell;
+use std::path::Path;
+
+use bevy::reflect::Typed;
+use serde::{Deserialize, Serialize};
+
+use crate::{ConfigFile, LoadConfigError, load_config_file};
+
+/// A loaded config together with what happened while loading it, returned by
+/// [`load_config_report`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigLoadReport<T> {
+    /// The loaded config.
+    pub value: T,
+    /// Non-fatal problems, such as an ignored override of a protected field or a missing
+    /// locale file. The same messages are logged when the `logging` feature is enabled.
+    pub warnings: Vec<String>,
+    /// The `.`-separated paths of the fields set by an environment, in-memory or
+    /// command-line override, in the order they were applied.
+    pub overridden_fields: Vec<String>,
+    /// The files that were read, in the order they were read, including any common,
+    /// `extends:`, `includes:` and child config files.
+    pub read_files: Vec<String>,
+}
+
+/// Whether overrides were applied in a load, returned by [`load_config_file_with_meta`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadMeta {
+    /// Whether an environment, in-memory or command-line override changed the config.
+    ///
+    /// Overrides of protected fields that were ignored don't count.
+    pub env_override_applied: bool,
+    /// The `.`-separated paths of the fields the overrides set, in the order they were
+    /// applied. Empty for an override replacing a config whose root is an array or scalar.
+    pub overridden_keys: Vec<String>,
+}
+
+#[derive(Default)]
+struct Collected {
+    warnings: Vec<String>,
+    overridden_fields: Vec<String>,
+    read_files: Vec<String>,
+    override_applied: bool,
+}
+
+impl Collected {
+    /// Whether an override changed the config: it replaced a non-object config or set a
+    /// field.
+    fn applied_override(&self) -> bool {
+        self.override_applied || !self.overridden_fields.is_empty()
+    }
+}
+
+thread_local! {
+    static COLLECTED: RefCell<Option<Collected>> = const { RefCell::new(None) };
+}
+
+/// Loads `T` like [`load_config_file`], also reporting the warnings raised, the fields
+/// overridden and the files read along the way.
+///
+/// # Errors
+///
+/// Returns the same errors as [`load_config_file`].
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, load_config_report};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Serialize, Deserialize)]
+/// struct CameraSettings {
+///     pan_speed: f32,
+/// }
+///
+/// impl ConfigFile for CameraSettings {
+///     const PATH: &'static str = "assets/config/camera.yaml";
+/// }
+///
+/// let report = load_config_report::<CameraSettings>().unwrap();
+/// for warning in &report.warnings {
+///     println!("warning: {}", warning);
+/// }
+/// println!("overridden: {:?}", report.overridden_fields);
+/// ```
+pub fn load_config_report<T>() -> Result<ConfigLoadReport<T>, LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    let (value, collected) = collecting(load_config_file::<T>);
+    Ok(ConfigLoadReport {
+        value: value?,
+        warnings: collected.warnings,
+        overridden_fields: collected.overridden_fields,
+        read_files: collected.read_files,
+    })
+}
+
+/// Loads `T` like [`load_config_file`], also returning whether an override was applied
+/// and which fields it set.
+///
+/// # Errors
+///
+/// Returns the same errors as [`load_config_file`].
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, load_config_file_with_meta};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Serialize, Deserialize)]
+/// struct CameraSettings {
+///     pan_speed: f32,
+/// }
+///
+/// impl ConfigFile for CameraSettings {
+///     const PATH: &'static str = "assets/config/camera.yaml";
+/// }
+///
+/// // CONFIG_CameraSettings='{"pan_speed": 2000.0}'
+/// let (_config, meta) = load_config_file_with_meta::<CameraSettings>().unwrap();
+/// assert!(meta.env_override_applied);
+/// assert_eq!(meta.overridden_keys, ["pan_speed"]);
+/// ```
+pub fn load_config_file_with_meta<T>() -> Result<(T, LoadMeta), LoadConfigError>
+where
+    T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+{
+    let (value, collected) = collecting(load_config_file::<T>);
+    let meta = LoadMeta {
+        env_override_applied: collected.applied_override(),
+        overridden_keys: collected.overridden_fields,
+    };
+    Ok((value?, meta))
+}
+
+/// Runs `load`, also returning whether it applied an override, as
+/// [`LoadMeta::env_override_applied`] reports.
+pub(crate) fn tracking_overrides<T>(
+    load: impl FnOnce() -> Result<T, LoadConfigError>,
+) -> Result<(T, bool), LoadConfigError> {
+    let (value, collected) = collecting(load);
+    value.map(|value| (value, collected.applied_override()))
+}
+
+/// Runs `load` with a fresh collector, restoring any outer one afterwards.
+fn collecting<R>(load: impl FnOnce() -> R) -> (R, Collected) {
+    let previous = COLLECTED.with(|collected| collected.replace(Some(Collected::default())));
+    let result = load();
+    let collected = COLLECTED
+        .with(|collected| collected.replace(previous))
+        .unwrap_or_default();
+    (result, collected)
+}
+
+fn collect(f: impl FnOnce(&mut Collected)) {
+    COLLECTED.with(|collected| {
+        if let Some(collected) = collected.borrow_mut().as_mut() {
+            f(collected);
+        }
+    });
+}
+
+/// Records `message` in the report being collected on this thread, if any.
+pub(crate) fn warning(message: String) {
+    collect(|collected| {
+        if !collected.warnings.contains(&message) {
+            collected.warnings.push(message);
+        }
+    });
+}
+
+/// Records that the field at `path` was overridden.
+pub(crate) fn overridden_field(path: &str) {
+    collect(|collected| collected.overridden_fields.push(path.to_string()));
+}
+
+/// Records that an override replaced a config whose root is an array or scalar.
+pub(crate) fn override_applied() {
+    collect(|collected| collected.override_applied = true);
+}
+
+/// Records that the file at `path` was read.
+pub(crate) fn read_file(path: &Path) {
+    collect(|collected| {
+        collected
+            .read_files
+            .push(path.to_string_lossy().into_owned())
+    });
+}
@@ -0,0 +1,206 @@
+//! Writing config resources back to their files.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::{
+    ConfigFile, JsonValue, LoadConfigError, config_extension, locale, parse_config,
+    read_raw_config_file, substitution,
+};
+#[cfg(feature = "yaml")]
+use crate::{is_yaml_path, yaml_patch};
+
+/// Writes `config` to `T`'s config file, in the format given by the file's extension.
+///
+/// The file is written to a temporary file next to it, which is then renamed over the
+/// config file, so a crash mid-write leaves the old file intact. Parent directories are
+/// created as needed. With a [`ConfigFile::ROOT_KEY`], only that section of the file is
+/// replaced and the other sections are kept. Comments and formatting are not preserved,
+/// unless [`ConfigFile::SAVE_CHANGES_ONLY`] is set. With a
+/// [`ConfigFile::SCHEMA_VERSION`], the current version is written under
+/// [`ConfigFile::SCHEMA_VERSION_KEY`]. With [`ConfigFile::EXPAND_ENV_VARS`], the other
+/// sections keep their `${VAR}` tokens rather than the values they expand to.
+/// With [`ConfigFile::VERIFY_CHECKSUM`], the `.sha256` sidecar is rewritten to match.
+/// The file is always written at `T::PATH`, even when it was loaded from one of the
+/// [`ConfigFile::FALLBACK_PATHS`].
+///
+/// # Errors
+///
+/// Returns an error if `config` fails to serialize, the extension is not a supported
+/// format (`LoadConfigError::UnsupportedFormat`), or the file can't be written
+/// (`LoadConfigError::Io`).
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, load_config_file, save_config_file};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Reflect, Serialize, Deserialize)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// let mut settings = load_config_file::<AudioSettings>().unwrap();
+/// settings.volume = 0.5;
+/// save_config_file(&settings).unwrap();
+/// ```
+pub fn save_config_file<T: Serialize + ConfigFile>(config: &T) -> Result<(), LoadConfigError> {
+    let path = locale::write_path::<T>();
+    let existing = if T::ROOT_KEY.is_some() || T::SAVE_CHANGES_ONLY {
+        existing_file::<T>(&path)?
+    } else {
+        None
+    };
+    let content = match T::ROOT_KEY {
+        Some(key) => {
+            // The other sections are kept as written, so their `${VAR}` tokens aren't
+            // replaced by the values they expand to
+            let mut document = match &existing {
+                Some((raw, _)) => parse_config(&path, raw)?,
+                None => JsonValue::Object(Default::default()),
+            };
+            let section = section_json::<T>(&path, config)?;
+            match &mut document {
+                JsonValue::Object(map) => {
+                    map.insert(key.to_string(), section);
+                }
+                other => *other = serde_json::json!({ key: section }),
+            }
+            serialize_config(&path, &document)?
+        }
+        None if T::SCHEMA_VERSION.is_some() => {
+            serialize_config(&path, &section_json::<T>(&path, config)?)?
+        }
+        None => serialize_config(&path, config)?,
+    };
+    #[cfg(feature = "yaml")]
+    let content = match existing {
+        Some((raw, expanded)) if T::SAVE_CHANGES_ONLY && is_yaml_path(&path) => {
+            let old = saved_json::<T>(&path, &raw, &expanded)?;
+            let new = parse_config(&path, &content)?;
+            yaml_patch::patch_yaml(&raw, &old, &new).unwrap_or(content)
+        }
+        _ => content,
+    };
+
+    #[cfg(feature = "encryption")]
+    let bytes = if T::ENCRYPTED {
+        crate::encryption::encrypt(&content)?
+    } else {
+        content.into_bytes()
+    };
+    #[cfg(not(feature = "encryption"))]
+    let bytes = content.into_bytes();
+
+    write_atomically(Path::new(&path), &bytes)?;
+    #[cfg(feature = "checksum")]
+    if T::VERIFY_CHECKSUM {
+        crate::checksum::write_sidecar(Path::new(&path), &bytes)?;
+    }
+    Ok(())
+}
+
+/// Returns `config` as the JSON of its section of the file at `path`, stamped with the
+/// current [`ConfigFile::SCHEMA_VERSION`] so it isn't migrated again when loaded.
+fn section_json<T: Serialize + ConfigFile>(
+    path: &str,
+    config: &T,
+) -> Result<JsonValue, LoadConfigError> {
+    // Round-trip through the file format so floats compare equal to the file's
+    let mut section = parse_config(path, &serialize_config(path, config)?)?;
+    if let (Some(version), JsonValue::Object(map)) = (T::SCHEMA_VERSION, &mut section) {
+        map.insert(T::SCHEMA_VERSION_KEY.to_string(), version.into());
+    }
+    Ok(section)
+}
+
+/// Reads `T`'s config file at `path`, if it exists, returning its text before and after
+/// `${VAR}` expansion.
+fn existing_file<T: ConfigFile>(path: &str) -> Result<Option<(String, String)>, LoadConfigError> {
+    let raw = match read_raw_config_file::<T>(Path::new(path)) {
+        Ok(raw) => raw,
+        Err(LoadConfigError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(None);
+        }
+        Err(error) => return Err(error),
+    };
+    let expanded = if T::EXPAND_ENV_VARS {
+        substitution::expand_env_vars::<T>(path, &raw)?
+    } else {
+        raw.clone()
+    };
+    Ok(Some((raw, expanded)))
+}
+
+/// Returns the file's content to compare the saved document with: `T`'s own section after
+/// `${VAR}` expansion, so its unchanged values match the config, and any other sections as
+/// written, as they are saved.
+#[cfg(feature = "yaml")]
+fn saved_json<T: ConfigFile>(
+    path: &str,
+    raw: &str,
+    expanded: &str,
+) -> Result<JsonValue, LoadConfigError> {
+    let Some(key) = T::ROOT_KEY.filter(|_| T::EXPAND_ENV_VARS) else {
+        return parse_config(path, expanded);
+    };
+    let mut document = parse_config(path, raw)?;
+    let section = parse_config::<JsonValue>(path, expanded)?
+        .get_mut(key)
+        .map(JsonValue::take);
+    if let (JsonValue::Object(map), Some(section)) = (&mut document, section) {
+        map.insert(key.to_string(), section);
+    }
+    Ok(document)
+}
+
+/// Serializes `value` in the format given by the extension of `path`.
+fn serialize_config(path: &str, value: &impl Serialize) -> Result<String, LoadConfigError> {
+    match config_extension(path).as_str() {
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => serde_yml::to_string(value).map_err(LoadConfigError::Yaml),
+        #[cfg(feature = "json")]
+        "json" => serde_json::to_string_pretty(value).map_err(LoadConfigError::Json),
+        #[cfg(feature = "ron")]
+        "ron" => ron::ser::to_string_pretty(value, Default::default())
+            .map_err(|error| LoadConfigError::Serialize(error.to_string())),
+        #[cfg(feature = "toml")]
+        "toml" => toml::to_string_pretty(value)
+            .map_err(|error| LoadConfigError::Serialize(error.to_string())),
+        other => Err(LoadConfigError::UnsupportedFormat(other.to_string())),
+    }
+}
+
+/// Writes `bytes` to a temporary file beside `path`, then renames it to `path`.
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), LoadConfigError> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(LoadConfigError::Io)?;
+    }
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let written = fs::File::create(&temp_path).and_then(|mut file| {
+        file.write_all(bytes)?;
+        file.sync_all()
+    });
+    match written.and_then(|()| fs::rename(&temp_path, path)) {
+        Ok(()) => Ok(()),
+        Err(error) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(LoadConfigError::Io(error))
+        }
+    }
+}
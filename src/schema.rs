@@ -0,0 +1,223 @@
+//! Generating JSON Schemas for config types, for editor autocompletion.
+
+use std::path::Path;
+
+use bevy::reflect::enums::VariantInfo;
+use bevy::reflect::{NamedField, TypeInfo, Typed};
+use serde_json::{Map, json};
+
+use crate::shape::{FLOAT_TYPES, INTEGER_TYPES, STRING_TYPES};
+use crate::{JsonValue, LoadConfigError, save, short_type_name};
+
+/// Returns a JSON Schema describing `T`, built from its reflected type structure.
+///
+/// Structs become objects listing their fields as `properties`, with every non-`Option`
+/// field `required`. Lists and sets become arrays, maps become objects with
+/// `additionalProperties`, and enums list their variants. Types the schema can't describe,
+/// such as opaque types without a JSON counterpart, accept any value. Serde attributes
+/// like `rename` are not reflected, so the schema uses the Rust field names.
+///
+/// With the `field_docs` feature, field doc comments become `description`s.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_config_file::config_json_schema;
+///
+/// #[derive(Reflect)]
+/// struct AudioSettings {
+///     volume: f32,
+///     muted: bool,
+/// }
+///
+/// let schema = config_json_schema::<AudioSettings>();
+/// assert_eq!(schema["properties"]["volume"]["type"], "number");
+/// assert_eq!(schema["properties"]["muted"]["type"], "boolean");
+/// ```
+pub fn config_json_schema<T: Typed>() -> JsonValue {
+    let mut schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": short_type_name::<T>(),
+    });
+    if let (JsonValue::Object(schema), JsonValue::Object(body)) = (
+        &mut schema,
+        type_schema(Some(T::type_info()), T::type_path()),
+    ) {
+        schema.extend(body);
+    }
+    schema
+}
+
+/// Writes [`config_json_schema`] for `T` to `path` as pretty-printed JSON, creating parent
+/// directories as needed.
+///
+/// Point an editor's YAML or JSON language server at the file to get completion and
+/// validation while editing the config.
+///
+/// # Errors
+///
+/// Returns `LoadConfigError::Io` if the file can't be written.
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # #[derive(Reflect)]
+/// # struct AudioSettings { volume: f32 }
+/// use bevy_config_file::write_config_json_schema;
+///
+/// write_config_json_schema::<AudioSettings>("assets/config/audio.schema.json").unwrap();
+/// ```
+pub fn write_config_json_schema<T: Typed>(path: impl AsRef<Path>) -> Result<(), LoadConfigError> {
+    let schema = config_json_schema::<T>();
+    let bytes = serde_json::to_vec_pretty(&schema)
+        .map_err(|error| LoadConfigError::Serialize(error.to_string()))?;
+    save::write_atomically(path.as_ref(), &bytes)
+}
+
+/// Returns the schema of a value whose type is described by `info`, or only named by
+/// `type_path` when it has no reflected type info.
+fn type_schema(info: Option<&TypeInfo>, type_path: &str) -> JsonValue {
+    let Some(info) = info else {
+        return opaque_schema(type_path);
+    };
+    match info {
+        TypeInfo::Struct(info) => struct_schema(info.iter()),
+        TypeInfo::TupleStruct(info) if info.field_len() == 1 => {
+            let field = info.field_at(0).expect("the tuple struct has one field");
+            type_schema(field.type_info(), field.type_path())
+        }
+        TypeInfo::TupleStruct(info) => tuple_schema(
+            info.iter()
+                .map(|field| type_schema(field.type_info(), field.type_path())),
+        ),
+        TypeInfo::Tuple(info) => tuple_schema(
+            info.iter()
+                .map(|field| type_schema(field.type_info(), field.type_path())),
+        ),
+        TypeInfo::List(info) => json!({
+            "type": "array",
+            "items": type_schema(info.item_info(), info.item_ty().path()),
+        }),
+        TypeInfo::Array(info) => json!({
+            "type": "array",
+            "items": type_schema(info.item_info(), info.item_ty().path()),
+            "minItems": info.capacity(),
+            "maxItems": info.capacity(),
+        }),
+        TypeInfo::Set(info) => json!({
+            "type": "array",
+            "items": opaque_schema(info.value_ty().path()),
+            "uniqueItems": true,
+        }),
+        TypeInfo::Map(info) => json!({
+            "type": "object",
+            "additionalProperties": type_schema(info.value_info(), info.value_ty().path()),
+        }),
+        TypeInfo::Enum(info) if is_option(info.type_path()) => {
+            let some = info
+                .variant("Some")
+                .and_then(|variant| variant.as_tuple_variant().ok())
+                .and_then(|variant| variant.field_at(0));
+            match some {
+                Some(field) => json!({
+                    "anyOf": [type_schema(field.type_info(), field.type_path()), {"type": "null"}],
+                }),
+                None => JsonValue::Object(Map::new()),
+            }
+        }
+        TypeInfo::Enum(info) => {
+            if info
+                .iter()
+                .all(|variant| matches!(variant, VariantInfo::Unit(_)))
+            {
+                return json!({ "enum": info.variant_names() });
+            }
+            let variants: Vec<JsonValue> = info.iter().map(variant_schema).collect();
+            json!({ "oneOf": variants })
+        }
+        TypeInfo::Opaque(info) => opaque_schema(info.type_path()),
+    }
+}
+
+/// Returns the schema of an object with the given fields.
+fn struct_schema<'a>(fields: impl Iterator<Item = &'a NamedField>) -> JsonValue {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for field in fields {
+        let schema = type_schema(field.type_info(), field.type_path());
+        #[cfg(feature = "field_docs")]
+        let schema = with_description(schema, field.docs());
+        if !is_option(field.type_path()) {
+            required.push(field.name());
+        }
+        properties.insert(field.name().to_string(), schema);
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Adds the doc comment `docs` to `schema` as its `description`.
+#[cfg(feature = "field_docs")]
+fn with_description(mut schema: JsonValue, docs: Option<&str>) -> JsonValue {
+    if let (Some(docs), JsonValue::Object(map)) = (docs, &mut schema) {
+        map.insert("description".to_string(), docs.trim().into());
+    }
+    schema
+}
+
+/// Returns the schema of a fixed-length array whose items match `items` in order.
+fn tuple_schema(items: impl Iterator<Item = JsonValue>) -> JsonValue {
+    let items: Vec<JsonValue> = items.collect();
+    json!({
+        "type": "array",
+        "prefixItems": items,
+        "minItems": items.len(),
+        "maxItems": items.len(),
+    })
+}
+
+/// Returns the schema of one enum variant, in serde's externally tagged representation.
+fn variant_schema(variant: &VariantInfo) -> JsonValue {
+    let content = match variant {
+        VariantInfo::Unit(_) => return json!({ "const": variant.name() }),
+        VariantInfo::Struct(info) => struct_schema(info.iter()),
+        VariantInfo::Tuple(info) if info.field_len() == 1 => {
+            let field = info.field_at(0).expect("the variant has one field");
+            type_schema(field.type_info(), field.type_path())
+        }
+        VariantInfo::Tuple(info) => tuple_schema(
+            info.iter()
+                .map(|field| type_schema(field.type_info(), field.type_path())),
+        ),
+    };
+    json!({
+        "type": "object",
+        "properties": { variant.name(): content },
+        "required": [variant.name()],
+        "additionalProperties": false,
+    })
+}
+
+/// Returns the schema of a value with no reflected structure, judged by its type name.
+fn opaque_schema(type_path: &str) -> JsonValue {
+    if INTEGER_TYPES.contains(&type_path) {
+        json!({ "type": "integer" })
+    } else if FLOAT_TYPES.contains(&type_path) {
+        json!({ "type": "number" })
+    } else if type_path == "bool" {
+        json!({ "type": "boolean" })
+    } else if STRING_TYPES.contains(&type_path) {
+        json!({ "type": "string" })
+    } else {
+        JsonValue::Object(Map::new())
+    }
+}
+
+fn is_option(type_path: &str) -> bool {
+    type_path.starts_with("core::option::Option<")
+}
@@ -0,0 +1,84 @@
+//! Loading several config resources from sections of one shared file.
+
+use crate::{ConfigFile, JsonValue, LoadConfigError, capture};
+
+#[doc(hidden)]
+pub use bevy::app::App as __App;
+
+/// Declares config types that are each loaded from one top-level section of a shared file,
+/// along with a plugin function that adds all of them.
+///
+/// Each `key: Type` entry implements [`ConfigFile`] for `Type` with the shared `PATH`,
+/// [`ROOT_KEY`](ConfigFile::ROOT_KEY) set to `key` and
+/// [`DOCUMENT_OVERRIDE_VAR`](ConfigFile::DOCUMENT_OVERRIDE_VAR) set to the `env` variable,
+/// so an override in that variable targets the combined document, e.g.
+/// `CONFIG_Settings='{"audio": {"volume": 0.5}}'`. The generated plugin function adds
+/// [`config_file_plugin`](crate::config_file_plugin) for every section, in order.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::config_sections;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct VideoSettings {
+///     vsync: bool,
+/// }
+///
+/// config_sections! {
+///     fn settings_plugin("assets/config/settings.yaml", env = "CONFIG_Settings") {
+///         audio: AudioSettings,
+///         video: VideoSettings,
+///     }
+/// }
+///
+/// App::new().add_plugins(settings_plugin).run();
+/// ```
+#[macro_export]
+macro_rules! config_sections {
+    (
+        $(#[$meta:meta])*
+        $vis:vis fn $plugin:ident($path:literal, env = $env:literal) {
+            $($key:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(
+            impl $crate::ConfigFile for $ty {
+                const PATH: &'static str = $path;
+                const ROOT_KEY: Option<&'static str> = Some(stringify!($key));
+                const DOCUMENT_OVERRIDE_VAR: Option<&'static str> = Some($env);
+            }
+        )*
+
+        $(#[$meta])*
+        $vis fn $plugin(app: &mut $crate::__App) {
+            $($crate::config_file_plugin::<$ty>(app);)*
+        }
+    };
+}
+
+/// Returns the `(source, JSON)` override for `T`'s section of the document override in
+/// [`ConfigFile::DOCUMENT_OVERRIDE_VAR`], if that variable is set and has the section.
+pub(crate) fn document_override<T: ConfigFile>() -> Result<Option<(String, String)>, LoadConfigError>
+{
+    let (Some(var), Some(key)) = (T::DOCUMENT_OVERRIDE_VAR, T::ROOT_KEY) else {
+        return Ok(None);
+    };
+    let Some(document) = capture::env_var(var) else {
+        return Ok(None);
+    };
+    let document: JsonValue = serde_json::from_str(&document).map_err(LoadConfigError::Json)?;
+    match document.get(key) {
+        Some(section) => Ok(Some((format!("{}.{}", var, key), section.to_string()))),
+        None => Ok(None),
+    }
+}
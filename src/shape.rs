@@ -0,0 +1,132 @@
+//! Checking that an override's fields fit the config type before it is merged.
+
+use bevy::reflect::TypeInfo;
+use serde_json::Map;
+
+use crate::{JsonValue, attributes, json_kind};
+
+/// Type paths of the opaque types serialized as JSON integers, shared with the schema
+/// generator.
+pub(crate) const INTEGER_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+/// Type paths of the opaque types serialized as JSON numbers.
+pub(crate) const FLOAT_TYPES: &[&str] = &["f32", "f64"];
+/// Type paths of the opaque types serialized as JSON strings.
+pub(crate) const STRING_TYPES: &[&str] = &[
+    "alloc::string::String",
+    "char",
+    "&str",
+    "std::path::PathBuf",
+];
+
+/// Lists the fields of `override_map` that don't fit `T`, such as unknown fields or a
+/// string given for a number.
+///
+/// `T`'s reflection info is read from the load in progress. Fields present in `base` (the
+/// loaded file) always count as known, so serde
+/// renames don't cause false reports. `null` values and `$mul`/`$add` operators are never
+/// reported.
+pub(crate) fn override_problems<T: 'static>(
+    base: &Map<String, JsonValue>,
+    override_map: &Map<String, JsonValue>,
+) -> Vec<String> {
+    let Some(info) = attributes::type_info::<T>() else {
+        return Vec::new();
+    };
+    let mut problems = Vec::new();
+    check_struct(info, override_map, Some(base), "", &mut problems);
+    problems
+}
+
+fn check_struct(
+    info: &TypeInfo,
+    fields: &Map<String, JsonValue>,
+    base: Option<&Map<String, JsonValue>>,
+    prefix: &str,
+    problems: &mut Vec<String>,
+) {
+    let Ok(info) = info.as_struct() else {
+        return;
+    };
+    for (key, value) in fields {
+        let path = format!("{}{}", prefix, key);
+        let base_value = base.and_then(|base| base.get(key));
+        match info.field(key) {
+            Some(field) => {
+                if let Some(field_info) = field.type_info() {
+                    check_value(field_info, value, base_value, &path, problems);
+                }
+            }
+            None if base_value.is_some() => {}
+            None => problems.push(format!("{} is not a field of the config", path)),
+        }
+    }
+}
+
+fn check_value(
+    info: &TypeInfo,
+    value: &JsonValue,
+    base: Option<&JsonValue>,
+    path: &str,
+    problems: &mut Vec<String>,
+) {
+    if value.is_null() || is_operator(value) {
+        return;
+    }
+    let type_path = info.type_path();
+    let expected = match info {
+        TypeInfo::Struct(_) => match value {
+            JsonValue::Object(fields) => {
+                let base = base.and_then(JsonValue::as_object);
+                return check_struct(info, fields, base, &format!("{}.", path), problems);
+            }
+            _ => "an object",
+        },
+        TypeInfo::List(_) | TypeInfo::Array(_) | TypeInfo::Set(_) if !value.is_array() => {
+            "an array"
+        }
+        TypeInfo::Map(_) if !value.is_object() => "an object",
+        TypeInfo::Enum(info) if type_path.starts_with("core::option::Option<") => {
+            // Check the `Some` payload, since `null` was accepted above
+            if let Some(inner) = info
+                .variant("Some")
+                .and_then(|variant| variant.as_tuple_variant().ok())
+                .and_then(|variant| variant.field_at(0))
+                .and_then(|field| field.type_info())
+            {
+                check_value(inner, value, base, path, problems);
+            }
+            return;
+        }
+        TypeInfo::Opaque(_) if INTEGER_TYPES.contains(&type_path) => {
+            if value.is_i64() || value.is_u64() {
+                return;
+            }
+            "an integer"
+        }
+        TypeInfo::Opaque(_) if FLOAT_TYPES.contains(&type_path) && !value.is_number() => "a number",
+        TypeInfo::Opaque(_) if type_path == "bool" && !value.is_boolean() => "a boolean",
+        TypeInfo::Opaque(_) if STRING_TYPES.contains(&type_path) && !value.is_string() => {
+            "a string"
+        }
+        // Enums, tuples and other opaque types have serde representations that can't be
+        // predicted from reflection alone
+        _ => return,
+    };
+    problems.push(format!(
+        "{} should be {} ({}), found {}",
+        path,
+        expected,
+        type_path,
+        json_kind(value)
+    ));
+}
+
+/// Whether `value` is a `{"$mul": n}` or `{"$add": n}` style operator.
+fn is_operator(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Object(map) => map.len() == 1 && map.keys().all(|key| key.starts_with('$')),
+        _ => false,
+    }
+}
@@ -0,0 +1,59 @@
+//! Collecting config types from across the crate graph with the `inventory` crate.
+
+use bevy::prelude::*;
+
+#[doc(hidden)]
+pub use inventory as __inventory;
+
+/// A config plugin submitted with [`submit_config_file!`](crate::submit_config_file).
+pub struct ConfigRegistration {
+    /// The plugin function that registers and loads the config type.
+    pub plugin: fn(&mut App),
+}
+
+inventory::collect!(ConfigRegistration);
+
+/// Submits a config type to be added by [`all_config_files_plugin`].
+///
+/// Invoke this once per type, anywhere in the crate graph (typically next to the
+/// `ConfigFile` impl). Requires the `inventory` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, all_config_files_plugin, submit_config_file};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct AudioSettings {
+///     volume: f32,
+/// }
+///
+/// impl ConfigFile for AudioSettings {
+///     const PATH: &'static str = "assets/config/audio.yaml";
+/// }
+///
+/// submit_config_file!(AudioSettings);
+///
+/// App::new().add_plugins(all_config_files_plugin).run();
+/// ```
+#[macro_export]
+macro_rules! submit_config_file {
+    ($ty:ty) => {
+        $crate::__inventory::submit! {
+            $crate::ConfigRegistration {
+                plugin: $crate::config_file_plugin::<$ty>,
+            }
+        }
+    };
+}
+
+/// Adds [`config_file_plugin`](crate::config_file_plugin) for every type submitted with
+/// [`submit_config_file!`](crate::submit_config_file).
+pub fn all_config_files_plugin(app: &mut App) {
+    for registration in inventory::iter::<ConfigRegistration> {
+        (registration.plugin)(app);
+    }
+}
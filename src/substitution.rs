@@ -0,0 +1,50 @@
+//! Expanding `${VAR}` environment variable tokens in raw config text.
+
+use crate::{ConfigFile, LoadConfigError, capture};
+
+/// Replaces every `${VAR}` token in `content` with the value of the environment variable
+/// `VAR`.
+///
+/// Only names made of ASCII letters, digits and underscores are expanded, so
+/// `${self.path}` references are left for [`crate::references`]. An unset variable fails
+/// with `LoadConfigError::MissingEnvVar`, or expands to an empty string with
+/// [`ConfigFile::ALLOW_MISSING_ENV_VARS`].
+pub(crate) fn expand_env_vars<T: ConfigFile>(
+    path: &str,
+    content: &str,
+) -> Result<String, LoadConfigError> {
+    let mut expanded = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let name = after
+            .find('}')
+            .map(|end| &after[..end])
+            .filter(|name| is_var_name(name));
+        let Some(name) = name else {
+            expanded.push_str("${");
+            rest = after;
+            continue;
+        };
+        match capture::env_var(name) {
+            Some(value) => expanded.push_str(&value),
+            None if T::ALLOW_MISSING_ENV_VARS => {}
+            None => {
+                return Err(LoadConfigError::MissingEnvVar {
+                    var: name.to_string(),
+                    path: path.to_string(),
+                });
+            }
+        }
+        rest = &after[name.len() + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+fn is_var_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,87 @@
+//! Counters of config loads, reloads and errors for monitoring.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+/// Load, reload and error counts for one config type, as reported by [`ConfigTelemetry`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfigCounts {
+    /// Successful initial loads, where the resource was not present before.
+    pub loads: u64,
+    /// Successful reloads replacing an existing resource.
+    pub reloads: u64,
+    /// Failed loads and reloads, including repeated reload failures that are only reported
+    /// once.
+    pub errors: u64,
+}
+
+impl ConfigCounts {
+    fn add(self, other: &ConfigCounts) -> ConfigCounts {
+        ConfigCounts {
+            loads: self.loads + other.loads,
+            reloads: self.reloads + other.reloads,
+            errors: self.errors + other.errors,
+        }
+    }
+}
+
+/// Resource counting loads, reloads and errors of every config type, for long-running
+/// servers to export to their monitoring.
+///
+/// Config plugins insert it, and every load and reload updates it.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::ConfigTelemetry;
+///
+/// fn export_metrics(telemetry: Res<ConfigTelemetry>) {
+///     for (type_path, counts) in telemetry.iter() {
+///         info!("{type_path}: {} reloads, {} errors", counts.reloads, counts.errors);
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct ConfigTelemetry {
+    counts: BTreeMap<&'static str, ConfigCounts>,
+}
+
+impl ConfigTelemetry {
+    /// Returns the counts for `T`, which are all zero if `T` has never been loaded.
+    pub fn get<T: 'static>(&self) -> ConfigCounts {
+        self.counts
+            .get(std::any::type_name::<T>())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the counts of every config type that has been loaded, keyed by fully qualified
+    /// type name, in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &ConfigCounts)> {
+        self.counts
+            .iter()
+            .map(|(type_path, counts)| (*type_path, counts))
+    }
+
+    /// Returns the counts summed over all config types.
+    pub fn total(&self) -> ConfigCounts {
+        self.counts
+            .values()
+            .fold(ConfigCounts::default(), ConfigCounts::add)
+    }
+}
+
+/// Queues `update` to be applied to `T`'s counts in the world's [`ConfigTelemetry`].
+pub(crate) fn record<T: 'static>(commands: &mut Commands, update: fn(&mut ConfigCounts)) {
+    commands.queue(move |world: &mut World| {
+        let mut telemetry = world.get_resource_or_init::<ConfigTelemetry>();
+        update(
+            telemetry
+                .counts
+                .entry(std::any::type_name::<T>())
+                .or_default(),
+        );
+    });
+}
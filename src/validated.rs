@@ -0,0 +1,145 @@
+//! Checking loaded configs with a user-supplied validation before they are inserted.
+
+use bevy::prelude::*;
+use bevy::reflect::{GetTypeRegistration, Typed};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ConfigFile, LoadConfigError, insert_config, load_config_file, register_config, report,
+    telemetry,
+};
+
+/// A config with a validation rule checked whenever [`config_file_validated_plugin`] loads
+/// or reloads it, such as a positive `zoom_speed` or a non-empty list of key bindings.
+///
+/// A config that fails validation is never inserted; the load fails with
+/// `LoadConfigError::Validation` holding the message. Rules that should hold for every
+/// loader, including [`load_config_file`], belong in [`ConfigFile::validate_invariants`]
+/// instead.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_config_file::{ConfigFile, ValidatedConfig};
+///
+/// struct CameraSettings {
+///     zoom_speed: f32,
+/// }
+///
+/// impl ConfigFile for CameraSettings {
+///     const PATH: &'static str = "assets/config/camera.yaml";
+/// }
+///
+/// impl ValidatedConfig for CameraSettings {
+///     fn validate(&self) -> Result<(), String> {
+///         if self.zoom_speed <= 0.0 {
+///             return Err(format!("zoom_speed must be positive, found {}", self.zoom_speed));
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let settings = CameraSettings { zoom_speed: 0.0 };
+/// assert!(settings.validate().is_err());
+/// ```
+pub trait ValidatedConfig: ConfigFile {
+    /// Checks the config, returning a description of the problem if it's invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns the message reported in `LoadConfigError::Validation`.
+    fn validate(&self) -> Result<(), String>;
+}
+
+/// The [`ValidatedConfig::validate`] of a config added with
+/// [`config_file_validated_plugin`], checked by the app's reloads of `T`.
+#[derive(Resource)]
+pub(crate) struct ConfigValidator<T>(fn(&T) -> Result<(), String>);
+
+/// Returns `config` if `validator` is absent or accepts it.
+pub(crate) fn check<T>(
+    validator: Option<&ConfigValidator<T>>,
+    config: T,
+) -> Result<T, LoadConfigError> {
+    match validator {
+        Some(validator) => (validator.0)(&config)
+            .map(|()| config)
+            .map_err(LoadConfigError::Validation),
+        None => Ok(config),
+    }
+}
+
+/// Adds `T` like [`config_file_plugin`](crate::config_file_plugin), checking it with
+/// [`ValidatedConfig::validate`] every time it is loaded or reloaded.
+///
+/// An invalid config fails the startup system with `LoadConfigError::Validation`, and an
+/// invalid reload keeps the existing resource and logs the error.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_config_file::{ConfigFile, ValidatedConfig, config_file_validated_plugin};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+/// #[reflect(Resource)]
+/// struct InputSettings {
+///     bindings: Vec<String>,
+/// }
+///
+/// impl ConfigFile for InputSettings {
+///     const PATH: &'static str = "assets/config/input.yaml";
+/// }
+///
+/// impl ValidatedConfig for InputSettings {
+///     fn validate(&self) -> Result<(), String> {
+///         if self.bindings.is_empty() {
+///             return Err("at least one key binding is required".to_string());
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(config_file_validated_plugin::<InputSettings>)
+///     .run();
+/// ```
+pub fn config_file_validated_plugin<T>(app: &mut App)
+where
+    T: Resource
+        + for<'de> Deserialize<'de>
+        + Serialize
+        + ValidatedConfig
+        + Reflect
+        + GetTypeRegistration
+        + Typed,
+{
+    if !register_config::<T>(app) {
+        return;
+    }
+    app.insert_resource(ConfigValidator::<T>(T::validate));
+    app.add_systems(Startup, load_validated_resource::<T>);
+}
+
+/// Startup system for [`config_file_validated_plugin`].
+fn load_validated_resource<T>(mut commands: Commands) -> bevy::ecs::error::Result
+where
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ValidatedConfig + Typed,
+{
+    let loaded =
+        report::tracking_overrides(load_config_file::<T>).and_then(|(config, overridden)| {
+            config.validate().map_err(LoadConfigError::Validation)?;
+            Ok((config, overridden))
+        });
+    match loaded {
+        Ok((config, overridden)) => {
+            insert_config(&mut commands, config, false, overridden);
+            Ok(())
+        }
+        Err(err) => {
+            telemetry::record::<T>(&mut commands, |counts| counts.errors += 1);
+            Err(err.with_context::<T>().into())
+        }
+    }
+}
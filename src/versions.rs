@@ -0,0 +1,41 @@
+//! Migrating config files written for older schema versions.
+
+use crate::{ConfigFile, JsonValue, LoadConfigError};
+
+/// Brings `file` up to [`ConfigFile::SCHEMA_VERSION`] with [`ConfigFile::migrate`].
+///
+/// The file's version is read from its [`ConfigFile::SCHEMA_VERSION_KEY`], and a file
+/// without the key is taken to be version 0. Versions outside
+/// [`ConfigFile::MIN_SCHEMA_VERSION`] to [`ConfigFile::MAX_SCHEMA_VERSION`] are rejected.
+/// After a migration the key is set to the current version.
+pub(crate) fn migrate_file<T: ConfigFile>(file: &mut JsonValue) -> Result<(), LoadConfigError> {
+    let Some(current) = T::SCHEMA_VERSION else {
+        return Ok(());
+    };
+    let found = match file.get(T::SCHEMA_VERSION_KEY) {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| {
+                LoadConfigError::Validation(format!(
+                    "{} should be a schema version number, found {}",
+                    T::SCHEMA_VERSION_KEY,
+                    version
+                ))
+            })?,
+    };
+
+    let min = T::MIN_SCHEMA_VERSION;
+    let max = T::MAX_SCHEMA_VERSION.unwrap_or(current);
+    if found < min || found > max {
+        return Err(LoadConfigError::SchemaVersion { found, min, max });
+    }
+    if found < current {
+        T::migrate(file, found);
+        if let JsonValue::Object(map) = file {
+            map.insert(T::SCHEMA_VERSION_KEY.to_string(), current.into());
+        }
+    }
+    Ok(())
+}
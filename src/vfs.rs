@@ -0,0 +1,139 @@
+//! Reading config files through a virtual filesystem.
+//!
+//! Engines with a virtual filesystem, for example to overlay mod files on the base game,
+//! can install it with [`set_config_fs`] so every config file is read through it.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::SystemTime;
+
+use crate::capture;
+
+/// A filesystem that config files are read from, installed with [`set_config_fs`].
+pub trait ConfigFs: Send + Sync {
+    /// Reads the whole file at `path`.
+    ///
+    /// Return an error of kind [`io::ErrorKind::NotFound`] for missing files, so fallbacks
+    /// such as [`config_file_plugin_or_default`](crate::config_file_plugin_or_default) work.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Returns `true` if a file exists at `path`.
+    ///
+    /// The default implementation tries to [`read`](Self::read) the file.
+    fn exists(&self, path: &Path) -> bool {
+        self.read(path).is_ok()
+    }
+
+    /// Lists the paths of the files directly inside the directory at `path`, as read for a
+    /// [`ConfigFile::FIELD_DIR`](crate::ConfigFile::FIELD_DIR).
+    ///
+    /// The default implementation lists the directory with `std::fs`.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        std_read_dir(path)
+    }
+}
+
+static CONFIG_FS: LazyLock<RwLock<Option<Arc<dyn ConfigFs>>>> = LazyLock::new(Default::default);
+
+/// Installs `fs` as the filesystem config files are read from, in place of `std::fs`.
+///
+/// This applies to every config type that doesn't override [`ConfigFile::read_file`],
+/// including `extends:` and `includes:` files, locale lookups and
+/// [`FIELD_DIR`](crate::ConfigFile::FIELD_DIR) listings, until
+/// [`clear_config_fs`] is called. Files are still written with `std::fs`.
+///
+/// [`ConfigFile::read_file`]: crate::ConfigFile::read_file
+///
+/// # Example
+///
+/// ```rust
+/// use std::io;
+/// use std::path::Path;
+///
+/// use bevy_config_file::{ConfigFs, set_config_fs};
+///
+/// struct ModOverlay;
+///
+/// impl ConfigFs for ModOverlay {
+///     fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+///         std::fs::read(Path::new("mods/active").join(path)).or_else(|_| std::fs::read(path))
+///     }
+/// }
+///
+/// set_config_fs(ModOverlay);
+/// ```
+pub fn set_config_fs(fs: impl ConfigFs + 'static) {
+    *CONFIG_FS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(fs));
+}
+
+/// Removes the filesystem installed with [`set_config_fs`], so config files are read with
+/// `std::fs` again.
+pub fn clear_config_fs() {
+    *CONFIG_FS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+fn installed() -> Option<Arc<dyn ConfigFs>> {
+    CONFIG_FS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Reads the file at `path` through the installed [`ConfigFs`], or `std::fs` if none is.
+pub(crate) fn read(path: &Path) -> io::Result<Vec<u8>> {
+    match installed() {
+        Some(fs) => fs.read(path),
+        None => std::fs::read(path),
+    }
+}
+
+/// Checks for a file at `path` through the installed [`ConfigFs`], or `std::fs` if none is.
+pub(crate) fn exists(path: &Path) -> bool {
+    if let Some(exists) = capture::replayed_exists(path) {
+        return exists;
+    }
+    match installed() {
+        Some(fs) => fs.exists(path),
+        None => path.exists(),
+    }
+}
+
+/// Lists the files in the directory at `path` through the installed [`ConfigFs`], or
+/// `std::fs` if none is.
+pub(crate) fn read_dir(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if let Some(files) = capture::replayed_dir(path) {
+        return Ok(files);
+    }
+    match installed() {
+        Some(fs) => fs.read_dir(path),
+        None => std_read_dir(path),
+    }
+}
+
+/// Returns the last modification time of the file at `path` on disk, or `None` if it can't
+/// be read or the file isn't read from disk because a [`ConfigFs`] is installed or a
+/// capture is being replayed.
+pub(crate) fn disk_mtime(path: &Path) -> Option<SystemTime> {
+    if installed().is_some() || capture::replayed_exists(path).is_some() {
+        return None;
+    }
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn std_read_dir(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
@@ -0,0 +1,231 @@
+//! Rewriting only the changed fields of a YAML config file, for
+//! [`ConfigFile::SAVE_CHANGES_ONLY`](crate::ConfigFile::SAVE_CHANGES_ONLY).
+
+use std::ops::Range;
+
+use crate::{JsonValue, duplicates, ranges};
+
+type JsonMap = serde_json::Map<String, JsonValue>;
+
+/// A `key: value` entry of a block mapping, spanning `lines`.
+struct Entry {
+    key: String,
+    lines: Range<usize>,
+}
+
+/// Lines `lines` of the file replaced by `text`.
+struct Edit {
+    lines: Range<usize>,
+    text: String,
+}
+
+/// Returns `content` with the fields that differ between `old`, the parsed `content`, and
+/// `new` rewritten, keeping every other line byte for byte.
+///
+/// Block mappings are descended while both sides are non-empty objects; at the first
+/// difference the field's whole block is replaced, keeping the inline comment of a
+/// single-line value. Fields only in `new` are appended to their mapping and fields only
+/// in `old` are removed. Returns `None` if a field can't be found in the text, such as in
+/// a file written as one flow mapping, so the caller can rewrite the whole file instead.
+pub(crate) fn patch_yaml(content: &str, old: &JsonValue, new: &JsonValue) -> Option<String> {
+    let (JsonValue::Object(old), JsonValue::Object(new)) = (old, new) else {
+        return None;
+    };
+    let content = match content.ends_with('\n') {
+        true => content.to_string(),
+        false => format!("{}\n", content),
+    };
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let indent = lines
+        .iter()
+        .find(|line| !is_blank_or_comment(line) && !is_document_marker(line))
+        .map_or(0, |line| indent_of(line));
+
+    let mut edits = Vec::new();
+    patch_mapping(&lines, 0..lines.len(), indent, old, new, &mut edits)?;
+    edits.sort_by_key(|edit| (edit.lines.start, edit.lines.end));
+
+    let mut patched = String::with_capacity(content.len());
+    let mut next = 0;
+    for edit in edits {
+        patched.extend(lines[next..edit.lines.start].iter().copied());
+        patched.push_str(&edit.text);
+        next = edit.lines.end;
+    }
+    patched.extend(lines[next..].iter().copied());
+    Some(patched)
+}
+
+/// Records the edits turning the block mapping whose entries are indented by `indent`
+/// within `body` from `old` into `new`.
+fn patch_mapping(
+    lines: &[&str],
+    body: Range<usize>,
+    indent: usize,
+    old: &JsonMap,
+    new: &JsonMap,
+    edits: &mut Vec<Edit>,
+) -> Option<()> {
+    let entries = entries(lines, body.clone(), indent)?;
+    let find = |key: &str| entries.iter().find(|entry| entry.key == key);
+
+    for (key, old_value) in old {
+        match new.get(key) {
+            Some(new_value) if new_value == old_value => {}
+            Some(new_value) => patch_entry(lines, find(key)?, indent, old_value, new_value, edits)?,
+            None => edits.push(Edit {
+                lines: find(key)?.lines.clone(),
+                text: String::new(),
+            }),
+        }
+    }
+
+    let mut added = String::new();
+    for (key, value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+        added.push_str(&entry_text(key, value, indent, None)?);
+    }
+    if !added.is_empty() {
+        let end = entries.last().map_or(body.start, |entry| entry.lines.end);
+        edits.push(Edit {
+            lines: end..end,
+            text: added,
+        });
+    }
+    Some(())
+}
+
+/// Records the edits turning `entry` from `old` into `new`, descending into a nested
+/// block mapping if both are objects.
+fn patch_entry(
+    lines: &[&str],
+    entry: &Entry,
+    indent: usize,
+    old: &JsonValue,
+    new: &JsonValue,
+    edits: &mut Vec<Edit>,
+) -> Option<()> {
+    if let (JsonValue::Object(old), JsonValue::Object(new)) = (old, new) {
+        // An emptied mapping is rewritten, since removing every child would leave `null`
+        if let Some(child_indent) = nested_indent(lines, entry).filter(|_| !new.is_empty()) {
+            let body = entry.lines.start + 1..entry.lines.end;
+            return patch_mapping(lines, body, child_indent, old, new, edits);
+        }
+    }
+    let comment = match entry.lines.len() {
+        1 => inline_comment(lines[entry.lines.start]),
+        _ => None,
+    };
+    edits.push(Edit {
+        lines: entry.lines.clone(),
+        text: entry_text(&entry.key, new, indent, comment)?,
+    });
+    Some(())
+}
+
+/// Finds the entries indented by `indent` within `body`, or `None` if a line at that
+/// indentation isn't a `key: value` entry.
+fn entries(lines: &[&str], body: Range<usize>, indent: usize) -> Option<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut index = body.start;
+    while index < body.end {
+        let line = lines[index];
+        if is_blank_or_comment(line) || is_document_marker(line) {
+            index += 1;
+            continue;
+        }
+        if indent_of(line) != indent {
+            return None;
+        }
+        let (code, _) = ranges::split_comment(line.trim());
+        let (key, _) = duplicates::split_key(code)?;
+        let end = entry_end(lines, index, body.end, indent);
+        entries.push(Entry {
+            key: key.to_string(),
+            lines: index..end,
+        });
+        index = end;
+    }
+    Some(entries)
+}
+
+/// Returns the line after the last line of the entry starting at `start`: its more
+/// indented lines and any sequence items at its own indentation. Trailing blank and
+/// comment lines are left for whatever follows.
+fn entry_end(lines: &[&str], start: usize, limit: usize, indent: usize) -> usize {
+    let mut end = start + 1;
+    for (index, line) in lines.iter().enumerate().take(limit).skip(start + 1) {
+        if is_blank_or_comment(line) {
+            continue;
+        }
+        let line_indent = indent_of(line);
+        if line_indent > indent || (line_indent == indent && is_sequence_item(line)) {
+            end = index + 1;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// Returns the indentation of the entries of `entry`'s value if it is a block mapping.
+fn nested_indent(lines: &[&str], entry: &Entry) -> Option<usize> {
+    let (code, _) = ranges::split_comment(lines[entry.lines.start].trim());
+    let (_, value) = duplicates::split_key(code)?;
+    if !value.trim().is_empty() {
+        return None;
+    }
+    let first = lines[entry.lines.start + 1..entry.lines.end]
+        .iter()
+        .find(|line| !is_blank_or_comment(line))?;
+    (!is_sequence_item(first)).then(|| indent_of(first))
+}
+
+/// Returns the comment at the end of `line`, with the whitespace before it.
+fn inline_comment(line: &str) -> Option<String> {
+    let (code, comment) = ranges::split_comment(line.trim_end());
+    let gap = code.len() - code.trim_end().len();
+    comment.map(|comment| format!("{}#{}", " ".repeat(gap.max(1)), comment))
+}
+
+/// Serializes the entry `key: value` indented by `indent`, appending `comment` to a
+/// single-line result.
+fn entry_text(
+    key: &str,
+    value: &JsonValue,
+    indent: usize,
+    comment: Option<String>,
+) -> Option<String> {
+    let entry = JsonValue::Object(JsonMap::from_iter([(key.to_string(), value.clone())]));
+    let yaml = serde_yml::to_string(&entry).ok()?;
+    let single_line = yaml.lines().count() == 1;
+    let mut text = String::with_capacity(yaml.len());
+    for line in yaml.lines() {
+        if !line.is_empty() {
+            text.push_str(&" ".repeat(indent));
+            text.push_str(line);
+        }
+        if let Some(comment) = comment.as_deref().filter(|_| single_line) {
+            text.push_str(comment);
+        }
+        text.push('\n');
+    }
+    Some(text)
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_blank_or_comment(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+fn is_document_marker(line: &str) -> bool {
+    matches!(line.trim_end(), "---" | "...")
+}
+
+fn is_sequence_item(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed == "-" || trimmed.starts_with("- ")
+}
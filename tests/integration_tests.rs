@@ -2,7 +2,8 @@
 
 use bevy::app::App;
 use bevy::ecs::system::RunSystemOnce;
-use bevy::prelude::Resource;
+use bevy::reflect::Typed;
+use bevy::prelude::{Reflect, Resource};
 use bevy_config_file::{load_resource_from_config_file, ConfigFile};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -26,7 +27,7 @@ fn run_config_test<T, F>(
     env_vars: Vec<(&str, &str)>,
     test_fn: F,
 ) where
-    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile,
+    T: Resource + for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
     F: FnOnce(App, Result<(), bevy::prelude::BevyError>),
 {
     // Lock to serialize tests that change current_dir and env vars
@@ -67,114 +68,6881 @@ fn run_config_test<T, F>(
     std::env::set_current_dir(original_dir).unwrap();
 }
 
+/// Helper function to run a test inside an isolated temp directory.
+///
+/// Acquires the TEST_MUTEX, writes each `(path, content)` pair into the temp directory
+/// (creating parent directories), changes into it and restores the original directory
+/// once `test_fn` returns.
+#[cfg_attr(not(feature = "yaml"), allow(dead_code))]
+fn with_test_dir<F>(files: &[(&str, &str)], test_fn: F)
+where
+    F: FnOnce(&std::path::Path),
+{
+    let _lock = TEST_MUTEX.lock().unwrap();
+    let test_dir = TempDir::new().unwrap();
+
+    for (path, content) in files {
+        let path = test_dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(test_dir.path()).unwrap();
+
+    test_fn(test_dir.path());
+
+    std::env::set_current_dir(original_dir).unwrap();
+}
+
+/// Records every log message emitted on the current thread while its guard is held.
+#[cfg(feature = "logging")]
+#[derive(Clone, Default)]
+struct CapturedLogs(std::sync::Arc<Mutex<Vec<String>>>);
+
+#[cfg(feature = "logging")]
+impl CapturedLogs {
+    fn set_default(&self) -> bevy::log::tracing::subscriber::DefaultGuard {
+        use bevy::log::tracing_subscriber::layer::SubscriberExt;
+        use bevy::log::tracing_subscriber::util::SubscriberInitExt;
+
+        bevy::log::tracing_subscriber::Registry::default()
+            .with(self.clone())
+            .set_default()
+    }
+
+    fn lines(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[cfg(feature = "logging")]
+impl<S: bevy::log::tracing::Subscriber> bevy::log::tracing_subscriber::Layer<S> for CapturedLogs {
+    fn on_event(
+        &self,
+        event: &bevy::log::tracing::Event<'_>,
+        _ctx: bevy::log::tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        struct MessageVisitor<'a>(&'a mut String);
+
+        impl bevy::log::tracing::field::Visit for MessageVisitor<'_> {
+            fn record_debug(
+                &mut self,
+                field: &bevy::log::tracing::field::Field,
+                value: &dyn std::fmt::Debug,
+            ) {
+                self.0.push_str(&format!("{}={:?} ", field.name(), value));
+            }
+        }
+
+        let mut message = String::new();
+        event.record(&mut MessageVisitor(&mut message));
+        self.0.lock().unwrap().push(message);
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod yaml_tests {
+    use super::*;
+
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct TestConfig {
+        value: i32,
+        name: String,
+    }
+
+    impl ConfigFile for TestConfig {
+        const PATH: &'static str = "config.yaml";
+    }
+
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct TestYmlConfig {
+        value: i32,
+        name: String,
+    }
+
+    impl ConfigFile for TestYmlConfig {
+        const PATH: &'static str = "config.yml";
+    }
+
+    #[test]
+    fn test_load_valid_yml_config() {
+        run_config_test::<TestYmlConfig, _>(
+            Some("value: 42\nname: test\n"),
+            vec![],
+            |app, load_result| {
+                assert!(load_result.is_ok());
+                let config = app.world().get_resource::<TestYmlConfig>().unwrap();
+                assert_eq!(config.value, 42);
+                assert_eq!(config.name, "test");
+            },
+        );
+    }
+
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct TestUppercaseYmlConfig {
+        value: i32,
+        name: String,
+    }
+
+    impl ConfigFile for TestUppercaseYmlConfig {
+        const PATH: &'static str = "config.YML";
+    }
+
+    #[test]
+    fn test_yml_extensions_load_like_yaml() {
+        let yaml = "value: 42\nname: test\n";
+        with_test_dir(
+            &[
+                ("config.yaml", yaml),
+                ("config.yml", yaml),
+                ("config.YML", yaml),
+            ],
+            |_| {
+                let expected = bevy_config_file::load_config_file::<TestConfig>().unwrap();
+                let yml = bevy_config_file::load_config_file::<TestYmlConfig>().unwrap();
+                let uppercase =
+                    bevy_config_file::load_config_file::<TestUppercaseYmlConfig>().unwrap();
+                assert_eq!((yml.value, yml.name), (expected.value, expected.name.clone()));
+                assert_eq!((uppercase.value, uppercase.name), (expected.value, expected.name));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_valid_config() {
+        run_config_test::<TestConfig, _>(
+            Some("value: 42\nname: test\n"),
+            vec![],
+            |app, load_result| {
+                assert!(load_result.is_ok());
+
+                let config = app.world().get_resource::<TestConfig>();
+                assert!(config.is_some());
+                let config = config.unwrap();
+                assert_eq!(config.value, 42);
+                assert_eq!(config.name, "test");
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_missing_config_file() {
+        run_config_test::<TestConfig, _>(
+            None, // No config file written
+            vec![],
+            |app, load_result| {
+                assert!(load_result.is_err());
+                let config = app.world().get_resource::<TestConfig>();
+                assert!(config.is_none());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_invalid_yaml() {
+        run_config_test::<TestConfig, _>(
+            Some("invalid: yaml: content:\n  - this is bad\n  missing bracket"),
+            vec![],
+            |app, load_result| {
+                assert!(load_result.is_err());
+                let config = app.world().get_resource::<TestConfig>();
+                assert!(config.is_none());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_with_env_override() {
+        run_config_test::<TestConfig, _>(
+            Some("value: 42\nname: test\n"),
+            vec![("CONFIG_TestConfig", r#"{"value": 100}"#)],
+            |app, load_result| {
+                assert!(load_result.is_ok());
+
+                let config = app.world().get_resource::<TestConfig>();
+                assert!(config.is_some());
+                let config = config.unwrap();
+                assert_eq!(config.value, 100); // Overridden value
+                assert_eq!(config.name, "test"); // Original value
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_missing_file_with_env_override_mentions_ignored_override() {
+        run_config_test::<TestConfig, _>(
+            None,
+            vec![("CONFIG_TestConfig", r#"{"value": 100}"#)],
+            |app, load_result| {
+                let message = load_result.unwrap_err().to_string();
+                assert!(message.contains("CONFIG_TestConfig"), "{message}");
+                assert!(message.contains("ignored"), "{message}");
+                assert!(app.world().get_resource::<TestConfig>().is_none());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_with_invalid_env_override() {
+        run_config_test::<TestConfig, _>(
+            Some("value: 42\nname: test\n"),
+            vec![("CONFIG_TestConfig", r#"{"value": invalid json}"#)],
+            |app, load_result| {
+                assert!(load_result.is_err());
+                let config = app.world().get_resource::<TestConfig>();
+                assert!(config.is_none());
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod on_override_tests {
+    use super::*;
+    use bevy_config_file::JsonValue;
+
+    static OVERRIDES: Mutex<Vec<(String, JsonValue, JsonValue)>> = Mutex::new(Vec::new());
+
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct AuditedConfig {
+        value: i32,
+        name: String,
+        enabled: bool,
+    }
+
+    impl ConfigFile for AuditedConfig {
+        const PATH: &'static str = "audited.yaml";
+
+        fn on_override(field: &str, old: &JsonValue, new: &JsonValue) {
+            OVERRIDES
+                .lock()
+                .unwrap()
+                .push((field.to_string(), old.clone(), new.clone()));
+        }
+    }
+
+    #[test]
+    fn test_on_override_called_once_per_overridden_field() {
+        OVERRIDES.lock().unwrap().clear();
+        run_config_test::<AuditedConfig, _>(
+            Some("value: 42\nname: test\nenabled: true\n"),
+            vec![(
+                "CONFIG_AuditedConfig",
+                r#"{"value": 100, "enabled": false}"#,
+            )],
+            |app, load_result| {
+                assert!(load_result.is_ok());
+                assert!(app.world().get_resource::<AuditedConfig>().is_some());
+
+                let mut overrides = OVERRIDES.lock().unwrap().clone();
+                overrides.sort_by(|a, b| a.0.cmp(&b.0));
+                assert_eq!(
+                    overrides,
+                    vec![
+                        (
+                            "enabled".to_string(),
+                            JsonValue::from(true),
+                            JsonValue::from(false)
+                        ),
+                        (
+                            "value".to_string(),
+                            JsonValue::from(42),
+                            JsonValue::from(100)
+                        ),
+                    ]
+                );
+            },
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct AudioSection {
+        volume: f32,
+        muted: bool,
+    }
+
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct NestedAuditedConfig {
+        audio: AudioSection,
+    }
+
+    static NESTED_OVERRIDES: Mutex<Vec<(String, JsonValue, JsonValue)>> = Mutex::new(Vec::new());
+
+    impl ConfigFile for NestedAuditedConfig {
+        const PATH: &'static str = "nested_audited.yaml";
+
+        fn on_override(field: &str, old: &JsonValue, new: &JsonValue) {
+            NESTED_OVERRIDES
+                .lock()
+                .unwrap()
+                .push((field.to_string(), old.clone(), new.clone()));
+        }
+    }
+
+    #[test]
+    fn test_on_override_reports_nested_leaf_paths() {
+        NESTED_OVERRIDES.lock().unwrap().clear();
+        run_config_test::<NestedAuditedConfig, _>(
+            Some("audio:\n  volume: 1.0\n  muted: false\n"),
+            vec![("CONFIG_NestedAuditedConfig", r#"{"audio": {"volume": 0}}"#)],
+            |app, load_result| {
+                assert!(load_result.is_ok());
+                let config = app.world().resource::<NestedAuditedConfig>();
+                assert_eq!(config.audio.volume, 0.0);
+                assert!(!config.audio.muted);
+                assert_eq!(
+                    *NESTED_OVERRIDES.lock().unwrap(),
+                    vec![(
+                        "audio.volume".to_string(),
+                        JsonValue::from(1.0),
+                        JsonValue::from(0)
+                    )]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_on_override_not_called_without_env_var() {
+        OVERRIDES.lock().unwrap().clear();
+        run_config_test::<AuditedConfig, _>(
+            Some("value: 42\nname: test\nenabled: true\n"),
+            vec![],
+            |_app, load_result| {
+                assert!(load_result.is_ok());
+                assert!(OVERRIDES.lock().unwrap().is_empty());
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod in_memory_override_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{clear_override, config_file_plugin, set_override};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct WasmConfig {
+        value: i32,
+        name: String,
+    }
+
+    impl ConfigFile for WasmConfig {
+        const PATH: &'static str = "wasm.yaml";
+    }
+
+    #[test]
+    fn test_set_override_reloads_resource() {
+        with_test_dir(&[("wasm.yaml", "value: 42\nname: test\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<WasmConfig>);
+            app.update();
+            assert_eq!(app.world().resource::<WasmConfig>().value, 42);
+
+            set_override::<WasmConfig>(r#"{"value": 7}"#);
+            app.update();
+            let config = app.world().resource::<WasmConfig>();
+            assert_eq!(config.value, 7);
+            assert_eq!(config.name, "test");
+
+            clear_override::<WasmConfig>();
+            app.update();
+            assert_eq!(app.world().resource::<WasmConfig>().value, 42);
+        });
+    }
+
+    #[test]
+    fn test_set_override_takes_precedence_over_env_var() {
+        run_config_test::<WasmConfig, _>(
+            Some("value: 42\nname: test\n"),
+            vec![("CONFIG_WasmConfig", r#"{"value": 100}"#)],
+            |_app, load_result| {
+                assert!(load_result.is_ok());
+
+                set_override::<WasmConfig>(r#"{"value": 7}"#);
+                let config = bevy_config_file::load_config_file::<WasmConfig>().unwrap();
+                clear_override::<WasmConfig>();
+                assert_eq!(config.value, 7);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod restart_required_tests {
+    use super::*;
+    use bevy::ecs::message::Messages;
+    use bevy::prelude::*;
+    use bevy_config_file::{RestartRequired, config_file_plugin, set_override};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct VideoConfig {
+        #[reflect(@bevy_config_file::attributes::RestartRequired)]
+        window_mode: String,
+        brightness: f32,
+    }
+
+    impl ConfigFile for VideoConfig {
+        const PATH: &'static str = "video.yaml";
+    }
+
+    fn restart_messages(app: &App) -> Vec<Vec<&'static str>> {
+        app.world()
+            .resource::<Messages<RestartRequired<VideoConfig>>>()
+            .iter_current_update_messages()
+            .map(|message| message.fields.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_reload_changing_restart_required_field_writes_message() {
+        with_test_dir(
+            &[("video.yaml", "window_mode: windowed\nbrightness: 1.0\n")],
+            |_| {
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin::<VideoConfig>);
+                app.update();
+
+                set_override::<VideoConfig>(r#"{"window_mode": "fullscreen", "brightness": 0.5}"#);
+                app.update();
+                bevy_config_file::clear_override::<VideoConfig>();
+
+                let config = app.world().resource::<VideoConfig>();
+                assert_eq!(config.window_mode, "windowed");
+                assert_eq!(config.brightness, 0.5);
+                assert_eq!(restart_messages(&app), vec![vec!["window_mode"]]);
+            },
+        );
+    }
+
+    #[test]
+    fn test_reload_changing_only_hot_reloadable_field_writes_no_message() {
+        with_test_dir(
+            &[("video.yaml", "window_mode: windowed\nbrightness: 1.0\n")],
+            |_| {
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin::<VideoConfig>);
+                app.update();
+
+                set_override::<VideoConfig>(r#"{"brightness": 0.5}"#);
+                app.update();
+                bevy_config_file::clear_override::<VideoConfig>();
+
+                assert_eq!(app.world().resource::<VideoConfig>().brightness, 0.5);
+                assert!(restart_messages(&app).is_empty());
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod composition_tests {
+    use super::*;
+    use bevy_config_file::{ChildConfig, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ChildAudio {
+        volume: f32,
+    }
+
+    impl ConfigFile for ChildAudio {
+        const PATH: &'static str = "audio.yaml";
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ChildVideo {
+        width: u32,
+        height: u32,
+    }
+
+    impl ConfigFile for ChildVideo {
+        const PATH: &'static str = "video.yaml";
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ParentGame {
+        title: String,
+        audio: ChildAudio,
+        video: ChildVideo,
+    }
+
+    impl ConfigFile for ParentGame {
+        const PATH: &'static str = "game.yaml";
+        const CHILDREN: &'static [ChildConfig] = &[
+            ChildConfig::new::<ChildAudio>("audio"),
+            ChildConfig::new::<ChildVideo>("video"),
+        ];
+    }
+
+    #[test]
+    fn test_parent_assembled_from_child_files() {
+        with_test_dir(
+            &[
+                ("game.yaml", "title: demo\n"),
+                ("audio.yaml", "volume: 0.8\n"),
+                ("video.yaml", "width: 1920\nheight: 1080\n"),
+            ],
+            |_| {
+                unsafe { std::env::set_var("CONFIG_ChildVideo", r#"{"height": 720}"#) };
+                let config = load_config_file::<ParentGame>();
+                unsafe { std::env::remove_var("CONFIG_ChildVideo") };
+
+                assert_eq!(
+                    config.unwrap(),
+                    ParentGame {
+                        title: "demo".to_string(),
+                        audio: ChildAudio { volume: 0.8 },
+                        video: ChildVideo {
+                            width: 1920,
+                            height: 720,
+                        },
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_missing_child_file_fails_parent_load() {
+        with_test_dir(
+            &[
+                ("game.yaml", "title: demo\n"),
+                ("audio.yaml", "volume: 0.8\n"),
+            ],
+            |_| {
+                assert!(load_config_file::<ParentGame>().is_err());
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod field_dir_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct CameraFields {
+        pan_speed: f32,
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for CameraFields {
+        const PATH: &'static str = "camera.yaml";
+        const FIELD_DIR: Option<&'static str> = Some("camera");
+    }
+
+    #[test]
+    fn test_config_assembled_from_field_files() {
+        with_test_dir(
+            &[
+                ("camera/pan_speed.yaml", "2.5\n"),
+                ("camera/zoom_speed.yaml", "0.5\n"),
+                ("camera/README.md", "one file per field\n"),
+            ],
+            |_| {
+                assert_eq!(
+                    load_config_file::<CameraFields>().unwrap(),
+                    CameraFields {
+                        pan_speed: 2.5,
+                        zoom_speed: 0.5,
+                    }
+                );
+            },
+        );
+    }
+
+    #[cfg(not(feature = "toml"))]
+    #[test]
+    fn test_field_files_without_their_format_feature_are_skipped() {
+        with_test_dir(
+            &[
+                ("camera/pan_speed.yaml", "2.5\n"),
+                ("camera/zoom_speed.yaml", "0.5\n"),
+                ("camera/zoom_speed.toml", "not parsed"),
+            ],
+            |_| {
+                assert_eq!(load_config_file::<CameraFields>().unwrap().zoom_speed, 0.5);
+            },
+        );
+    }
+
+    #[test]
+    fn test_two_files_for_one_field_fail() {
+        with_test_dir(
+            &[
+                ("camera/pan_speed.yaml", "2.5\n"),
+                ("camera/pan_speed.yml", "3.0\n"),
+                ("camera/zoom_speed.yaml", "0.5\n"),
+            ],
+            |_| {
+                assert!(matches!(
+                    load_config_file::<CameraFields>(),
+                    Err(LoadConfigError::DuplicateKey(_))
+                ));
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod if_missing_plugin_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::config_file_plugin_if_missing;
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct PresetConfig {
+        value: i32,
+    }
+
+    impl ConfigFile for PresetConfig {
+        const PATH: &'static str = "preset.yaml";
+    }
+
+    #[test]
+    fn test_pre_inserted_resource_survives_startup() {
+        with_test_dir(&[("preset.yaml", "value: 42\n")], |_| {
+            let mut app = App::new();
+            app.insert_resource(PresetConfig { value: 7 });
+            app.add_plugins(config_file_plugin_if_missing::<PresetConfig>);
+            app.update();
+            assert_eq!(app.world().resource::<PresetConfig>().value, 7);
+        });
+    }
+
+    #[test]
+    fn test_missing_resource_is_loaded_at_startup() {
+        with_test_dir(&[("preset.yaml", "value: 42\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin_if_missing::<PresetConfig>);
+            app.update();
+            assert_eq!(app.world().resource::<PresetConfig>().value, 42);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod or_default_plugin_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::config_file_plugin_or_default;
+
+    #[derive(Resource, Reflect, Debug, Default, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct PrototypeConfig {
+        value: i32,
+        name: String,
+    }
+
+    impl ConfigFile for PrototypeConfig {
+        const PATH: &'static str = "prototype.yaml";
+    }
+
+    #[test]
+    fn test_missing_file_inserts_default() {
+        with_test_dir(&[], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin_or_default::<PrototypeConfig>);
+            app.update();
+            assert_eq!(
+                app.world().resource::<PrototypeConfig>(),
+                &PrototypeConfig::default()
+            );
+        });
+    }
+
+    #[test]
+    fn test_invalid_file_still_fails() {
+        with_test_dir(&[("prototype.yaml", "value: [not a number\n")], |_| {
+            let mut app = App::new();
+            app.set_error_handler(bevy::ecs::error::ignore);
+            app.add_plugins(config_file_plugin_or_default::<PrototypeConfig>);
+            app.update();
+            assert!(app.world().get_resource::<PrototypeConfig>().is_none());
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod plugin_group_tests {
+    use super::*;
+    use bevy::ecs::error::{BevyError, ErrorContext};
+    use bevy::prelude::*;
+    use bevy_config_file::{
+        ConfigFilePlugins, ConfigLoadErrors, config_file_plugin_capture_errors,
+    };
+    use std::sync::Mutex;
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct GroupCamera {
+        pan_speed: f32,
+    }
+
+    impl ConfigFile for GroupCamera {
+        const PATH: &'static str = "camera.yaml";
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct GroupAudio {
+        volume: f32,
+    }
+
+    impl ConfigFile for GroupAudio {
+        const PATH: &'static str = "audio.yaml";
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct GroupInput {
+        bindings: Vec<String>,
+    }
+
+    impl ConfigFile for GroupInput {
+        const PATH: &'static str = "input.yaml";
+    }
+
+    #[test]
+    fn test_group_loads_every_config() {
+        with_test_dir(
+            &[
+                ("camera.yaml", "pan_speed: 2.0\n"),
+                ("audio.yaml", "volume: 0.5\n"),
+            ],
+            |_| {
+                let mut app = App::new();
+                app.add_plugins(
+                    ConfigFilePlugins::default()
+                        .add::<GroupCamera>()
+                        .add::<GroupAudio>(),
+                );
+                app.update();
+
+                assert_eq!(app.world().resource::<GroupCamera>().pan_speed, 2.0);
+                assert_eq!(app.world().resource::<GroupAudio>().volume, 0.5);
+            },
+        );
+    }
+
+    const BROKEN_FILES: &[(&str, &str)] = &[
+        ("camera.yaml", "pan_speed: [broken\n"),
+        ("audio.yaml", "volume: loud\n"),
+    ];
+
+    fn broken_group(fail_app: bool) -> ConfigFilePlugins {
+        ConfigFilePlugins::default()
+            .add::<GroupCamera>()
+            .add::<GroupAudio>()
+            .collect_errors(fail_app)
+    }
+
+    #[test]
+    fn test_collected_errors_include_every_broken_file() {
+        with_test_dir(BROKEN_FILES, |_| {
+            let mut app = App::new();
+            app.add_plugins(broken_group(false));
+            app.update();
+
+            let errors = &app.world().resource::<ConfigLoadErrors>().errors;
+            let mut type_names: Vec<_> = errors.iter().map(|(name, _)| name.as_str()).collect();
+            type_names.sort();
+            assert_eq!(type_names, ["GroupAudio", "GroupCamera"]);
+            assert!(!app.world().contains_resource::<GroupCamera>());
+        });
+    }
+
+    static REPORTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    fn record_error(error: BevyError, _ctx: ErrorContext) {
+        REPORTED.lock().unwrap().push(error.to_string());
+    }
+
+    #[test]
+    fn test_collected_errors_fail_the_app_once() {
+        with_test_dir(BROKEN_FILES, |_| {
+            REPORTED.lock().unwrap().clear();
+            let mut app = App::new();
+            app.set_error_handler(record_error);
+            app.add_plugins(broken_group(true));
+            app.update();
+
+            let reported = REPORTED.lock().unwrap();
+            assert_eq!(reported.len(), 1, "{:?}", reported);
+            assert!(
+                reported[0].contains("2 config(s) failed to load"),
+                "{}",
+                reported[0]
+            );
+            assert!(reported[0].contains("GroupCamera: "), "{}", reported[0]);
+            assert!(reported[0].contains("GroupAudio: "), "{}", reported[0]);
+        });
+    }
+
+    #[test]
+    fn test_each_collecting_group_summarizes_its_own_configs() {
+        let files = [
+            ("camera.yaml", "pan_speed: [broken\n"),
+            ("audio.yaml", "volume: loud\n"),
+            ("input.yaml", "bindings: 3\n"),
+        ];
+        with_test_dir(&files, |_| {
+            REPORTED.lock().unwrap().clear();
+            let mut app = App::new();
+            app.set_error_handler(record_error);
+            app.add_plugins(config_file_plugin_capture_errors::<GroupInput>);
+            app.add_plugins((
+                ConfigFilePlugins::default()
+                    .add::<GroupCamera>()
+                    .collect_errors(true),
+                ConfigFilePlugins::default()
+                    .add::<GroupAudio>()
+                    .collect_errors(true),
+            ));
+            app.update();
+
+            let mut reported = REPORTED.lock().unwrap().clone();
+            reported.sort();
+            assert_eq!(reported.len(), 2, "{:?}", reported);
+            assert!(reported[0].starts_with("1 config(s) failed to load: GroupAudio: "));
+            assert!(reported[1].starts_with("1 config(s) failed to load: GroupCamera: "));
+            assert!(reported.iter().all(|error| !error.contains("GroupInput")));
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod nonfatal_plugin_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::config_file_plugin_nonfatal;
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct LiveTuning {
+        speed: f32,
+    }
+
+    impl ConfigFile for LiveTuning {
+        const PATH: &'static str = "live_tuning.yaml";
+    }
+
+    #[test]
+    fn test_invalid_file_leaves_resource_absent() {
+        with_test_dir(&[("live_tuning.yaml", "speed: [not a number\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin_nonfatal::<LiveTuning>);
+            app.update();
+            app.update();
+            assert!(app.world().get_resource::<LiveTuning>().is_none());
+        });
+    }
+
+    #[test]
+    fn test_valid_file_inserts_resource() {
+        with_test_dir(&[("live_tuning.yaml", "speed: 2.5\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin_nonfatal::<LiveTuning>);
+            app.update();
+            assert_eq!(app.world().resource::<LiveTuning>().speed, 2.5);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod reload_command_tests {
+    use super::*;
+    use bevy::ecs::system::Command;
+    use bevy::prelude::*;
+    use bevy_config_file::{LoadConfigError, ReloadConfig};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct DebugMenu {
+        speed: f32,
+    }
+
+    impl ConfigFile for DebugMenu {
+        const PATH: &'static str = "debug_menu.yaml";
+    }
+
+    #[test]
+    fn test_successful_reload_updates_resource() {
+        with_test_dir(&[("debug_menu.yaml", "speed: 1.0\n")], |_| {
+            let mut world = World::new();
+            ReloadConfig::<DebugMenu>::new().apply(&mut world).unwrap();
+            assert_eq!(world.resource::<DebugMenu>().speed, 1.0);
+
+            std::fs::write("debug_menu.yaml", "speed: 2.0\n").unwrap();
+            ReloadConfig::<DebugMenu>::new().apply(&mut world).unwrap();
+            assert_eq!(world.resource::<DebugMenu>().speed, 2.0);
+        });
+    }
+
+    #[test]
+    fn test_failed_reload_keeps_resource() {
+        with_test_dir(&[("debug_menu.yaml", "speed: 1.0\n")], |_| {
+            let mut world = World::new();
+            ReloadConfig::<DebugMenu>::new().apply(&mut world).unwrap();
+
+            std::fs::write("debug_menu.yaml", "speed: [not a number\n").unwrap();
+            let result = ReloadConfig::<DebugMenu>::new().apply(&mut world);
+            assert!(matches!(result, Err(LoadConfigError::Yaml(_))));
+            assert_eq!(world.resource::<DebugMenu>().speed, 1.0);
+        });
+    }
+
+    #[test]
+    fn test_reload_of_unchanged_file_restores_mutated_resource() {
+        with_test_dir(&[("debug_menu.yaml", "speed: 1.0\n")], |_| {
+            let mut world = World::new();
+            ReloadConfig::<DebugMenu>::new().apply(&mut world).unwrap();
+
+            world.resource_mut::<DebugMenu>().speed = 5.0;
+            ReloadConfig::<DebugMenu>::new().apply(&mut world).unwrap();
+            assert_eq!(world.resource::<DebugMenu>().speed, 1.0);
+        });
+    }
+
+    fn refuse_to_serialize<S: serde::Serializer>(_: &String, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom("write-only field"))
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct WriteOnlyMenu {
+        #[serde(serialize_with = "refuse_to_serialize")]
+        title: String,
+    }
+
+    impl ConfigFile for WriteOnlyMenu {
+        const PATH: &'static str = "write_only_menu.yaml";
+    }
+
+    #[test]
+    fn test_reload_of_unserializable_config_is_not_skipped() {
+        with_test_dir(&[("write_only_menu.yaml", "title: Old\n")], |_| {
+            let mut world = World::new();
+            ReloadConfig::<WriteOnlyMenu>::new().apply(&mut world).unwrap();
+
+            std::fs::write("write_only_menu.yaml", "title: New\n").unwrap();
+            ReloadConfig::<WriteOnlyMenu>::new().apply(&mut world).unwrap();
+            assert_eq!(world.resource::<WriteOnlyMenu>().title, "New");
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod reload_change_detection_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{clear_override, config_file_plugin, set_override};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct WatchedSettings {
+        speed: f32,
+    }
+
+    impl ConfigFile for WatchedSettings {
+        const PATH: &'static str = "watched.yaml";
+    }
+
+    #[derive(Resource, Default)]
+    struct ChangeCount(u32);
+
+    fn count_changes(mut count: ResMut<ChangeCount>) {
+        count.0 += 1;
+    }
+
+    #[test]
+    fn test_reload_marks_resource_changed_once() {
+        with_test_dir(&[("watched.yaml", "speed: 1.0\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<WatchedSettings>);
+            app.init_resource::<ChangeCount>();
+            app.add_systems(
+                PostUpdate,
+                count_changes.run_if(resource_changed::<WatchedSettings>),
+            );
+            app.update();
+            assert_eq!(app.world().resource::<ChangeCount>().0, 1);
+
+            set_override::<WatchedSettings>(r#"{"speed": 2.0}"#);
+            app.update();
+            app.update();
+            clear_override::<WatchedSettings>();
+
+            assert_eq!(app.world().resource::<WatchedSettings>().speed, 2.0);
+            assert_eq!(app.world().resource::<ChangeCount>().0, 2);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod reset_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{config_file_plugin, reset_config_file};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct ResettableAudio {
+        volume: f32,
+        muted: bool,
+    }
+
+    impl ConfigFile for ResettableAudio {
+        const PATH: &'static str = "resettable_audio.yaml";
+    }
+
+    #[test]
+    fn test_reset_restores_file_values_over_override_and_edits() {
+        with_test_dir(
+            &[("resettable_audio.yaml", "volume: 1.0\nmuted: false\n")],
+            |_| {
+                unsafe { std::env::set_var("CONFIG_ResettableAudio", r#"{"volume": 0.25}"#) };
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin::<ResettableAudio>);
+                app.update();
+                assert_eq!(app.world().resource::<ResettableAudio>().volume, 0.25);
+                app.world_mut().resource_mut::<ResettableAudio>().muted = true;
+
+                let world = app.world_mut();
+                let result = reset_config_file::<ResettableAudio>(&mut world.commands());
+                world.flush();
+                unsafe { std::env::remove_var("CONFIG_ResettableAudio") };
+
+                result.unwrap();
+                let config = app.world().resource::<ResettableAudio>();
+                assert_eq!(config.volume, 1.0);
+                assert!(!config.muted);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod reload_group_tests {
+    use super::*;
+    use bevy::ecs::system::Command;
+    use bevy::prelude::*;
+    use bevy_config_file::{LoadConfigError, ReloadConfigGroup};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct Economy {
+        items: Vec<String>,
+    }
+
+    impl ConfigFile for Economy {
+        const PATH: &'static str = "economy.yaml";
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct Shop {
+        stock: Vec<String>,
+    }
+
+    impl ConfigFile for Shop {
+        const PATH: &'static str = "shop.yaml";
+    }
+
+    fn stocks_known_items((economy, shop): &(Economy, Shop)) -> Result<(), String> {
+        match shop.stock.iter().find(|item| !economy.items.contains(item)) {
+            Some(item) => Err(format!("unknown item {}", item)),
+            None => Ok(()),
+        }
+    }
+
+    fn loaded_world() -> World {
+        let mut world = World::new();
+        ReloadConfigGroup::<(Economy, Shop)>::new()
+            .apply(&mut world)
+            .unwrap();
+        world
+    }
+
+    const FILES: &[(&str, &str)] = &[
+        ("economy.yaml", "items: [sword]\n"),
+        ("shop.yaml", "stock: [sword]\n"),
+    ];
+
+    #[test]
+    fn test_successful_reload_replaces_every_config() {
+        with_test_dir(FILES, |_| {
+            let mut world = loaded_world();
+            fs::write("economy.yaml", "items: [sword, shield]\n").unwrap();
+            fs::write("shop.yaml", "stock: [shield]\n").unwrap();
+
+            ReloadConfigGroup::<(Economy, Shop)>::new()
+                .with_check(stocks_known_items)
+                .apply(&mut world)
+                .unwrap();
+            assert_eq!(world.resource::<Economy>().items, ["sword", "shield"]);
+            assert_eq!(world.resource::<Shop>().stock, ["shield"]);
+        });
+    }
+
+    #[test]
+    fn test_failing_config_keeps_every_config() {
+        with_test_dir(FILES, |_| {
+            let mut world = loaded_world();
+            fs::write("economy.yaml", "items: [sword, shield]\n").unwrap();
+            fs::write("shop.yaml", "stock: [not closed\n").unwrap();
+
+            let result = ReloadConfigGroup::<(Economy, Shop)>::new().apply(&mut world);
+            assert!(matches!(result, Err(LoadConfigError::Yaml(_))));
+            assert_eq!(world.resource::<Economy>().items, ["sword"]);
+            assert_eq!(world.resource::<Shop>().stock, ["sword"]);
+        });
+    }
+
+    #[test]
+    fn test_failing_check_keeps_every_config() {
+        with_test_dir(FILES, |_| {
+            let mut world = loaded_world();
+            fs::write("economy.yaml", "items: [shield]\n").unwrap();
+
+            let result = ReloadConfigGroup::<(Economy, Shop)>::new()
+                .with_check(stocks_known_items)
+                .apply(&mut world);
+            assert!(
+                matches!(result, Err(LoadConfigError::Validation(message)) if message == "unknown item sword")
+            );
+            assert_eq!(world.resource::<Economy>().items, ["sword"]);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod error_context_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{LoadConfigError, load_resource_from_config_file};
+
+    #[derive(Resource, Debug, Serialize, Deserialize, Reflect)]
+    struct RadarSettings {
+        range: f32,
+    }
+
+    impl ConfigFile for RadarSettings {
+        const PATH: &'static str = "config/radar.yaml";
+    }
+
+    #[test]
+    fn test_error_names_type_and_path() {
+        with_test_dir(&[("config/radar.yaml", "range: far\n")], |_| {
+            let error = bevy_config_file::load_config_file::<RadarSettings>()
+                .unwrap_err()
+                .with_context::<RadarSettings>();
+
+            assert!(matches!(error.error, LoadConfigError::Yaml(_)));
+            let message = error.to_string();
+            assert!(
+                message.starts_with("failed to load RadarSettings from config/radar.yaml: "),
+                "{message}"
+            );
+        });
+    }
+
+    #[test]
+    fn test_startup_system_error_names_type_and_path() {
+        with_test_dir(&[("config/radar.yaml", "range: far\n")], |_| {
+            let mut world = World::new();
+            let result: Result<(), BevyError> = world
+                .run_system_cached(load_resource_from_config_file::<RadarSettings>)
+                .unwrap();
+            let error = result.unwrap_err().to_string();
+
+            assert!(error.contains("RadarSettings"), "{error}");
+            assert!(error.contains("config/radar.yaml"), "{error}");
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod save_tests {
+    use super::*;
+    use bevy_config_file::{load_config_file, save_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct PlayerSettings {
+        volume: f32,
+        name: String,
+        bindings: Vec<String>,
+    }
+
+    impl ConfigFile for PlayerSettings {
+        const PATH: &'static str = "saves/player/settings.yaml";
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ServerSection {
+        port: u16,
+    }
+
+    impl ConfigFile for ServerSection {
+        const PATH: &'static str = "app.yaml";
+        const ROOT_KEY: Option<&'static str> = Some("server");
+    }
+
+    #[test]
+    fn test_save_round_trips_through_new_directories() {
+        with_test_dir(&[], |dir| {
+            let mut settings = PlayerSettings {
+                volume: 0.8,
+                name: "Ada".to_string(),
+                bindings: vec!["W".to_string(), "Space".to_string()],
+            };
+            save_config_file(&settings).unwrap();
+            assert_eq!(load_config_file::<PlayerSettings>().unwrap(), settings);
+
+            settings.volume = 0.25;
+            save_config_file(&settings).unwrap();
+            assert_eq!(load_config_file::<PlayerSettings>().unwrap(), settings);
+
+            // Only the config file is left behind, not the temporary file
+            let files: Vec<_> = fs::read_dir(dir.join("saves/player")).unwrap().collect();
+            assert_eq!(files.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_save_with_root_key_keeps_other_sections() {
+        let app = "server:\n  port: 80\nclient:\n  theme: dark\n";
+        with_test_dir(&[("app.yaml", app)], |_| {
+            save_config_file(&ServerSection { port: 8080 }).unwrap();
+
+            assert_eq!(load_config_file::<ServerSection>().unwrap().port, 8080);
+            let saved = fs::read_to_string("app.yaml").unwrap();
+            assert!(saved.contains("theme: dark"), "{}", saved);
+        });
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct AudioSection {
+        volume: f32,
+        music: f32,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct VideoSection {
+        vsync: bool,
+        resolution: Vec<u32>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct LargeSettings {
+        audio: AudioSection,
+        video: VideoSection,
+        name: String,
+    }
+
+    impl ConfigFile for LargeSettings {
+        const PATH: &'static str = "large_settings.yaml";
+        const SAVE_CHANGES_ONLY: bool = true;
+    }
+
+    #[test]
+    fn test_save_changes_only_keeps_unrelated_sections() {
+        let file = "# Player settings\n\
+                    audio:\n  volume: 0.8  # range: 0..1\n  music:   0.5\n\n\
+                    # Display\n\
+                    video:\n  vsync: true # keep\n  resolution: [1920, 1080]\n\
+                    name: 'Ada'\n";
+        with_test_dir(&[("large_settings.yaml", file)], |_| {
+            let mut settings = load_config_file::<LargeSettings>().unwrap();
+            settings.audio.volume = 0.3;
+            save_config_file(&settings).unwrap();
+
+            assert_eq!(
+                fs::read_to_string("large_settings.yaml").unwrap(),
+                file.replace("volume: 0.8", "volume: 0.3")
+            );
+            assert_eq!(load_config_file::<LargeSettings>().unwrap(), settings);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod config_fs_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{
+        ConfigFs, ConfigMeta, clear_config_fs, config_file_plugin, load_config_file,
+        set_config_fs,
+    };
+    use std::collections::HashMap;
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    /// Serves files from memory, falling back to disk so concurrent tests are unaffected.
+    struct MemoryFs(HashMap<PathBuf, &'static str>);
+
+    impl ConfigFs for MemoryFs {
+        fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+            match self.0.get(path) {
+                Some(content) => Ok(content.as_bytes().to_vec()),
+                None => fs::read(path),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ModdedSettings {
+        difficulty: String,
+        lives: u32,
+    }
+
+    impl ConfigFile for ModdedSettings {
+        const PATH: &'static str = "vfs/modded.yaml";
+        const EXTENDS: bool = true;
+    }
+
+    #[test]
+    fn test_config_loads_through_installed_fs() {
+        with_test_dir(&[], |_| {
+            set_config_fs(MemoryFs(HashMap::from([
+                (
+                    PathBuf::from("vfs/modded.yaml"),
+                    "extends: base.yaml\ndifficulty: hard\n",
+                ),
+                (
+                    PathBuf::from("vfs/base.yaml"),
+                    "difficulty: easy\nlives: 3\n",
+                ),
+            ])));
+            let config = load_config_file::<ModdedSettings>();
+            clear_config_fs();
+
+            assert_eq!(
+                config.unwrap(),
+                ModdedSettings {
+                    difficulty: "hard".to_string(),
+                    lives: 3
+                }
+            );
+            assert!(load_config_file::<ModdedSettings>().is_err());
+        });
+    }
+
+    /// Serves a single in-memory directory of field files.
+    struct FieldDirFs;
+
+    impl ConfigFs for FieldDirFs {
+        fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+            match path.to_str() {
+                Some("vfs/ship/speed.yaml") => Ok(b"12.0\n".to_vec()),
+                Some("vfs/ship/name.yaml") => Ok(b"Voyager\n".to_vec()),
+                _ => fs::read(path),
+            }
+        }
+
+        fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+            if path == Path::new("vfs/ship") {
+                return Ok(vec![
+                    PathBuf::from("vfs/ship/speed.yaml"),
+                    PathBuf::from("vfs/ship/name.yaml"),
+                    PathBuf::from("vfs/ship/notes.txt"),
+                ]);
+            }
+            Err(io::ErrorKind::NotFound.into())
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ShipFields {
+        speed: f32,
+        name: String,
+    }
+
+    impl ConfigFile for ShipFields {
+        const PATH: &'static str = "vfs/ship.yaml";
+        const FIELD_DIR: Option<&'static str> = Some("vfs/ship");
+    }
+
+    #[test]
+    fn test_field_dir_listed_through_installed_fs() {
+        with_test_dir(&[], |_| {
+            set_config_fs(FieldDirFs);
+            let config = load_config_file::<ShipFields>();
+            clear_config_fs();
+
+            assert_eq!(
+                config.unwrap(),
+                ShipFields {
+                    speed: 12.0,
+                    name: "Voyager".to_string(),
+                }
+            );
+            assert!(load_config_file::<ShipFields>().is_err());
+        });
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct MountedSettings {
+        level: u32,
+    }
+
+    impl ConfigFile for MountedSettings {
+        const PATH: &'static str = "vfs/mounted.yaml";
+    }
+
+    #[test]
+    fn test_meta_has_no_file_mtime_under_installed_fs() {
+        with_test_dir(&[("vfs/mounted.yaml", "level: 1\n")], |_| {
+            set_config_fs(MemoryFs(HashMap::from([(
+                PathBuf::from("vfs/mounted.yaml"),
+                "level: 2\n",
+            )])));
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<MountedSettings>);
+            app.update();
+            clear_config_fs();
+
+            assert_eq!(app.world().resource::<MountedSettings>().level, 2);
+            let meta = app.world().resource::<ConfigMeta<MountedSettings>>();
+            assert_eq!(meta.file_mtime, None);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod loaded_message_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{ConfigLoaded, clear_override, config_file_plugin, set_override};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct BindingConfig {
+        jump: String,
+    }
+
+    impl ConfigFile for BindingConfig {
+        const PATH: &'static str = "bindings.yaml";
+    }
+
+    #[derive(Resource, Default)]
+    struct Loaded(Vec<(&'static str, String)>);
+
+    fn record_loaded(
+        mut reader: MessageReader<ConfigLoaded<BindingConfig>>,
+        mut loaded: ResMut<Loaded>,
+    ) {
+        for message in reader.read() {
+            loaded.0.push((message.type_name, message.path.clone()));
+        }
+    }
+
+    #[test]
+    fn test_first_load_writes_one_message() {
+        with_test_dir(&[("bindings.yaml", "jump: Space\n")], |_| {
+            let mut app = App::new();
+            app.init_resource::<Loaded>();
+            app.add_plugins(config_file_plugin::<BindingConfig>);
+            app.add_systems(Update, record_loaded);
+            app.update();
+
+            set_override::<BindingConfig>(r#"{"jump": "W"}"#);
+            app.update();
+            app.update();
+            clear_override::<BindingConfig>();
+
+            assert_eq!(app.world().resource::<BindingConfig>().jump, "W");
+            assert_eq!(
+                app.world().resource::<Loaded>().0,
+                [("BindingConfig", "bindings.yaml".to_string())]
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod env_field_override_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Audio {
+        volume: f32,
+        device: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ShellTuning {
+        pan_speed: f32,
+        zoom_speed: f32,
+        audio: Audio,
+    }
+
+    impl ConfigFile for ShellTuning {
+        const PATH: &'static str = "shell_tuning.yaml";
+    }
+
+    const FILE: &str = "pan_speed: 1000\nzoom_speed: 1\naudio:\n  volume: 1\n  device: default\n";
+
+    #[test]
+    fn test_field_override_composes_with_and_beats_object_override() {
+        with_test_dir(&[("shell_tuning.yaml", FILE)], |_| {
+            unsafe {
+                std::env::set_var(
+                    "CONFIG_ShellTuning",
+                    r#"{"pan_speed": 1500, "zoom_speed": 2}"#,
+                );
+                std::env::set_var("CONFIG_ShellTuning__pan_speed", "2000.0");
+                std::env::set_var("CONFIG_ShellTuning__audio__device", "headphones");
+            }
+            let config = load_config_file::<ShellTuning>();
+            unsafe {
+                std::env::remove_var("CONFIG_ShellTuning");
+                std::env::remove_var("CONFIG_ShellTuning__pan_speed");
+                std::env::remove_var("CONFIG_ShellTuning__audio__device");
+            }
+
+            assert_eq!(
+                config.unwrap(),
+                ShellTuning {
+                    pan_speed: 2000.0,
+                    zoom_speed: 2.0,
+                    audio: Audio {
+                        volume: 1.0,
+                        device: "headphones".to_string()
+                    }
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_field_override_without_object_override() {
+        with_test_dir(&[("shell_tuning.yaml", FILE)], |_| {
+            unsafe { std::env::set_var("CONFIG_ShellTuning__zoom_speed", "3") };
+            let config = load_config_file::<ShellTuning>();
+            unsafe { std::env::remove_var("CONFIG_ShellTuning__zoom_speed") };
+
+            let config = config.unwrap();
+            assert_eq!(config.pan_speed, 1000.0);
+            assert_eq!(config.zoom_speed, 3.0);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod env_prefix_tests {
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct CiSettings {
+        pan_speed: f32,
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for CiSettings {
+        const PATH: &'static str = "ci_settings.yaml";
+        const ENV_PREFIX: &'static str = "MYGAME_";
+    }
+
+    #[test]
+    fn test_custom_prefix_replaces_default_prefix() {
+        let files = [("ci_settings.yaml", "pan_speed: 1000\nzoom_speed: 1\n")];
+        with_test_dir(&files, |_| {
+            unsafe {
+                std::env::set_var("MYGAME_CiSettings", r#"{"pan_speed": 2000}"#);
+                std::env::set_var("CONFIG_CiSettings", r#"{"zoom_speed": 3}"#);
+            }
+            let config = bevy_config_file::load_config_file::<CiSettings>();
+            unsafe {
+                std::env::remove_var("MYGAME_CiSettings");
+                std::env::remove_var("CONFIG_CiSettings");
+            }
+
+            assert_eq!(
+                config.unwrap(),
+                CiSettings {
+                    pan_speed: 2000.0,
+                    zoom_speed: 1.0
+                }
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod env_format_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, OverrideFormat, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct YamlOverridden {
+        pan_speed: f32,
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for YamlOverridden {
+        const PATH: &'static str = "camera.yaml";
+        const ENV_FORMAT: OverrideFormat = OverrideFormat::Yaml;
+    }
+
+    const FILES: &[(&str, &str)] = &[("camera.yaml", "pan_speed: 1.0\nzoom_speed: 1.0\n")];
+
+    fn load_with_override(value: &str) -> Result<YamlOverridden, LoadConfigError> {
+        unsafe { std::env::set_var("CONFIG_YamlOverridden", value) };
+        let config = load_config_file::<YamlOverridden>();
+        unsafe { std::env::remove_var("CONFIG_YamlOverridden") };
+        config
+    }
+
+    #[test]
+    fn test_yaml_override_sets_field() {
+        with_test_dir(FILES, |_| {
+            assert_eq!(
+                load_with_override("pan_speed: 2000.0").unwrap(),
+                YamlOverridden {
+                    pan_speed: 2000.0,
+                    zoom_speed: 1.0,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_json_override_still_parses() {
+        with_test_dir(FILES, |_| {
+            assert_eq!(
+                load_with_override(r#"{"zoom_speed": 3.0}"#)
+                    .unwrap()
+                    .zoom_speed,
+                3.0
+            );
+        });
+    }
+
+    #[test]
+    fn test_invalid_override_reports_yaml_error() {
+        with_test_dir(FILES, |_| {
+            assert!(matches!(
+                load_with_override("pan_speed: [2000"),
+                Err(LoadConfigError::Yaml(_))
+            ));
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod env_var_names_tests {
+    use super::*;
+    use bevy_config_file::{load_config_file, set_env_var_name};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct MappedSettings {
+        pan_speed: f32,
+    }
+
+    impl ConfigFile for MappedSettings {
+        const PATH: &'static str = "mapped.yaml";
+    }
+
+    #[test]
+    fn test_mapped_name_replaces_derived_name() {
+        with_test_dir(&[("mapped.yaml", "pan_speed: 1.0\n")], |_| {
+            set_env_var_name::<MappedSettings>("MYGAME_MAPPED");
+            unsafe {
+                std::env::set_var("CONFIG_MappedSettings", r#"{"pan_speed": 2.0}"#);
+                std::env::set_var("MYGAME_MAPPED", r#"{"pan_speed": 3.0}"#);
+            };
+            let config = load_config_file::<MappedSettings>();
+            unsafe {
+                std::env::remove_var("CONFIG_MappedSettings");
+                std::env::remove_var("MYGAME_MAPPED");
+            };
+
+            assert_eq!(config.unwrap().pan_speed, 3.0);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod env_default_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ContainerSettings {
+        pan_speed: f32,
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for ContainerSettings {
+        const PATH: &'static str = "container.yaml";
+    }
+
+    #[test]
+    fn test_env_default_fills_missing_field_only() {
+        with_test_dir(&[("container.yaml", "pan_speed: 3.0\n")], |_| {
+            unsafe {
+                std::env::set_var(
+                    "CONFIG_DEFAULT_ContainerSettings",
+                    r#"{"pan_speed": 1.0, "zoom_speed": 1.5}"#,
+                )
+            };
+            let config = load_config_file::<ContainerSettings>();
+            unsafe { std::env::remove_var("CONFIG_DEFAULT_ContainerSettings") };
+
+            assert_eq!(
+                config.unwrap(),
+                ContainerSettings {
+                    pan_speed: 3.0,
+                    zoom_speed: 1.5,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_override_still_wins_over_env_default() {
+        with_test_dir(&[("container.yaml", "pan_speed: 3.0\n")], |_| {
+            unsafe {
+                std::env::set_var("CONFIG_DEFAULT_ContainerSettings", r#"{"zoom_speed": 1.5}"#);
+                std::env::set_var("CONFIG_ContainerSettings", r#"{"zoom_speed": 4.0}"#);
+            };
+            let config = load_config_file::<ContainerSettings>();
+            unsafe {
+                std::env::remove_var("CONFIG_DEFAULT_ContainerSettings");
+                std::env::remove_var("CONFIG_ContainerSettings");
+            };
+
+            assert_eq!(config.unwrap().zoom_speed, 4.0);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod env_var_tests {
+    use super::*;
+
+    mod audio {
+        use super::*;
+
+        #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+        pub struct Settings {
+            pub volume: f32,
+        }
+
+        impl ConfigFile for Settings {
+            const PATH: &'static str = "audio.yaml";
+            const ENV_VAR: Option<&'static str> = Some("AUDIO_SETTINGS");
+        }
+    }
+
+    mod video {
+        use super::*;
+
+        #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+        pub struct Settings {
+            pub volume: f32,
+        }
+
+        impl ConfigFile for Settings {
+            const PATH: &'static str = "video.yaml";
+            const ENV_VAR: Option<&'static str> = Some("VIDEO_SETTINGS");
+        }
+    }
+
+    #[test]
+    fn test_same_named_types_read_their_own_env_var() {
+        let files = [("audio.yaml", "volume: 1\n"), ("video.yaml", "volume: 1\n")];
+        with_test_dir(&files, |_| {
+            unsafe {
+                std::env::set_var("AUDIO_SETTINGS", r#"{"volume": 0.5}"#);
+                std::env::set_var("VIDEO_SETTINGS", r#"{"volume": 0.25}"#);
+                std::env::set_var("CONFIG_Settings", r#"{"volume": 0.0}"#);
+            }
+            let audio = bevy_config_file::load_config_file::<audio::Settings>();
+            let video = bevy_config_file::load_config_file::<video::Settings>();
+            unsafe {
+                std::env::remove_var("AUDIO_SETTINGS");
+                std::env::remove_var("VIDEO_SETTINGS");
+                std::env::remove_var("CONFIG_Settings");
+            }
+
+            assert_eq!(audio.unwrap().volume, 0.5);
+            assert_eq!(video.unwrap().volume, 0.25);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod merge_resource_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::merge_config_into_resource;
+    use std::collections::HashMap;
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct ModContent {
+        units: Vec<String>,
+        prices: HashMap<String, u32>,
+        difficulty: f32,
+    }
+
+    impl ConfigFile for ModContent {
+        const PATH: &'static str = "mods/content.yaml";
+    }
+
+    #[test]
+    fn test_sequential_merges_accumulate() {
+        let files = [
+            (
+                "mods/base.yaml",
+                "units: [knight]\nprices: {knight: 10}\ndifficulty: 1\n",
+            ),
+            (
+                "mods/archers.yaml",
+                "units: [archer]\nprices: {archer: 15, knight: 12}\ndifficulty: 2\n",
+            ),
+        ];
+        with_test_dir(&files, |_| {
+            let mut world = World::new();
+            for path in ["mods/base.yaml", "mods/archers.yaml"] {
+                let pack: ModContent =
+                    serde_yml::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+                merge_config_into_resource(&mut world, pack);
+            }
+
+            assert_eq!(
+                world.resource::<ModContent>(),
+                &ModContent {
+                    units: vec!["knight".to_string(), "archer".to_string()],
+                    prices: HashMap::from([("knight".to_string(), 12), ("archer".to_string(), 15)]),
+                    difficulty: 2.0,
+                }
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod json_schema_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{config_json_schema, write_config_json_schema};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[derive(Reflect)]
+    enum Quality {
+        Low,
+        High,
+    }
+
+    #[derive(Reflect)]
+    struct Graphics {
+        quality: Quality,
+        resolution_scale: Option<f32>,
+    }
+
+    #[derive(Reflect)]
+    struct GameSettings {
+        player_name: String,
+        max_fps: u32,
+        fullscreen: bool,
+        graphics: Graphics,
+        recent_servers: Vec<String>,
+        keybinds: HashMap<String, String>,
+    }
+
+    #[test]
+    fn test_schema_lists_properties_and_types() {
+        let schema = config_json_schema::<GameSettings>();
+
+        assert_eq!(schema["title"], "GameSettings");
+        assert_eq!(schema["type"], "object");
+        let properties = &schema["properties"];
+        assert_eq!(properties["player_name"], json!({"type": "string"}));
+        assert_eq!(properties["max_fps"], json!({"type": "integer"}));
+        assert_eq!(properties["fullscreen"], json!({"type": "boolean"}));
+        assert_eq!(
+            properties["recent_servers"],
+            json!({"type": "array", "items": {"type": "string"}})
+        );
+        assert_eq!(
+            properties["keybinds"],
+            json!({"type": "object", "additionalProperties": {"type": "string"}})
+        );
+        assert_eq!(
+            properties["graphics"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "quality": {"enum": ["Low", "High"]},
+                    "resolution_scale": {"anyOf": [{"type": "number"}, {"type": "null"}]}
+                },
+                "required": ["quality"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_schema_written_to_file() {
+        with_test_dir(&[], |_| {
+            write_config_json_schema::<GameSettings>("schemas/game_settings.json").unwrap();
+
+            let written: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string("schemas/game_settings.json").unwrap())
+                    .unwrap();
+            assert_eq!(written, config_json_schema::<GameSettings>());
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "logging"))]
+mod orphan_override_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::config_file_plugin;
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct CameraRig {
+        pan_speed: f32,
+    }
+
+    impl ConfigFile for CameraRig {
+        const PATH: &'static str = "camera_rig.yaml";
+    }
+
+    #[test]
+    fn test_unmatched_override_var_is_warned_about() {
+        let logs = CapturedLogs::default();
+        let _guard = logs.set_default();
+
+        with_test_dir(&[("camera_rig.yaml", "pan_speed: 1000\n")], |_| {
+            unsafe {
+                std::env::set_var("CONFIG_CameraRig", r#"{"pan_speed": 2000}"#);
+                std::env::set_var("CONFIG_CamraRig", r#"{"pan_speed": 3000}"#);
+            }
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<CameraRig>);
+            app.update();
+            unsafe {
+                std::env::remove_var("CONFIG_CameraRig");
+                std::env::remove_var("CONFIG_CamraRig");
+            }
+            assert_eq!(app.world().resource::<CameraRig>().pan_speed, 2000.0);
+        });
+
+        let warned = |var: &str| {
+            logs.lines()
+                .iter()
+                .any(|line| line.contains(&format!("variable {} doesn't match", var)))
+        };
+        assert!(warned("CONFIG_CamraRig"), "{:?}", logs.lines());
+        assert!(!warned("CONFIG_CameraRig"), "{:?}", logs.lines());
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod layer_paths_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{config_file_plugin, load_config_file};
+
+    #[derive(Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    struct Graphics {
+        shadows: bool,
+        resolution: String,
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct TeamSettings {
+        server: String,
+        graphics: Graphics,
+    }
+
+    impl ConfigFile for TeamSettings {
+        const PATH: &'static str = "settings.yaml";
+        const LAYER_PATHS: &'static [&'static str] = &["settings.yaml", "settings.local.yaml"];
+    }
+
+    const BASE: &str = "\
+server: prod.example.com
+graphics:
+  shadows: true
+  resolution: 1920x1080
+";
+
+    fn load(files: &[(&str, &str)]) -> Option<TeamSettings> {
+        let mut loaded = None;
+        with_test_dir(files, |_| {
+            unsafe { std::env::set_var("CONFIG_TeamSettings", r#"{"server": "localhost"}"#) };
+            let mut app = App::new();
+            app.set_error_handler(bevy::ecs::error::ignore);
+            app.add_plugins(config_file_plugin::<TeamSettings>);
+            app.update();
+            unsafe { std::env::remove_var("CONFIG_TeamSettings") };
+            loaded = app.world_mut().remove_resource::<TeamSettings>();
+        });
+        loaded
+    }
+
+    #[test]
+    fn test_later_files_merge_over_earlier_ones_before_overrides() {
+        let local = "server: staging.example.com\ngraphics:\n  shadows: false\n";
+        let settings = load(&[("settings.yaml", BASE), ("settings.local.yaml", local)]);
+        assert_eq!(
+            settings,
+            Some(TeamSettings {
+                server: "localhost".to_string(),
+                graphics: Graphics {
+                    shadows: false,
+                    resolution: "1920x1080".to_string(),
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_layer_is_skipped_but_malformed_layer_fails() {
+        let settings = load(&[("settings.yaml", BASE)]).unwrap();
+        assert!(settings.graphics.shadows);
+
+        let malformed = load(&[
+            ("settings.yaml", BASE),
+            ("settings.local.yaml", "graphics: [\n"),
+        ]);
+        assert_eq!(malformed, None);
+    }
+
+    #[derive(Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    struct ExtendedTeam {
+        server: String,
+    }
+
+    impl ConfigFile for ExtendedTeam {
+        const PATH: &'static str = "team.yaml";
+        const LAYER_PATHS: &'static [&'static str] = &["team.yaml", "team.local.yaml"];
+        const EXTENDS: bool = true;
+    }
+
+    #[test]
+    fn test_missing_extends_target_of_existing_layer_fails() {
+        let files = [
+            ("team.yaml", "server: prod.example.com\n"),
+            ("team.local.yaml", "extends: missing.yaml\nserver: localhost\n"),
+        ];
+        with_test_dir(&files, |_| {
+            let result = load_config_file::<ExtendedTeam>();
+            assert!(
+                matches!(
+                    &result,
+                    Err(bevy_config_file::LoadConfigError::Io(error))
+                        if error.kind() == std::io::ErrorKind::NotFound
+                ),
+                "{:?}",
+                result
+            );
+        });
+    }
+
+    #[derive(Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    struct TeamStrings {
+        greeting: String,
+        farewell: String,
+    }
+
+    impl ConfigFile for TeamStrings {
+        const PATH: &'static str = "strings.{locale}.yaml";
+        const LAYER_PATHS: &'static [&'static str] =
+            &["strings.{locale}.yaml", "strings.{locale}.local.yaml"];
+        const LOCALE_VAR: Option<&'static str> = Some("TEAM_LOCALE");
+    }
+
+    #[test]
+    fn test_layer_paths_substitute_locale() {
+        let files = [
+            ("strings.en.yaml", "greeting: hello\nfarewell: bye\n"),
+            (
+                "strings.fr.yaml",
+                "greeting: bonjour\nfarewell: au revoir\n",
+            ),
+            ("strings.fr.local.yaml", "farewell: salut\n"),
+        ];
+        with_test_dir(&files, |_| {
+            unsafe { std::env::set_var("TEAM_LOCALE", "fr") };
+            let strings = load_config_file::<TeamStrings>();
+            unsafe { std::env::remove_var("TEAM_LOCALE") };
+
+            assert_eq!(
+                strings.unwrap(),
+                TeamStrings {
+                    greeting: "bonjour".to_string(),
+                    farewell: "salut".to_string(),
+                }
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod observer_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{OnConfigChanged, clear_override, config_file_plugin, set_override};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct ObservedConfig {
+        value: i32,
+    }
+
+    impl ConfigFile for ObservedConfig {
+        const PATH: &'static str = "observed.yaml";
+    }
+
+    /// Records `(value seen by the observer, reloaded)` for each trigger.
+    #[derive(Resource, Default)]
+    struct Triggers(Vec<(i32, bool)>);
+
+    fn record_trigger(
+        event: On<OnConfigChanged<ObservedConfig>>,
+        config: Res<ObservedConfig>,
+        mut triggers: ResMut<Triggers>,
+    ) {
+        assert_eq!(event.path, "observed.yaml");
+        triggers.0.push((config.value, event.reloaded));
+    }
+
+    #[test]
+    fn test_observer_receives_trigger_on_load_and_reload() {
+        with_test_dir(&[("observed.yaml", "value: 42\n")], |_| {
+            let mut app = App::new();
+            app.init_resource::<Triggers>();
+            app.add_plugins(config_file_plugin::<ObservedConfig>);
+            app.add_observer(record_trigger);
+
+            app.update();
+            assert_eq!(app.world().resource::<Triggers>().0, vec![(42, false)]);
+
+            set_override::<ObservedConfig>(r#"{"value": 7}"#);
+            app.update();
+            clear_override::<ObservedConfig>();
+            assert_eq!(
+                app.world().resource::<Triggers>().0,
+                vec![(42, false), (7, true)]
+            );
+        });
+    }
+
+    #[test]
+    fn test_reload_with_identical_content_is_skipped() {
+        with_test_dir(&[("observed.yaml", "value: 42\n")], |_| {
+            let mut app = App::new();
+            app.init_resource::<Triggers>();
+            app.add_plugins(config_file_plugin::<ObservedConfig>);
+            app.add_observer(record_trigger);
+            app.update();
+
+            // Rewrite the same content (updating the mtime), then force a reload
+            fs::write("observed.yaml", "value: 42\n").unwrap();
+            set_override::<ObservedConfig>("{}");
+            app.update();
+            assert_eq!(app.world().resource::<Triggers>().0, vec![(42, false)]);
+
+            fs::write("observed.yaml", "value: 43\n").unwrap();
+            set_override::<ObservedConfig>("{}");
+            app.update();
+            clear_override::<ObservedConfig>();
+            assert_eq!(
+                app.world().resource::<Triggers>().0,
+                vec![(42, false), (43, true)]
+            );
+        });
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct ObservedFallback {
+        value: i32,
+    }
+
+    impl ConfigFile for ObservedFallback {
+        const PATH: &'static str = "user/observed.yaml";
+        const FALLBACK_PATHS: &'static [&'static str] = &["defaults/observed.yaml"];
+    }
+
+    #[derive(Resource, Default)]
+    struct ObservedPaths(Vec<String>);
+
+    #[test]
+    fn test_event_path_is_the_file_actually_read() {
+        with_test_dir(&[("defaults/observed.yaml", "value: 1\n")], |_| {
+            let mut app = App::new();
+            app.init_resource::<ObservedPaths>();
+            app.add_plugins(config_file_plugin::<ObservedFallback>);
+            app.add_observer(
+                |event: On<OnConfigChanged<ObservedFallback>>, mut paths: ResMut<ObservedPaths>| {
+                    paths.0.push(event.path.clone());
+                },
+            );
+            app.update();
+            assert_eq!(
+                app.world().resource::<ObservedPaths>().0,
+                ["defaults/observed.yaml"]
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod env_doc_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ServerlessConfig {
+        port: u16,
+        host: String,
+    }
+
+    impl ConfigFile for ServerlessConfig {
+        const PATH: &'static str = "serverless.yaml";
+        const ENV_DOC_VAR: Option<&'static str> = Some("TEST_APP_CONFIG");
+        const ENV_DOC_KEY: Option<&'static str> = Some("server");
+    }
+
+    fn load_with_doc(document: Option<&str>) -> Result<ServerlessConfig, LoadConfigError> {
+        let mut result = None;
+        with_test_dir(&[("serverless.yaml", "port: 80\nhost: file\n")], |_| {
+            if let Some(document) = document {
+                unsafe { std::env::set_var("TEST_APP_CONFIG", document) };
+            }
+            result = Some(load_config_file::<ServerlessConfig>());
+            unsafe { std::env::remove_var("TEST_APP_CONFIG") };
+        });
+        result.unwrap()
+    }
+
+    #[test]
+    fn test_base_config_extracted_from_shared_env_doc() {
+        let config = load_with_doc(Some(
+            r#"{"server": {"port": 8080, "host": "doc"}, "database": {"url": "x"}}"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config,
+            ServerlessConfig {
+                port: 8080,
+                host: "doc".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_file_without_env_doc() {
+        let config = load_with_doc(None).unwrap();
+        assert_eq!(config.port, 80);
+        assert_eq!(config.host, "file");
+    }
+
+    #[test]
+    fn test_missing_key_in_env_doc_errors() {
+        match load_with_doc(Some(r#"{"database": {"url": "x"}}"#)) {
+            Err(LoadConfigError::EnvDocKeyMissing { var, key }) => {
+                assert_eq!(var, "TEST_APP_CONFIG");
+                assert_eq!(key, "server");
+            }
+            other => panic!("expected EnvDocKeyMissing, got {:?}", other),
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod assert_config_parses_tests {
+    use bevy_config_file::{ConfigFile, assert_config_parses};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct FixtureCameraSettings {
+        pan_speed: f32,
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for FixtureCameraSettings {
+        const PATH: &'static str = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/camera_settings.yaml"
+        );
+    }
+
+    #[test]
+    fn test_valid_fixture_parses() {
+        assert_config_parses!(FixtureCameraSettings);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match FixtureCameraSettings")]
+    fn test_invalid_fixture_panics_with_clear_message() {
+        assert_config_parses!(
+            FixtureCameraSettings,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/camera_settings_invalid.yaml"
+            )
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod manifest_relative_tests {
+    use bevy::prelude::Reflect;
+    use bevy_config_file::{ConfigFile, load_config_file};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct ManifestCameraSettings {
+        pan_speed: f32,
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for ManifestCameraSettings {
+        const PATH: &'static str = "tests/fixtures/camera_settings.yaml";
+        const TEST_RELATIVE_TO_MANIFEST: bool = true;
+    }
+
+    #[test]
+    fn test_fixture_loads_relative_to_manifest_dir() {
+        let config = load_config_file::<ManifestCameraSettings>().unwrap();
+        assert_eq!(config.pan_speed, 1000.0);
+        assert_eq!(config.zoom_speed, 1.0);
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod capture_errors_plugin_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{ConfigLoadErrors, config_file_plugin_capture_errors};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct BrokenConfig {
+        value: i32,
+    }
+
+    impl ConfigFile for BrokenConfig {
+        const PATH: &'static str = "broken.yaml";
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct WorkingConfig {
+        value: i32,
+    }
+
+    impl ConfigFile for WorkingConfig {
+        const PATH: &'static str = "working.yaml";
+    }
+
+    #[test]
+    fn test_failed_load_populates_errors_and_app_keeps_running() {
+        with_test_dir(
+            &[
+                ("broken.yaml", "value: not a number\n"),
+                ("working.yaml", "value: 1\n"),
+            ],
+            |_| {
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin_capture_errors::<BrokenConfig>);
+                app.add_plugins(config_file_plugin_capture_errors::<WorkingConfig>);
+                app.update();
+                app.update();
+
+                assert!(app.world().get_resource::<BrokenConfig>().is_none());
+                assert_eq!(app.world().resource::<WorkingConfig>().value, 1);
+
+                let errors = &app.world().resource::<ConfigLoadErrors>().errors;
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].0, "BrokenConfig");
+                assert!(errors[0].1.contains("YAML"), "{}", errors[0].1);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod cli_override_tests {
+    use super::*;
+    use bevy_config_file::{load_config_file, set_cli_args};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct CliWindow {
+        width: u32,
+        title: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct CliConfig {
+        speed: f32,
+        enabled: bool,
+        name: String,
+        window: CliWindow,
+    }
+
+    impl ConfigFile for CliConfig {
+        const PATH: &'static str = "cli.yaml";
+    }
+
+    const CLI_YAML: &str =
+        "speed: 1.0\nenabled: true\nname: file\nwindow:\n  width: 800\n  title: game\n";
+
+    fn load_with_args(args: &[&str], env: Option<&str>) -> CliConfig {
+        let mut config = None;
+        with_test_dir(&[("cli.yaml", CLI_YAML)], |_| {
+            if let Some(env) = env {
+                unsafe { std::env::set_var("CONFIG_CliConfig", env) };
+            }
+            set_cli_args(Some(args.iter().copied()));
+            config = Some(load_config_file::<CliConfig>());
+            set_cli_args(None::<Vec<String>>);
+            unsafe { std::env::remove_var("CONFIG_CliConfig") };
+        });
+        config.unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_cli_args_override_and_coerce_scalars() {
+        let config = load_with_args(
+            &[
+                "--config",
+                "CliConfig.speed=2.5",
+                "--config=CliConfig.enabled=false",
+                "--config",
+                "CliConfig.name=from cli",
+                "--config",
+                "CliConfig.window.width=1024",
+                "--config",
+                "OtherConfig.speed=9",
+                "--verbose",
+            ],
+            None,
+        );
+        assert_eq!(
+            config,
+            CliConfig {
+                speed: 2.5,
+                enabled: false,
+                name: "from cli".to_string(),
+                window: CliWindow {
+                    width: 1024,
+                    title: "game".to_string(),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_cli_args_applied_after_env_override() {
+        let config = load_with_args(
+            &["--config", "CliConfig.speed=3"],
+            Some(r#"{"speed": 2.0, "name": "env"}"#),
+        );
+        assert_eq!(config.speed, 3.0);
+        assert_eq!(config.name, "env");
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod override_not_object_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ObjectConfig {
+        value: i32,
+    }
+
+    impl ConfigFile for ObjectConfig {
+        const PATH: &'static str = "object.yaml";
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ListConfig(Vec<i32>);
+
+    impl ConfigFile for ListConfig {
+        const PATH: &'static str = "list.yaml";
+    }
+
+    #[test]
+    fn test_scalar_override_on_object_config_errors() {
+        with_test_dir(&[("object.yaml", "value: 42\n")], |_| {
+            unsafe { std::env::set_var("CONFIG_ObjectConfig", "42") };
+            let result = load_config_file::<ObjectConfig>();
+            unsafe { std::env::remove_var("CONFIG_ObjectConfig") };
+
+            match result {
+                Err(LoadConfigError::OverrideNotObject {
+                    override_source,
+                    found,
+                }) => {
+                    assert_eq!(override_source, "CONFIG_ObjectConfig");
+                    assert_eq!(found, "a number");
+                }
+                other => panic!("expected OverrideNotObject, got {:?}", other),
+            }
+        });
+    }
+
+    #[test]
+    fn test_array_override_replaces_array_config() {
+        with_test_dir(&[("list.yaml", "- 1\n- 2\n")], |_| {
+            unsafe { std::env::set_var("CONFIG_ListConfig", "[3, 4, 5]") };
+            let result = load_config_file::<ListConfig>();
+            unsafe { std::env::remove_var("CONFIG_ListConfig") };
+
+            assert_eq!(result.unwrap(), ListConfig(vec![3, 4, 5]));
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "inventory"))]
+mod inventory_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{all_config_files_plugin, submit_config_file};
+
+    macro_rules! collected_config {
+        ($name:ident, $path:literal) => {
+            #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+            #[reflect(Resource)]
+            struct $name {
+                value: i32,
+            }
+
+            impl ConfigFile for $name {
+                const PATH: &'static str = $path;
+            }
+
+            submit_config_file!($name);
+        };
+    }
+
+    collected_config!(CollectedA, "collected_a.yaml");
+    collected_config!(CollectedB, "collected_b.yaml");
+    collected_config!(CollectedC, "collected_c.yaml");
+
+    #[test]
+    fn test_all_submitted_configs_load() {
+        with_test_dir(
+            &[
+                ("collected_a.yaml", "value: 1\n"),
+                ("collected_b.yaml", "value: 2\n"),
+                ("collected_c.yaml", "value: 3\n"),
+            ],
+            |_| {
+                let mut app = App::new();
+                app.add_plugins(all_config_files_plugin);
+                app.update();
+
+                assert_eq!(app.world().resource::<CollectedA>().value, 1);
+                assert_eq!(app.world().resource::<CollectedB>().value, 2);
+                assert_eq!(app.world().resource::<CollectedC>().value, 3);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod registration_conflict_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{ConfigRegistry, config_file_plugin, config_file_plugin_if_missing};
+
+    mod audio {
+        use super::*;
+
+        #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+        #[reflect(Resource)]
+        pub struct Settings {
+            pub volume: f32,
+        }
+
+        impl ConfigFile for Settings {
+            const PATH: &'static str = "audio_settings.yaml";
+        }
+    }
+
+    mod video {
+        use super::*;
+
+        #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+        #[reflect(Resource)]
+        pub struct Settings {
+            pub brightness: f32,
+        }
+
+        impl ConfigFile for Settings {
+            const PATH: &'static str = "video_settings.yaml";
+        }
+    }
+
+    #[test]
+    fn test_distinct_types_sharing_short_name_both_load() {
+        with_test_dir(
+            &[
+                ("audio_settings.yaml", "volume: 0.5\n"),
+                ("video_settings.yaml", "brightness: 0.8\n"),
+            ],
+            |_| {
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin::<audio::Settings>);
+                app.add_plugins(config_file_plugin::<video::Settings>);
+                app.update();
+
+                assert_eq!(app.world().resource::<audio::Settings>().volume, 0.5);
+                assert_eq!(app.world().resource::<video::Settings>().brightness, 0.8);
+
+                let registry = app.world().resource::<ConfigRegistry>();
+                let names: Vec<_> = registry.iter().map(|config| config.short_name).collect();
+                assert_eq!(names, vec!["Settings", "Settings"]);
+            },
+        );
+    }
+
+    #[test]
+    fn test_same_type_added_by_two_plugins_is_registered_once() {
+        with_test_dir(&[("audio_settings.yaml", "volume: 0.5\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<audio::Settings>);
+            app.add_plugins(config_file_plugin_if_missing::<audio::Settings>);
+            app.update();
+
+            assert_eq!(app.world().resource::<audio::Settings>().volume, 0.5);
+            assert_eq!(app.world().resource::<ConfigRegistry>().iter().count(), 1);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod no_env_override_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{
+        attributes::NoEnvOverride, config_file_plugin, load_config_file, set_cli_args,
+    };
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct SecureConfig {
+        #[reflect(@NoEnvOverride)]
+        save_dir: String,
+        volume: f32,
+    }
+
+    impl ConfigFile for SecureConfig {
+        const PATH: &'static str = "secure.yaml";
+    }
+
+    #[test]
+    fn test_override_of_protected_field_is_ignored() {
+        with_test_dir(&[("secure.yaml", "save_dir: saves\nvolume: 1.0\n")], |_| {
+            unsafe {
+                std::env::set_var(
+                    "CONFIG_SecureConfig",
+                    r#"{"save_dir": "/etc", "volume": 0.5}"#,
+                )
+            };
+            set_cli_args(Some(["--config", "SecureConfig.save_dir=/tmp"]));
+
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<SecureConfig>);
+            app.update();
+
+            set_cli_args(None::<Vec<String>>);
+            unsafe { std::env::remove_var("CONFIG_SecureConfig") };
+
+            let config = app.world().resource::<SecureConfig>();
+            assert_eq!(config.save_dir, "saves");
+            assert_eq!(config.volume, 0.5);
+        });
+    }
+
+    // Never added with a plugin, so the loader alone must protect the field
+    #[derive(Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    struct UnpluggedSecureConfig {
+        #[reflect(@NoEnvOverride)]
+        #[serde(default)]
+        save_dir: String,
+        volume: f32,
+    }
+
+    impl ConfigFile for UnpluggedSecureConfig {
+        const PATH: &'static str = "unplugged_secure.yaml";
+    }
+
+    #[test]
+    fn test_plain_load_ignores_override_of_protected_field() {
+        let file = "save_dir: saves\nvolume: 1.0\n";
+        with_test_dir(&[("unplugged_secure.yaml", file)], |_| {
+            let json = r#"{"save_dir": "/etc", "volume": 0.5}"#;
+            unsafe { std::env::set_var("CONFIG_UnpluggedSecureConfig", json) };
+            let config = load_config_file::<UnpluggedSecureConfig>();
+            unsafe { std::env::remove_var("CONFIG_UnpluggedSecureConfig") };
+
+            let config = config.unwrap();
+            assert_eq!(config.save_dir, "saves");
+            assert_eq!(config.volume, 0.5);
+        });
+    }
+
+    #[test]
+    fn test_env_default_of_protected_field_is_ignored() {
+        with_test_dir(&[("unplugged_secure.yaml", "volume: 1.0\n")], |_| {
+            let json = r#"{"save_dir": "/etc"}"#;
+            unsafe { std::env::set_var("CONFIG_DEFAULT_UnpluggedSecureConfig", json) };
+            let config = load_config_file::<UnpluggedSecureConfig>();
+            unsafe { std::env::remove_var("CONFIG_DEFAULT_UnpluggedSecureConfig") };
+
+            assert_eq!(config.unwrap().save_dir, "");
+        });
+    }
+
+    #[derive(Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    struct StorageSection {
+        #[reflect(@NoEnvOverride)]
+        save_dir: String,
+        quota: u32,
+    }
+
+    #[derive(Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    struct NestedSecureConfig {
+        storage: StorageSection,
+        volume: f32,
+    }
+
+    impl ConfigFile for NestedSecureConfig {
+        const PATH: &'static str = "nested_secure.yaml";
+    }
+
+    const NESTED_FILE: &str = "storage:\n  save_dir: saves\n  quota: 10\nvolume: 1.0\n";
+
+    #[test]
+    fn test_override_of_nested_protected_field_is_ignored() {
+        with_test_dir(&[("nested_secure.yaml", NESTED_FILE)], |_| {
+            let json = r#"{"storage": {"save_dir": "/etc", "quota": 20}}"#;
+            unsafe { std::env::set_var("CONFIG_NestedSecureConfig", json) };
+            unsafe { std::env::set_var("CONFIG_NestedSecureConfig__storage__save_dir", "/tmp") };
+            set_cli_args(Some(["--config", "NestedSecureConfig.storage.save_dir=/var"]));
+            let config = load_config_file::<NestedSecureConfig>();
+            set_cli_args(None::<Vec<String>>);
+            unsafe { std::env::remove_var("CONFIG_NestedSecureConfig") };
+            unsafe { std::env::remove_var("CONFIG_NestedSecureConfig__storage__save_dir") };
+
+            let config = config.unwrap();
+            assert_eq!(config.storage.save_dir, "saves");
+            assert_eq!(config.storage.quota, 20);
+        });
+    }
+
+    #[test]
+    fn test_override_of_parent_keeps_nested_protected_field() {
+        with_test_dir(&[("nested_secure.yaml", NESTED_FILE)], |_| {
+            let args = ["--config", r#"NestedSecureConfig.storage={"save_dir": "/etc", "quota": 5}"#];
+            set_cli_args(Some(args));
+            let config = load_config_file::<NestedSecureConfig>();
+            set_cli_args(None::<Vec<String>>);
+
+            let config = config.unwrap();
+            assert_eq!(config.storage.save_dir, "saves");
+            assert_eq!(config.storage.quota, 5);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod load_report_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{
+        JsonValue, LoadMeta, attributes::NoEnvOverride, config_file_plugin,
+        load_config_file_with_meta, load_config_report,
+    };
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct ReportedConfig {
+        #[reflect(@NoEnvOverride)]
+        save_dir: String,
+        volume: f32,
+    }
+
+    impl ConfigFile for ReportedConfig {
+        const PATH: &'static str = "reported.yaml";
+    }
+
+    #[test]
+    fn test_report_collects_warnings_overrides_and_files() {
+        with_test_dir(
+            &[("reported.yaml", "save_dir: saves\nvolume: 1.0\n")],
+            |_| {
+                // Adding the plugin registers the type's field attributes
+                App::new().add_plugins(config_file_plugin::<ReportedConfig>);
+                unsafe {
+                    std::env::set_var(
+                        "CONFIG_ReportedConfig",
+                        r#"{"save_dir": "/etc", "volume": 0.5}"#,
+                    )
+                };
+                let report = load_config_report::<ReportedConfig>();
+                unsafe { std::env::remove_var("CONFIG_ReportedConfig") };
+
+                let report = report.unwrap();
+                assert_eq!(report.value.save_dir, "saves");
+                assert_eq!(report.value.volume, 0.5);
+                assert_eq!(
+                    report.warnings,
+                    [
+                        "ignoring override of protected field ReportedConfig.save_dir from CONFIG_ReportedConfig"
+                    ]
+                );
+                assert_eq!(report.overridden_fields, ["volume"]);
+                assert_eq!(report.read_files, ["reported.yaml"]);
+            },
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct MetaAudio {
+        volume: f32,
+        muted: bool,
+        device: String,
+    }
+
+    impl ConfigFile for MetaAudio {
+        const PATH: &'static str = "meta_audio.yaml";
+    }
+
+    #[test]
+    fn test_meta_lists_the_override_keys() {
+        let yaml = "volume: 1.0\nmuted: false\ndevice: speakers\n";
+        with_test_dir(&[("meta_audio.yaml", yaml)], |_| {
+            let (_, meta) = load_config_file_with_meta::<MetaAudio>().unwrap();
+            assert_eq!(meta, LoadMeta::default());
+
+            let json = r#"{"muted": true, "device": "headphones"}"#;
+            unsafe { std::env::set_var("CONFIG_MetaAudio", json) };
+            let result = load_config_file_with_meta::<MetaAudio>();
+            unsafe { std::env::remove_var("CONFIG_MetaAudio") };
+
+            let (config, meta) = result.unwrap();
+            assert!(config.muted);
+            assert!(meta.env_override_applied);
+            let mut expected: Vec<String> = serde_json::from_str::<JsonValue>(json)
+                .unwrap()
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect();
+            let mut keys = meta.overridden_keys;
+            expected.sort();
+            keys.sort();
+            assert_eq!(keys, expected);
+        });
+    }
+
+    #[test]
+    fn test_report_is_empty_without_overrides() {
+        with_test_dir(
+            &[("reported.yaml", "save_dir: saves\nvolume: 1.0\n")],
+            |_| {
+                let report = load_config_report::<ReportedConfig>().unwrap();
+                assert_eq!(report.value.volume, 1.0);
+                assert!(report.warnings.is_empty());
+                assert!(report.overridden_fields.is_empty());
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod reference_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ZoomLimits {
+        min: f32,
+        max: f32,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct ReferencingConfig {
+        name: String,
+        backup_name: String,
+        default_zoom: f32,
+        limits: ZoomLimits,
+    }
+
+    impl ConfigFile for ReferencingConfig {
+        const PATH: &'static str = "referencing.yaml";
+        const RESOLVE_REFERENCES: bool = true;
+    }
+
+    fn load(yaml: &str, env: Option<&str>) -> Result<ReferencingConfig, LoadConfigError> {
+        let mut result = None;
+        with_test_dir(&[("referencing.yaml", yaml)], |_| {
+            if let Some(env) = env {
+                unsafe { std::env::set_var("CONFIG_ReferencingConfig", env) };
+            }
+            result = Some(load_config_file::<ReferencingConfig>());
+            unsafe { std::env::remove_var("CONFIG_ReferencingConfig") };
+        });
+        result.unwrap()
+    }
+
+    const YAML: &str = "name: save\n\
+        backup_name: \"${self.name}-backup\"\n\
+        default_zoom: \"${self.limits.min}\"\n\
+        limits:\n  min: 2.0\n  max: 8.0\n";
+
+    #[test]
+    fn test_field_referencing_another_field_resolves() {
+        let config = load(YAML, None).unwrap();
+        assert_eq!(config.default_zoom, 2.0);
+        assert_eq!(config.backup_name, "save-backup");
+    }
+
+    #[test]
+    fn test_references_resolve_after_overrides() {
+        let config = load(YAML, Some(r#"{"limits": {"min": 3.0, "max": 9.0}}"#)).unwrap();
+        assert_eq!(config.default_zoom, 3.0);
+    }
+
+    #[test]
+    fn test_cyclic_references_error() {
+        let yaml = "name: \"${self.backup_name}\"\nbackup_name: \"${self.name}\"\n\
+            default_zoom: 1.0\nlimits:\n  min: 2.0\n  max: 8.0\n";
+        assert!(matches!(
+            load(yaml, None),
+            Err(LoadConfigError::Reference(_))
+        ));
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod json_round_trip_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static SERIALIZE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Deserialize, PartialEq, Reflect)]
+    struct LargeConfig {
+        values: Vec<i32>,
+    }
+
+    /// Counts serializations, which only happen when converting to JSON for an override.
+    impl Serialize for LargeConfig {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeStruct;
+            SERIALIZE_CALLS.fetch_add(1, Ordering::SeqCst);
+            let mut state = serializer.serialize_struct("LargeConfig", 1)?;
+            state.serialize_field("values", &self.values)?;
+            state.end()
+        }
+    }
+
+    impl ConfigFile for LargeConfig {
+        const PATH: &'static str = "large.yaml";
+    }
+
+    fn large_yaml() -> String {
+        let values: Vec<String> = (0..10_000).map(|i| format!("- {i}")).collect();
+        format!("values:\n{}\n", values.join("\n"))
+    }
+
+    #[test]
+    fn test_no_json_conversion_without_override() {
+        with_test_dir(&[("large.yaml", &large_yaml())], |_| {
+            SERIALIZE_CALLS.store(0, Ordering::SeqCst);
+            let config = load_config_file::<LargeConfig>().unwrap();
+            assert_eq!(config.values.len(), 10_000);
+            assert_eq!(SERIALIZE_CALLS.load(Ordering::SeqCst), 0);
+        });
+    }
+
+    #[test]
+    fn test_json_conversion_with_override() {
+        with_test_dir(&[("large.yaml", &large_yaml())], |_| {
+            SERIALIZE_CALLS.store(0, Ordering::SeqCst);
+            unsafe { std::env::set_var("CONFIG_LargeConfig", r#"{"values": [1]}"#) };
+            let config = load_config_file::<LargeConfig>();
+            unsafe { std::env::remove_var("CONFIG_LargeConfig") };
+
+            assert_eq!(config.unwrap().values, vec![1]);
+            assert_eq!(SERIALIZE_CALLS.load(Ordering::SeqCst), 1);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod untagged_enum_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    #[serde(untagged)]
+    enum CameraRig {
+        Follow { target: String, distance: f32 },
+        Fixed { position: [f32; 3] },
+    }
+
+    impl ConfigFile for CameraRig {
+        const PATH: &'static str = "camera_rig.yaml";
+    }
+
+    fn load(yaml: &str, env: Option<&str>) -> CameraRig {
+        let mut result = None;
+        with_test_dir(&[("camera_rig.yaml", yaml)], |_| {
+            if let Some(env) = env {
+                unsafe { std::env::set_var("CONFIG_CameraRig", env) };
+            }
+            result = Some(load_config_file::<CameraRig>());
+            unsafe { std::env::remove_var("CONFIG_CameraRig") };
+        });
+        result.unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_follow_variant_without_override() {
+        assert_eq!(
+            load("target: player\ndistance: 5.0\n", None),
+            CameraRig::Follow {
+                target: "player".to_string(),
+                distance: 5.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fixed_variant_without_override() {
+        assert_eq!(
+            load("position: [1.0, 2.0, 3.0]\n", None),
+            CameraRig::Fixed {
+                position: [1.0, 2.0, 3.0],
+            }
+        );
+    }
+
+    #[test]
+    fn test_follow_variant_with_override() {
+        assert_eq!(
+            load(
+                "target: player\ndistance: 5.0\n",
+                Some(r#"{"distance": 8.0}"#)
+            ),
+            CameraRig::Follow {
+                target: "player".to_string(),
+                distance: 8.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fixed_variant_with_override() {
+        assert_eq!(
+            load(
+                "position: [1.0, 2.0, 3.0]\n",
+                Some(r#"{"position": [4.0, 5.0, 6.0]}"#)
+            ),
+            CameraRig::Fixed {
+                position: [4.0, 5.0, 6.0],
+            }
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod capture_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{
+        ConfigCapture, ConfigRegistry, LoadConfigError, config_file_plugin, load_config_file,
+    };
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct CapturedAudio {
+        volume: f32,
+        muted: bool,
+    }
+
+    impl ConfigFile for CapturedAudio {
+        const PATH: &'static str = "captured_audio.yaml";
+    }
+
+    #[test]
+    fn test_replaying_capture_ignores_current_environment() {
+        let mut serialized = String::new();
+        let mut original = None;
+        with_test_dir(
+            &[("captured_audio.yaml", "volume: 1.0\nmuted: false\n")],
+            |_| {
+                unsafe { std::env::set_var("CONFIG_CapturedAudio", r#"{"volume": 0.25}"#) };
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin::<CapturedAudio>);
+                app.update();
+                let capture =
+                    ConfigCapture::from_registry(app.world().resource::<ConfigRegistry>());
+                unsafe { std::env::remove_var("CONFIG_CapturedAudio") };
+                serialized = serde_json::to_string(&capture).unwrap();
+                original = app.world_mut().remove_resource::<CapturedAudio>();
+            },
+        );
+
+        // A different file and override are in effect when replaying
+        with_test_dir(
+            &[("captured_audio.yaml", "volume: 0.0\nmuted: true\n")],
+            |_| {
+                unsafe { std::env::set_var("CONFIG_CapturedAudio", r#"{"volume": 0.75}"#) };
+                let capture: ConfigCapture = serde_json::from_str(&serialized).unwrap();
+                let replayed = capture.replay::<CapturedAudio>();
+                let current = load_config_file::<CapturedAudio>();
+                unsafe { std::env::remove_var("CONFIG_CapturedAudio") };
+
+                assert_eq!(replayed.unwrap(), original.unwrap());
+                assert_eq!(current.unwrap().volume, 0.75);
+            },
+        );
+    }
+
+    #[test]
+    fn test_replaying_uncaptured_type_errors() {
+        assert!(ConfigCapture::default().replay::<CapturedAudio>().is_err());
+    }
+
+    #[derive(Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    struct CapturedServer {
+        host: String,
+        port: u16,
+        workers: u32,
+    }
+
+    impl ConfigFile for CapturedServer {
+        const PATH: &'static str = "captured_server.yaml";
+        const COMMON_PATH: Option<&'static str> = Some("captured_common.yaml");
+
+        fn validate_invariants(&self) -> Vec<String> {
+            if self.workers == 0 {
+                return vec!["workers must be positive".to_string()];
+            }
+            Vec::new()
+        }
+    }
+
+    const SERVER_VARS: [(&str, &str); 2] = [
+        ("CONFIG_CapturedServer__port", "9000"),
+        ("CONFIG_DEFAULT_CapturedServer", r#"{"workers": 4}"#),
+    ];
+
+    fn capture_server(files: &[(&str, &str)]) -> ConfigCapture {
+        let mut capture = ConfigCapture::default();
+        with_test_dir(files, |_| {
+            for (var, value) in SERVER_VARS {
+                unsafe { std::env::set_var(var, value) };
+            }
+            capture.capture::<CapturedServer>();
+            for (var, _) in SERVER_VARS {
+                unsafe { std::env::remove_var(var) };
+            }
+        });
+        capture
+    }
+
+    #[test]
+    fn test_replay_uses_every_captured_input() {
+        let capture = capture_server(&[
+            ("captured_common.yaml", "host: example.com\nport: 80\n"),
+            ("captured_server.yaml", "port: 8080\n"),
+        ]);
+
+        with_test_dir(&[], |_| {
+            let replayed = capture.replay::<CapturedServer>();
+            assert_eq!(
+                replayed.unwrap(),
+                CapturedServer {
+                    host: "example.com".to_string(),
+                    port: 9000,
+                    workers: 4,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_replay_checks_invariants() {
+        let capture = capture_server(&[
+            ("captured_common.yaml", "host: example.com\nport: 80\n"),
+            ("captured_server.yaml", "workers: 0\n"),
+        ]);
+
+        with_test_dir(&[], |_| {
+            let replayed = capture.replay::<CapturedServer>();
+            assert!(
+                matches!(replayed, Err(LoadConfigError::InvariantViolations(_))),
+                "{:?}",
+                replayed
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod typed_override_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file_with_override};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct TypedAudio {
+        volume: f32,
+        muted: bool,
+        device: String,
+    }
+
+    impl ConfigFile for TypedAudio {
+        const PATH: &'static str = "typed_audio.yaml";
+    }
+
+    #[derive(Serialize)]
+    struct TypedAudioOverride {
+        volume: f32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        device: Option<String>,
+    }
+
+    #[test]
+    fn test_typed_partial_override_merges_and_ignores_env_var() {
+        with_test_dir(
+            &[(
+                "typed_audio.yaml",
+                "volume: 1.0\nmuted: true\ndevice: speakers\n",
+            )],
+            |_| {
+                unsafe { std::env::set_var("CONFIG_TypedAudio", r#"{"device": "headphones"}"#) };
+                let result = load_config_file_with_override::<TypedAudio>(&TypedAudioOverride {
+                    volume: 0.5,
+                    device: None,
+                });
+                unsafe { std::env::remove_var("CONFIG_TypedAudio") };
+
+                assert_eq!(
+                    result.unwrap(),
+                    TypedAudio {
+                        volume: 0.5,
+                        muted: true,
+                        device: "speakers".to_string(),
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_typed_override_with_missing_file_reports_ignored_override() {
+        with_test_dir(&[], |_| {
+            let result = load_config_file_with_override::<TypedAudio>(&TypedAudioOverride {
+                volume: 0.5,
+                device: Some("headphones".to_string()),
+            });
+            assert!(matches!(
+                result,
+                Err(LoadConfigError::OverrideIgnored { .. })
+            ));
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod no_env_tests {
+    use super::*;
+    use bevy_config_file::{load_config_file, load_config_file_no_env, set_override};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct NoEnvAudio {
+        volume: f32,
+        muted: bool,
+    }
+
+    impl ConfigFile for NoEnvAudio {
+        const PATH: &'static str = "no_env_audio.yaml";
+    }
+
+    #[test]
+    fn test_env_var_override_is_ignored() {
+        with_test_dir(
+            &[("no_env_audio.yaml", "volume: 1.0\nmuted: false\n")],
+            |_| {
+                unsafe { std::env::set_var("CONFIG_NoEnvAudio", r#"{"volume": 0.5}"#) };
+                let overridden = load_config_file::<NoEnvAudio>();
+                let result = load_config_file_no_env::<NoEnvAudio>();
+                unsafe { std::env::remove_var("CONFIG_NoEnvAudio") };
+
+                assert_eq!(overridden.unwrap().volume, 0.5);
+                assert_eq!(
+                    result.unwrap(),
+                    NoEnvAudio {
+                        volume: 1.0,
+                        muted: false,
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_in_memory_override_is_ignored() {
+        with_test_dir(
+            &[("no_env_audio.yaml", "volume: 1.0\nmuted: false\n")],
+            |_| {
+                set_override::<NoEnvAudio>(r#"{"muted": true}"#);
+                let result = load_config_file_no_env::<NoEnvAudio>();
+                bevy_config_file::clear_override::<NoEnvAudio>();
+
+                assert!(!result.unwrap().muted);
+            },
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct NoEnvServer {
+        host: String,
+        port: u16,
+    }
+
+    impl ConfigFile for NoEnvServer {
+        const PATH: &'static str = "no_env_server.yaml";
+        const COMMON_PATH: Option<&'static str> = Some("no_env_common.yaml");
+    }
+
+    #[test]
+    fn test_common_file_is_merged_without_env_defaults() {
+        let files = [
+            ("no_env_common.yaml", "host: localhost\nport: 80\n"),
+            ("no_env_server.yaml", "port: 8080\n"),
+        ];
+        with_test_dir(&files, |_| {
+            unsafe { std::env::set_var("CONFIG_DEFAULT_NoEnvServer", r#"{"host": "env"}"#) };
+            let result = load_config_file_no_env::<NoEnvServer>();
+            unsafe { std::env::remove_var("CONFIG_DEFAULT_NoEnvServer") };
+
+            assert_eq!(
+                result.unwrap(),
+                NoEnvServer {
+                    host: "localhost".to_string(),
+                    port: 8080,
+                }
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod from_str_tests {
+    use super::*;
+    use bevy_config_file::load_config_from_str;
+
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct InlineAudio {
+        volume: f32,
+        muted: bool,
+        device: String,
+    }
+
+    impl ConfigFile for InlineAudio {
+        const PATH: &'static str = "missing/inline_audio.yaml";
+    }
+
+    #[test]
+    fn test_loads_yaml_text_without_a_file() {
+        let _lock = TEST_MUTEX.lock().unwrap();
+        let config =
+            load_config_from_str::<InlineAudio>("volume: 1.0\nmuted: false\ndevice: speakers\n")
+                .unwrap();
+        assert_eq!(
+            config,
+            InlineAudio {
+                volume: 1.0,
+                muted: false,
+                device: "speakers".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_applies_env_overrides_to_yaml_text() {
+        let _lock = TEST_MUTEX.lock().unwrap();
+        unsafe { std::env::set_var("CONFIG_InlineAudio", r#"{"muted": true}"#) };
+        let result =
+            load_config_from_str::<InlineAudio>("volume: 1.0\nmuted: false\ndevice: speakers\n");
+        unsafe { std::env::remove_var("CONFIG_InlineAudio") };
+
+        let config = result.unwrap();
+        assert!(config.muted);
+        assert_eq!(config.volume, 1.0);
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod array_merge_key_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Ability {
+        name: String,
+        cooldown: f32,
+        #[serde(default)]
+        damage: u32,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct AbilitySettings {
+        abilities: Vec<Ability>,
+        tags: Vec<String>,
+    }
+
+    impl ConfigFile for AbilitySettings {
+        const PATH: &'static str = "ability_settings.yaml";
+        const ARRAY_MERGE_KEY: Option<&'static str> = Some("name");
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct ReplacedAbilitySettings {
+        abilities: Vec<Ability>,
+    }
+
+    impl ConfigFile for ReplacedAbilitySettings {
+        const PATH: &'static str = "ability_settings.yaml";
+    }
+
+    const ABILITIES: &str = "abilities:\n  - name: dash\n    cooldown: 2.0\n    damage: 0\n  - name: slash\n    cooldown: 1.0\n    damage: 10\ntags: [melee]\n";
+
+    #[test]
+    fn test_ability_lists_merge_by_name() {
+        with_test_dir(&[("ability_settings.yaml", ABILITIES)], |_| {
+            unsafe {
+                std::env::set_var(
+                    "CONFIG_AbilitySettings",
+                    r#"{"abilities": [{"name": "slash", "damage": 25}, {"name": "blink", "cooldown": 5.0}], "tags": ["ranged"]}"#,
+                )
+            };
+            let result = load_config_file::<AbilitySettings>();
+            unsafe { std::env::remove_var("CONFIG_AbilitySettings") };
+
+            let config = result.unwrap();
+            assert_eq!(
+                config.abilities,
+                vec![
+                    Ability {
+                        name: "dash".to_string(),
+                        cooldown: 2.0,
+                        damage: 0
+                    },
+                    Ability {
+                        name: "slash".to_string(),
+                        cooldown: 1.0,
+                        damage: 25
+                    },
+                    Ability {
+                        name: "blink".to_string(),
+                        cooldown: 5.0,
+                        damage: 0
+                    },
+                ]
+            );
+            // Arrays of scalars have no key field, so entries are appended
+            assert_eq!(config.tags, vec!["melee", "ranged"]);
+        });
+    }
+
+    #[test]
+    fn test_arrays_are_replaced_without_merge_key() {
+        with_test_dir(&[("ability_settings.yaml", ABILITIES)], |_| {
+            unsafe {
+                std::env::set_var(
+                    "CONFIG_ReplacedAbilitySettings",
+                    r#"{"abilities": [{"name": "blink", "cooldown": 5.0}]}"#,
+                )
+            };
+            let result = load_config_file::<ReplacedAbilitySettings>();
+            unsafe { std::env::remove_var("CONFIG_ReplacedAbilitySettings") };
+
+            let abilities = result.unwrap().abilities;
+            assert_eq!(abilities.len(), 1);
+            assert_eq!(abilities[0].name, "blink");
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod extends_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Graphics {
+        width: u32,
+        height: u32,
+        vsync: bool,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct DeploySettings {
+        name: String,
+        graphics: Graphics,
+        debug: bool,
+    }
+
+    impl ConfigFile for DeploySettings {
+        const PATH: &'static str = "config/staging.yaml";
+        const EXTENDS: bool = true;
+    }
+
+    #[test]
+    fn test_two_level_inheritance_chain_merges_child_on_top() {
+        with_test_dir(
+            &[
+                (
+                    "config/base.yaml",
+                    "name: base\ngraphics:\n  width: 1280\n  height: 720\n  vsync: true\ndebug: false\n",
+                ),
+                (
+                    "config/shared/dev.yaml",
+                    "extends: ../base.yaml\nname: dev\ngraphics:\n  width: 1920\ndebug: true\n",
+                ),
+                (
+                    "config/staging.yaml",
+                    "extends: shared/dev.yaml\nname: staging\ngraphics:\n  vsync: false\n",
+                ),
+            ],
+            |_| {
+                let config = load_config_file::<DeploySettings>().unwrap();
+                assert_eq!(config.name, "staging");
+                assert!(config.debug);
+                assert_eq!(
+                    config.graphics,
+                    Graphics {
+                        width: 1920,
+                        height: 720,
+                        vsync: false,
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_inheritance_cycle_errors() {
+        with_test_dir(
+            &[
+                ("config/staging.yaml", "extends: dev.yaml\nname: staging\n"),
+                ("config/dev.yaml", "extends: ./staging.yaml\nname: dev\n"),
+            ],
+            |_| {
+                let result = load_config_file::<DeploySettings>();
+                match result {
+                    Err(LoadConfigError::Extends(message)) => {
+                        assert!(message.contains("cycle"), "{}", message)
+                    }
+                    other => panic!("expected a cycle error, got {:?}", other),
+                }
+            },
+        );
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "rayon"))]
+mod parallel_tests {
+    use super::*;
+    use bevy_config_file::load_configs_parallel;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct ItemConfig {
+        name: String,
+        price: u32,
+    }
+
+    impl ConfigFile for ItemConfig {
+        const PATH: &'static str = "items/default.yaml";
+    }
+
+    #[test]
+    fn test_parallel_load_preserves_input_order() {
+        let files: Vec<(String, String)> = (0..16)
+            .map(|i| {
+                (
+                    format!("items/item_{}.yaml", i),
+                    format!("name: item {}\nprice: {}\n", i, i * 10),
+                )
+            })
+            .collect();
+        let files: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(path, content)| (path.as_str(), content.as_str()))
+            .collect();
+
+        with_test_dir(&files, |_| {
+            let mut paths: Vec<&str> = files.iter().map(|(path, _)| *path).rev().collect();
+            paths.insert(3, "items/missing.yaml");
+
+            let results = load_configs_parallel::<ItemConfig>(&paths);
+            assert_eq!(results.len(), 17);
+            assert!(results[3].is_err());
+
+            let loaded: Vec<ItemConfig> = results.into_iter().filter_map(Result::ok).collect();
+            let expected: Vec<ItemConfig> = (0..16)
+                .rev()
+                .map(|i| ItemConfig {
+                    name: format!("item {}", i),
+                    price: i * 10,
+                })
+                .collect();
+            assert_eq!(loaded, expected);
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "logging"))]
+mod secret_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::attributes::Secret;
+    use bevy_config_file::{
+        JsonValue, config_as_override_string, config_file_plugin, load_config_file,
+        set_override,
+    };
+
+    const API_KEY: &str = "hunter2-api-key";
+
+    static OVERRIDDEN: Mutex<Vec<(String, JsonValue, JsonValue)>> = Mutex::new(Vec::new());
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct ServiceSettings {
+        endpoint: String,
+        #[reflect(@Secret)]
+        api_key: String,
+        database: ServiceDatabase,
+    }
+
+    #[derive(Reflect, Debug, Serialize, Deserialize)]
+    struct ServiceDatabase {
+        host: String,
+        #[reflect(@Secret)]
+        password: String,
+    }
+
+    impl ConfigFile for ServiceSettings {
+        const PATH: &'static str = "service_settings.yaml";
+
+        fn on_override(field: &str, old: &JsonValue, new: &JsonValue) {
+            OVERRIDDEN
+                .lock()
+                .unwrap()
+                .push((field.to_string(), old.clone(), new.clone()));
+        }
+    }
+
+    #[test]
+    fn test_secret_field_never_appears_in_logs_across_load_and_reload() {
+        let logs = CapturedLogs::default();
+        let _guard = logs.set_default();
+
+        with_test_dir(
+            &[(
+                "service_settings.yaml",
+                &format!(
+                    "endpoint: https://example.com\napi_key: {0}\ndatabase:\n  host: db\n  password: {0}-db\n",
+                    API_KEY
+                ),
+            )],
+            |_| {
+                unsafe {
+                    std::env::set_var(
+                        "CONFIG_ServiceSettings",
+                        format!(
+                            r#"{{"api_key": "{0}-env", "database": {{"password": "{0}-db-env"}}}}"#,
+                            API_KEY
+                        ),
+                    )
+                };
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin::<ServiceSettings>);
+                app.update();
+                unsafe { std::env::remove_var("CONFIG_ServiceSettings") };
+
+                set_override::<ServiceSettings>(&format!(
+                    r#"{{"endpoint": "https://other.example.com", "api_key": "{}-memory"}}"#,
+                    API_KEY
+                ));
+                app.update();
+                bevy_config_file::clear_override::<ServiceSettings>();
+
+                let settings = app.world().resource::<ServiceSettings>();
+                assert_eq!(settings.api_key, format!("{}-memory", API_KEY));
+                assert_eq!(settings.endpoint, "https://other.example.com");
+                assert_eq!(settings.database.password, format!("{}-db", API_KEY));
+            },
+        );
+
+        let logs = logs.lines();
+        assert!(logs.iter().any(|line| line.contains("reloaded config")));
+        assert!(logs.iter().any(|line| line.contains(r#""api_key":"***""#)));
+        assert!(logs.iter().any(|line| line.contains(r#""password":"***""#)));
+        assert!(
+            logs.iter().all(|line| !line.contains(API_KEY)),
+            "{:?}",
+            logs
+        );
+
+        let overridden = OVERRIDDEN.lock().unwrap();
+        let secret = JsonValue::String("***".to_string());
+        for (field, old, new) in overridden.iter().filter(|(field, ..)| field == "api_key") {
+            assert_eq!((old, new), (&secret, &secret), "{}", field);
+        }
+        for (field, old, new) in overridden.iter() {
+            let shown = format!("{} {}", old, new);
+            assert!(!shown.contains(API_KEY), "{}: {}", field, shown);
+        }
+        assert!(overridden.iter().any(|(field, ..)| field.starts_with("database")));
+        assert!(overridden.iter().any(|(field, ..)| field == "endpoint"));
+    }
+
+    static UNPLUGGED_OVERRIDDEN: Mutex<Vec<(String, JsonValue, JsonValue)>> =
+        Mutex::new(Vec::new());
+
+    // Never added with a plugin, so the loader alone must mark the secret
+    #[derive(Reflect, Debug, Serialize, Deserialize)]
+    struct UnpluggedServiceSettings {
+        endpoint: String,
+        #[reflect(@Secret)]
+        api_key: String,
+    }
+
+    impl ConfigFile for UnpluggedServiceSettings {
+        const PATH: &'static str = "unplugged_service_settings.yaml";
+
+        fn on_override(field: &str, old: &JsonValue, new: &JsonValue) {
+            UNPLUGGED_OVERRIDDEN.lock().unwrap().push((
+                field.to_string(),
+                old.clone(),
+                new.clone(),
+            ));
+        }
+    }
+
+    #[test]
+    fn test_secret_of_unregistered_type_is_redacted() {
+        with_test_dir(
+            &[(
+                "unplugged_service_settings.yaml",
+                &format!("endpoint: https://example.com\napi_key: {}\n", API_KEY),
+            )],
+            |_| {
+                let json = format!(r#"{{"api_key": "{}-env"}}"#, API_KEY);
+                unsafe { std::env::set_var("CONFIG_UnpluggedServiceSettings", json) };
+                let settings = load_config_file::<UnpluggedServiceSettings>();
+                unsafe { std::env::remove_var("CONFIG_UnpluggedServiceSettings") };
+                let mut settings = settings.unwrap();
+                assert_eq!(settings.api_key, format!("{}-env", API_KEY));
+
+                settings.api_key = format!("{}-changed", API_KEY);
+                let override_string = config_as_override_string(&settings).unwrap();
+                assert!(!override_string.contains(API_KEY), "{}", override_string);
+            },
+        );
+
+        let secret = JsonValue::String("***".to_string());
+        assert_eq!(
+            *UNPLUGGED_OVERRIDDEN.lock().unwrap(),
+            [("api_key".to_string(), secret.clone(), secret)]
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod layer_defaults_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{clear_override, config_file_plugin, load_config_file, set_override};
+
+    #[derive(Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    struct Window {
+        width: u32,
+        height: u32,
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct DefaultedSettings {
+        volume: f32,
+        fullscreen: bool,
+        window: Window,
+    }
+
+    impl Default for DefaultedSettings {
+        fn default() -> Self {
+            Self {
+                volume: 1.0,
+                fullscreen: true,
+                window: Window {
+                    width: 1280,
+                    height: 720,
+                },
+            }
+        }
+    }
+
+    impl ConfigFile for DefaultedSettings {
+        const PATH: &'static str = "defaulted_settings.yaml";
+        const LAYER_DEFAULTS: Option<fn() -> Self> = Some(Self::default);
+    }
+
+    #[test]
+    fn test_absent_fields_take_defaults_and_present_fields_override() {
+        with_test_dir(
+            &[(
+                "defaulted_settings.yaml",
+                "volume: 0.5\nwindow:\n  width: 1920\n",
+            )],
+            |_| {
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin::<DefaultedSettings>);
+                app.update();
+
+                let settings = app.world().resource::<DefaultedSettings>();
+                assert_eq!(settings.volume, 0.5);
+                assert!(settings.fullscreen);
+                assert_eq!(
+                    settings.window,
+                    Window {
+                        width: 1920,
+                        height: 720,
+                    }
+                );
+
+                // Reloads keep the default layer beneath the file
+                set_override::<DefaultedSettings>(r#"{"fullscreen": false}"#);
+                app.update();
+                clear_override::<DefaultedSettings>();
+
+                let settings = app.world().resource::<DefaultedSettings>();
+                assert!(!settings.fullscreen);
+                assert_eq!(settings.volume, 0.5);
+                assert_eq!(settings.window.height, 720);
+            },
+        );
+    }
+
+    #[test]
+    fn test_direct_load_uses_default_layer() {
+        with_test_dir(&[("defaulted_settings.yaml", "volume: 0.5\n")], |_| {
+            let settings = load_config_file::<DefaultedSettings>().unwrap();
+            assert_eq!(settings.volume, 0.5);
+            assert!(settings.fullscreen);
+            assert_eq!(settings.window.width, 1280);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod resolve_path_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+    use std::sync::Mutex;
+
+    static CACHE_DIR: Mutex<Option<std::path::PathBuf>> = Mutex::new(None);
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct CdnSettings {
+        region: String,
+    }
+
+    impl ConfigFile for CdnSettings {
+        const PATH: &'static str = "assets/config/cdn.yaml";
+
+        fn resolve_path(default: &str) -> String {
+            let cache_dir = CACHE_DIR.lock().unwrap().clone().unwrap();
+            let relative = default.strip_prefix("assets/").unwrap();
+            cache_dir.join(relative).to_string_lossy().into_owned()
+        }
+    }
+
+    #[test]
+    fn test_rewritten_path_is_read() {
+        let cache = TempDir::new().unwrap();
+        fs::create_dir_all(cache.path().join("config")).unwrap();
+        fs::write(cache.path().join("config/cdn.yaml"), "region: eu-west\n").unwrap();
+        *CACHE_DIR.lock().unwrap() = Some(cache.path().to_path_buf());
+
+        with_test_dir(&[("assets/config/cdn.yaml", "region: default\n")], |_| {
+            let config = load_config_file::<CdnSettings>().unwrap();
+            assert_eq!(config.region, "eu-west");
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod includes_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct LevelSettings {
+        name: String,
+        gravity: f32,
+        music: String,
+        enemies: u32,
+    }
+
+    impl ConfigFile for LevelSettings {
+        const PATH: &'static str = "levels/forest.yaml";
+        const INCLUDES: bool = true;
+    }
+
+    #[test]
+    fn test_include_in_subdirectory_resolves_relative_to_including_file() {
+        with_test_dir(
+            &[
+                (
+                    "levels/forest.yaml",
+                    "includes: [common/base.yaml, common/audio.yaml]\nname: forest\nenemies: 12\n",
+                ),
+                (
+                    "levels/common/base.yaml",
+                    "includes: [physics.yaml]\nname: base\nenemies: 0\n",
+                ),
+                ("levels/common/physics.yaml", "gravity: 9.8\n"),
+                ("levels/common/audio.yaml", "music: calm.ogg\n"),
+                // Decoys at the paths a CWD-relative lookup would find
+                ("common/base.yaml", "name: wrong\ngravity: 0.0\n"),
+                ("physics.yaml", "gravity: 0.0\n"),
+            ],
+            |_| {
+                let config = load_config_file::<LevelSettings>().unwrap();
+                assert_eq!(config.name, "forest");
+                assert_eq!(config.gravity, 9.8);
+                assert_eq!(config.music, "calm.ogg");
+                assert_eq!(config.enemies, 12);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod config_meta_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{
+        ConfigMeta, attributes::NoEnvOverride, clear_override, config_file_plugin, set_override,
+    };
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct MetaSettings {
+        level: u32,
+    }
+
+    impl ConfigFile for MetaSettings {
+        const PATH: &'static str = "meta_settings.yaml";
+    }
+
+    #[test]
+    fn test_meta_records_file_mtime_and_updates_on_reload() {
+        with_test_dir(&[("meta_settings.yaml", "level: 1\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<MetaSettings>);
+            app.update();
+
+            let mtime = fs::metadata("meta_settings.yaml")
+                .unwrap()
+                .modified()
+                .unwrap();
+            let meta = app.world().resource::<ConfigMeta<MetaSettings>>();
+            assert_eq!(meta.path, "meta_settings.yaml");
+            assert_eq!(meta.file_mtime, Some(mtime));
+            assert!(meta.loaded_at >= mtime);
+            let first_load = meta.loaded_at;
+
+            set_override::<MetaSettings>(r#"{"level": 2}"#);
+            app.update();
+            clear_override::<MetaSettings>();
+
+            let meta = app.world().resource::<ConfigMeta<MetaSettings>>();
+            assert!(meta.loaded_at >= first_load);
+            assert_eq!(meta.file_mtime, Some(mtime));
+        });
+    }
+    #[test]
+    fn test_meta_reports_path_and_whether_overridden() {
+        with_test_dir(&[("meta_settings.yaml", "level: 1\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<MetaSettings>);
+            app.update();
+
+            let meta = app.world().resource::<ConfigMeta<MetaSettings>>();
+            assert_eq!(meta.path, "meta_settings.yaml");
+            assert!(!meta.overridden);
+
+            set_override::<MetaSettings>(r#"{"level": 2}"#);
+            app.update();
+            let overridden = app
+                .world()
+                .resource::<ConfigMeta<MetaSettings>>()
+                .overridden;
+            clear_override::<MetaSettings>();
+            assert!(overridden);
+        });
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct ProtectedMetaSettings {
+        #[reflect(@NoEnvOverride)]
+        save_dir: String,
+    }
+
+    impl ConfigFile for ProtectedMetaSettings {
+        const PATH: &'static str = "protected_meta_settings.yaml";
+    }
+
+    #[test]
+    fn test_ignored_override_does_not_count_as_overridden() {
+        with_test_dir(
+            &[("protected_meta_settings.yaml", "save_dir: saves\n")],
+            |_| {
+                set_override::<ProtectedMetaSettings>(r#"{"save_dir": "/etc"}"#);
+                let mut app = App::new();
+                app.add_plugins(config_file_plugin::<ProtectedMetaSettings>);
+                app.update();
+                clear_override::<ProtectedMetaSettings>();
+
+                let meta = app.world().resource::<ConfigMeta<ProtectedMetaSettings>>();
+                assert!(!meta.overridden);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod clear_override_field_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, clear_override_field, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Mixer {
+        music: f32,
+        effects: f32,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct MixerSettings {
+        muted: bool,
+        mixer: Mixer,
+    }
+
+    impl ConfigFile for MixerSettings {
+        const PATH: &'static str = "mixer_settings.yaml";
+    }
+
+    #[test]
+    fn test_clearing_overridden_field_restores_file_value() {
+        with_test_dir(
+            &[(
+                "mixer_settings.yaml",
+                "muted: false\nmixer:\n  music: 0.8\n  effects: 0.6\n",
+            )],
+            |_| {
+                unsafe {
+                    std::env::set_var(
+                        "CONFIG_MixerSettings",
+                        r#"{"muted": true, "mixer": {"music": 0.1, "effects": 0.2}}"#,
+                    )
+                };
+                let mut settings = load_config_file::<MixerSettings>().unwrap();
+                let top_level = clear_override_field(&mut settings, "muted");
+                let nested = clear_override_field(&mut settings, "mixer.music");
+                let missing = clear_override_field(&mut settings, "mixer.voice");
+                unsafe { std::env::remove_var("CONFIG_MixerSettings") };
+
+                top_level.unwrap();
+                nested.unwrap();
+                assert!(!settings.muted);
+                assert_eq!(
+                    settings.mixer,
+                    Mixer {
+                        music: 0.8,
+                        effects: 0.2,
+                    }
+                );
+                assert!(matches!(missing, Err(LoadConfigError::FieldNotFound(_))));
+            },
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct BindingSettings {
+        bindings: std::collections::HashMap<String, String>,
+    }
+
+    impl ConfigFile for BindingSettings {
+        const PATH: &'static str = "binding_settings.yaml";
+    }
+
+    #[test]
+    fn test_clearing_field_whose_key_contains_slash_or_tilde() {
+        let files = [(
+            "binding_settings.yaml",
+            "bindings:\n  ui/zoom: Z\n  ~menu: Escape\n",
+        )];
+        with_test_dir(&files, |_| {
+            let mut settings = load_config_file::<BindingSettings>().unwrap();
+            settings.bindings.insert("ui/zoom".into(), "X".into());
+            settings.bindings.insert("~menu".into(), "M".into());
+            clear_override_field(&mut settings, "bindings.ui/zoom").unwrap();
+            clear_override_field(&mut settings, "bindings.~menu").unwrap();
+            assert_eq!(settings.bindings["ui/zoom"], "Z");
+            assert_eq!(settings.bindings["~menu"], "Escape");
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "logging"))]
+mod path_check_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::config_file_plugin;
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct UnresolvedSettings {
+        value: u32,
+    }
+
+    impl ConfigFile for UnresolvedSettings {
+        const PATH: &'static str = "settings.yaml";
+
+        fn resolve_path(_default: &str) -> String {
+            String::new()
+        }
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct ExtensionOnlySettings {
+        value: u32,
+    }
+
+    impl ConfigFile for ExtensionOnlySettings {
+        const PATH: &'static str = "assets/.yaml";
+    }
+
+    #[test]
+    fn test_unusable_paths_warn_at_registration() {
+        let logs = CapturedLogs::default();
+        let _guard = logs.set_default();
+
+        let mut app = App::new();
+        app.add_plugins(config_file_plugin::<UnresolvedSettings>);
+        app.add_plugins(config_file_plugin::<ExtensionOnlySettings>);
+
+        let warnings: Vec<String> = logs
+            .lines()
+            .into_iter()
+            .filter(|line| line.contains("suspicious config file path"))
+            .collect();
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(warnings[0].contains("UnresolvedSettings") && warnings[0].contains("empty"));
+        assert!(warnings[1].contains("ExtensionOnlySettings") && warnings[1].contains("extension"));
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod root_key_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    const SHARED: &str = "camera:\n  pan_speed: 2000.0\n  zoom: 1.5\naudio:\n  volume: 0.8\n";
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct RootedCamera {
+        pan_speed: f32,
+        zoom: f32,
+    }
+
+    impl ConfigFile for RootedCamera {
+        const PATH: &'static str = "shared.yaml";
+        const ROOT_KEY: Option<&'static str> = Some("camera");
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct RootedInput {
+        sensitivity: f32,
+    }
+
+    impl ConfigFile for RootedInput {
+        const PATH: &'static str = "shared.yaml";
+        const ROOT_KEY: Option<&'static str> = Some("input");
+    }
+
+    #[test]
+    fn test_config_loaded_from_under_root_key_with_override() {
+        with_test_dir(&[("shared.yaml", SHARED)], |_| {
+            unsafe { std::env::set_var("CONFIG_RootedCamera", r#"{"zoom": 3.0}"#) };
+            let result = load_config_file::<RootedCamera>();
+            unsafe { std::env::remove_var("CONFIG_RootedCamera") };
+
+            let camera = result.unwrap();
+            assert_eq!(camera.pan_speed, 2000.0);
+            assert_eq!(camera.zoom, 3.0);
+        });
+    }
+
+    #[test]
+    fn test_missing_root_key_errors() {
+        with_test_dir(&[("shared.yaml", SHARED)], |_| {
+            let result = load_config_file::<RootedInput>();
+            assert!(matches!(
+                result,
+                Err(LoadConfigError::RootKeyMissing { ref key, .. }) if key == "input"
+            ));
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod override_operator_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct ScaledCamera {
+        pan_speed: f32,
+        zoom: f64,
+        lives: i32,
+        name: String,
+    }
+
+    impl ConfigFile for ScaledCamera {
+        const PATH: &'static str = "scaled_camera.yaml";
+    }
+
+    const CAMERA: &str = "pan_speed: 1500.0\nzoom: 1.25\nlives: 3\nname: main\n";
+
+    fn load_with(json: &str) -> Result<ScaledCamera, LoadConfigError> {
+        unsafe { std::env::set_var("CONFIG_ScaledCamera", json) };
+        let result = load_config_file::<ScaledCamera>();
+        unsafe { std::env::remove_var("CONFIG_ScaledCamera") };
+        result
+    }
+
+    #[test]
+    fn test_mul_and_add_adjust_file_values() {
+        with_test_dir(&[("scaled_camera.yaml", CAMERA)], |_| {
+            let camera = load_with(
+                r#"{"pan_speed": {"$mul": 2}, "zoom": {"$add": 0.5}, "lives": {"$add": -1}}"#,
+            )
+            .unwrap();
+            assert_eq!(camera.pan_speed, 3000.0);
+            assert_eq!(camera.zoom, 1.75);
+            assert_eq!(camera.lives, 2);
+        });
+    }
+
+    #[test]
+    fn test_operator_on_non_numeric_field_errors() {
+        with_test_dir(&[("scaled_camera.yaml", CAMERA)], |_| {
+            let result = load_with(r#"{"name": {"$mul": 2}}"#);
+            assert!(matches!(result, Err(LoadConfigError::InvalidOperator(_))));
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "logging", debug_assertions))]
+mod mutation_check_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::attributes::Secret;
+    use bevy_config_file::{
+        clear_override, config_file_plugin, config_mutation_check_plugin, set_override,
+    };
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct GuardedSettings {
+        speed: f32,
+    }
+
+    impl ConfigFile for GuardedSettings {
+        const PATH: &'static str = "guarded_settings.yaml";
+    }
+
+    fn mutation_warnings(logs: &CapturedLogs) -> usize {
+        logs.lines()
+            .iter()
+            .filter(|line| line.contains("mutated outside the config reload API"))
+            .count()
+    }
+
+    #[test]
+    fn test_unexpected_mutation_warns_but_reload_does_not() {
+        let logs = CapturedLogs::default();
+        let _guard = logs.set_default();
+
+        with_test_dir(&[("guarded_settings.yaml", "speed: 1.0\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<GuardedSettings>);
+            app.add_plugins(config_mutation_check_plugin::<GuardedSettings>);
+            app.update();
+            app.update();
+
+            // Setting and clearing an override each reload the resource
+            set_override::<GuardedSettings>(r#"{"speed": 2.0}"#);
+            app.update();
+            clear_override::<GuardedSettings>();
+            app.update();
+            assert_eq!(mutation_warnings(&logs), 0);
+
+            app.world_mut().resource_mut::<GuardedSettings>().speed = 99.0;
+            app.update();
+            assert_eq!(mutation_warnings(&logs), 1);
+        });
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct GuardedCredentials {
+        #[reflect(@Secret)]
+        token: String,
+    }
+
+    impl ConfigFile for GuardedCredentials {
+        const PATH: &'static str = "guarded_credentials.yaml";
+    }
+
+    #[test]
+    fn test_mutation_of_secret_field_warns() {
+        let logs = CapturedLogs::default();
+        let _guard = logs.set_default();
+
+        with_test_dir(&[("guarded_credentials.yaml", "token: abc\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<GuardedCredentials>);
+            app.add_plugins(config_mutation_check_plugin::<GuardedCredentials>);
+            app.update();
+            app.update();
+
+            app.world_mut().resource_mut::<GuardedCredentials>().token = "xyz".to_string();
+            app.update();
+            assert_eq!(mutation_warnings(&logs), 1);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod locale_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct GameStrings {
+        greeting: String,
+    }
+
+    impl ConfigFile for GameStrings {
+        const PATH: &'static str = "strings/strings.{locale}.yaml";
+        const LOCALE_VAR: Option<&'static str> = Some("TEST_GAME_LOCALE");
+    }
+
+    fn load_for_locale(locale: Option<&str>) -> String {
+        match locale {
+            Some(locale) => unsafe { std::env::set_var("TEST_GAME_LOCALE", locale) },
+            None => unsafe { std::env::remove_var("TEST_GAME_LOCALE") },
+        }
+        let result = load_config_file::<GameStrings>();
+        unsafe { std::env::remove_var("TEST_GAME_LOCALE") };
+        result.unwrap().greeting
+    }
+
+    #[test]
+    fn test_locale_file_selected_with_default_fallback() {
+        with_test_dir(
+            &[
+                ("strings/strings.en.yaml", "greeting: Hello\n"),
+                ("strings/strings.fr.yaml", "greeting: Bonjour\n"),
+            ],
+            |_| {
+                assert_eq!(load_for_locale(Some("fr")), "Bonjour");
+                assert_eq!(load_for_locale(Some("de")), "Hello");
+                assert_eq!(load_for_locale(None), "Hello");
+            },
+        );
+    }
+
+    #[cfg(feature = "logging")]
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct MenuStrings {
+        title: String,
+    }
+
+    #[cfg(feature = "logging")]
+    impl ConfigFile for MenuStrings {
+        const PATH: &'static str = "strings/menu.{locale}.yaml";
+        const LOCALE_VAR: Option<&'static str> = Some("TEST_MENU_LOCALE");
+    }
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn test_missing_locale_warned_once_per_load() {
+        use bevy_config_file::config_file_plugin;
+
+        let logs = CapturedLogs::default();
+        let _guard = logs.set_default();
+
+        with_test_dir(&[("strings/menu.en.yaml", "title: Menu\n")], |_| {
+            unsafe { std::env::set_var("TEST_MENU_LOCALE", "de") };
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<MenuStrings>);
+            app.update();
+            unsafe { std::env::remove_var("TEST_MENU_LOCALE") };
+
+            assert_eq!(app.world().resource::<MenuStrings>().title, "Menu");
+            let warnings = logs
+                .lines()
+                .into_iter()
+                .filter(|line| line.contains("no config for locale de"))
+                .count();
+            assert_eq!(warnings, 1);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod common_path_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct ServerSettings {
+        log_level: String,
+        port: u16,
+    }
+
+    impl ConfigFile for ServerSettings {
+        const PATH: &'static str = "config/server.yaml";
+        const COMMON_PATH: Option<&'static str> = Some("config/common.yaml");
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct WorkerSettings {
+        log_level: String,
+        threads: u32,
+    }
+
+    impl ConfigFile for WorkerSettings {
+        const PATH: &'static str = "config/worker.yaml";
+        const COMMON_PATH: Option<&'static str> = Some("config/common.yaml");
+    }
+
+    #[test]
+    fn test_common_file_fills_fields_beneath_own_file() {
+        let files = [
+            ("config/common.yaml", "log_level: info\n"),
+            ("config/server.yaml", "port: 8080\n"),
+            ("config/worker.yaml", "log_level: debug\nthreads: 4\n"),
+        ];
+        with_test_dir(&files, |_| {
+            let server = load_config_file::<ServerSettings>().unwrap();
+            assert_eq!(server.log_level, "info");
+            assert_eq!(server.port, 8080);
+
+            let worker = load_config_file::<WorkerSettings>().unwrap();
+            assert_eq!(worker.log_level, "debug");
+            assert_eq!(worker.threads, 4);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod variant_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct RolloutSettings {
+        server: String,
+    }
+
+    impl ConfigFile for RolloutSettings {
+        const PATH: &'static str = "config/settings.yaml";
+        const VARIANT_VAR: Option<&'static str> = Some("TEST_CONFIG_VARIANT");
+    }
+
+    fn load_for_variant(variant: Option<&str>) -> String {
+        match variant {
+            Some(variant) => unsafe { std::env::set_var("TEST_CONFIG_VARIANT", variant) },
+            None => unsafe { std::env::remove_var("TEST_CONFIG_VARIANT") },
+        }
+        let result = load_config_file::<RolloutSettings>();
+        unsafe { std::env::remove_var("TEST_CONFIG_VARIANT") };
+        result.unwrap().server
+    }
+
+    #[test]
+    fn test_variant_file_read_when_it_exists() {
+        with_test_dir(
+            &[
+                ("config/settings.yaml", "server: prod\n"),
+                ("config/settings.beta.yaml", "server: beta\n"),
+            ],
+            |_| {
+                assert_eq!(load_for_variant(Some("beta")), "beta");
+                assert_eq!(load_for_variant(None), "prod");
+            },
+        );
+    }
+
+    #[test]
+    fn test_missing_variant_file_falls_back_to_path() {
+        with_test_dir(&[("config/settings.yaml", "server: prod\n")], |_| {
+            assert_eq!(load_for_variant(Some("beta")), "prod");
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "logging"))]
+mod reload_error_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{clear_override, config_file_plugin, set_override};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct FlakySettings {
+        rate: u32,
+    }
+
+    impl ConfigFile for FlakySettings {
+        const PATH: &'static str = "flaky_settings.yaml";
+    }
+
+    fn reload_with(app: &mut App, json: &str) {
+        set_override::<FlakySettings>(json);
+        app.update();
+    }
+
+    fn reload_errors(logs: &CapturedLogs) -> Vec<String> {
+        logs.lines()
+            .into_iter()
+            .filter(|line| line.contains("failed to load FlakySettings"))
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_reload_errors_logged_once_until_they_change() {
+        let logs = CapturedLogs::default();
+        let _guard = logs.set_default();
+
+        with_test_dir(&[("flaky_settings.yaml", "rate: 1\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_plugin::<FlakySettings>);
+            app.update();
+
+            for _ in 0..3 {
+                reload_with(&mut app, "{not json");
+            }
+            assert_eq!(reload_errors(&logs).len(), 1);
+            assert_eq!(app.world().resource::<FlakySettings>().rate, 1);
+
+            // A different error is logged, as is the first error again after a success
+            reload_with(&mut app, r#"{"rate": "fast"}"#);
+            reload_with(&mut app, r#"{"rate": 2}"#);
+            reload_with(&mut app, "{not json");
+            clear_override::<FlakySettings>();
+            app.update();
+
+            let logged = reload_errors(&logs);
+            assert_eq!(logged.len(), 3, "{:?}", logged);
+            assert_eq!(logged[0], logged[2]);
+            assert_eq!(app.world().resource::<FlakySettings>().rate, 1);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod preflight_tests {
+    use super::*;
+    use bevy::prelude::Reflect;
+    use bevy_config_file::{LoadConfigError, OverrideFormat, preflight_overrides};
+
+    macro_rules! preflight_config {
+        ($name:ident) => {
+            #[derive(Resource, Reflect, Serialize, Deserialize)]
+            struct $name {
+                ok: bool,
+            }
+
+            impl ConfigFile for $name {
+                const PATH: &'static str = "preflight.yaml";
+            }
+        };
+    }
+
+    preflight_config!(PreflightA);
+    preflight_config!(PreflightB);
+    preflight_config!(PreflightC);
+    preflight_config!(PreflightUnset);
+
+    #[derive(Resource, Reflect, Serialize, Deserialize)]
+    struct PreflightYaml {
+        ok: bool,
+    }
+
+    impl ConfigFile for PreflightYaml {
+        const PATH: &'static str = "preflight.yaml";
+        const ENV_VAR: Option<&'static str> = Some("PREFLIGHT_YAML");
+        const ENV_FORMAT: OverrideFormat = OverrideFormat::Yaml;
+    }
+
+    #[test]
+    fn test_preflight_aggregates_all_malformed_overrides() {
+        with_test_dir(&[], |_| {
+            let vars = [
+                ("CONFIG_PreflightA", "{not json"),
+                ("CONFIG_PreflightB", r#"{"ok": true}"#),
+                ("CONFIG_PreflightC", "[1, 2"),
+                ("PREFLIGHT_YAML", "ok: true"),
+            ];
+            for (var, value) in vars {
+                unsafe { std::env::set_var(var, value) };
+            }
+            let result =
+                preflight_overrides::<(PreflightA, PreflightB, PreflightC, PreflightUnset)>();
+            let valid = preflight_overrides::<(PreflightB, PreflightUnset, PreflightYaml)>();
+            for (var, _) in vars {
+                unsafe { std::env::remove_var(var) };
+            }
+
+            let errors = result.unwrap_err();
+            let vars: Vec<&str> = errors.iter().map(|(var, _)| var.as_str()).collect();
+            assert_eq!(vars, ["CONFIG_PreflightA", "CONFIG_PreflightC"]);
+            assert!(
+                errors
+                    .iter()
+                    .all(|(_, error)| matches!(error, LoadConfigError::Json(_)))
+            );
+
+            assert!(valid.is_ok());
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod merge_strategy_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, MergeStrategy, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Video {
+        width: u32,
+        height: u32,
+        #[serde(default = "default_fps")]
+        fps: u32,
+    }
+
+    fn default_fps() -> u32 {
+        60
+    }
+
+    const VIDEO: &str = "title: game\nvideo:\n  width: 1280\n  height: 720\n  fps: 144\n";
+    const OVERRIDE: &str = r#"{"video": {"width": 1920, "fps": null}}"#;
+
+    macro_rules! strategy_config {
+        ($name:ident, $strategy:expr) => {
+            #[derive(Debug, Serialize, Deserialize, Reflect)]
+            struct $name {
+                title: String,
+                video: Video,
+            }
+
+            impl ConfigFile for $name {
+                const PATH: &'static str = "video.yaml";
+                const MERGE_STRATEGY: MergeStrategy = $strategy;
+            }
+        };
+    }
+
+    strategy_config!(ShallowVideo, MergeStrategy::ShallowTopLevel);
+    strategy_config!(DeepVideo, MergeStrategy::Deep);
+    strategy_config!(PatchVideo, MergeStrategy::Patch);
+
+    fn load_with_override<T>(var: &str, json: &str) -> Result<T, LoadConfigError>
+    where
+        T: for<'de> Deserialize<'de> + Serialize + ConfigFile + Typed,
+    {
+        unsafe { std::env::set_var(var, json) };
+        let result = load_config_file::<T>();
+        unsafe { std::env::remove_var(var) };
+        result
+    }
+
+    #[test]
+    fn test_shallow_strategy_replaces_nested_objects() {
+        with_test_dir(&[("video.yaml", VIDEO)], |_| {
+            let json = r#"{"video": {"width": 1920, "height": 1080}}"#;
+            let config: ShallowVideo = load_with_override("CONFIG_ShallowVideo", json).unwrap();
+            assert_eq!(
+                config.video,
+                Video {
+                    width: 1920,
+                    height: 1080,
+                    fps: 60,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_deep_strategy_merges_nested_objects_and_keeps_nulls() {
+        with_test_dir(&[("video.yaml", VIDEO)], |_| {
+            let json = r#"{"video": {"width": 1920}}"#;
+            let config: DeepVideo = load_with_override("CONFIG_DeepVideo", json).unwrap();
+            assert_eq!(
+                config.video,
+                Video {
+                    width: 1920,
+                    height: 720,
+                    fps: 144,
+                }
+            );
+            assert_eq!(config.title, "game");
+
+            // null is stored as a value, which a u32 can't hold
+            let result = load_with_override::<DeepVideo>("CONFIG_DeepVideo", OVERRIDE);
+            assert!(matches!(result, Err(LoadConfigError::Json(_))));
+        });
+    }
+
+    #[test]
+    fn test_patch_strategy_removes_fields_set_to_null() {
+        with_test_dir(&[("video.yaml", VIDEO)], |_| {
+            let config: PatchVideo = load_with_override("CONFIG_PatchVideo", OVERRIDE).unwrap();
+            assert_eq!(
+                config.video,
+                Video {
+                    width: 1920,
+                    height: 720,
+                    fps: 60,
+                }
+            );
+            assert_eq!(config.title, "game");
+        });
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Advanced {
+        tuning: Tuning,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Tuning {
+        foo: u32,
+        #[serde(default)]
+        bar: u32,
+    }
+
+    macro_rules! optional_config {
+        ($name:ident, $strategy:expr) => {
+            #[derive(Debug, Serialize, Deserialize, Reflect)]
+            struct $name {
+                title: String,
+                advanced: Option<Advanced>,
+            }
+
+            impl ConfigFile for $name {
+                const PATH: &'static str = "advanced.yaml";
+                const MERGE_STRATEGY: MergeStrategy = $strategy;
+            }
+        };
+    }
+
+    optional_config!(ShallowAdvanced, MergeStrategy::ShallowTopLevel);
+    optional_config!(DeepAdvanced, MergeStrategy::Deep);
+    optional_config!(PatchAdvanced, MergeStrategy::Patch);
+
+    const ADVANCED_OVERRIDE: &str = r#"{"advanced": {"tuning": {"foo": 1}}}"#;
+
+    fn created_advanced() -> Option<Advanced> {
+        Some(Advanced {
+            tuning: Tuning { foo: 1, bar: 0 },
+        })
+    }
+
+    #[test]
+    fn test_override_creates_object_missing_from_file() {
+        with_test_dir(&[("advanced.yaml", "title: game\n")], |_| {
+            let shallow: ShallowAdvanced =
+                load_with_override("CONFIG_ShallowAdvanced", ADVANCED_OVERRIDE).unwrap();
+            let deep: DeepAdvanced =
+                load_with_override("CONFIG_DeepAdvanced", ADVANCED_OVERRIDE).unwrap();
+            let patch: PatchAdvanced =
+                load_with_override("CONFIG_PatchAdvanced", ADVANCED_OVERRIDE).unwrap();
+
+            assert_eq!(shallow.advanced, created_advanced());
+            assert_eq!(deep.advanced, created_advanced());
+            assert_eq!(patch.advanced, created_advanced());
+        });
+    }
+
+    #[test]
+    fn test_override_creates_object_where_file_has_null() {
+        with_test_dir(&[("advanced.yaml", "title: game\nadvanced: ~\n")], |_| {
+            let deep: DeepAdvanced =
+                load_with_override("CONFIG_DeepAdvanced", ADVANCED_OVERRIDE).unwrap();
+            let patch: PatchAdvanced =
+                load_with_override("CONFIG_PatchAdvanced", ADVANCED_OVERRIDE).unwrap();
+
+            assert_eq!(deep.advanced, created_advanced());
+            assert_eq!(patch.advanced, created_advanced());
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod override_string_tests {
+    use super::*;
+    use bevy::prelude::Reflect;
+    use bevy_config_file::{config_as_override_string, load_config_file};
+
+    #[derive(Reflect, Debug, Clone, Serialize, Deserialize, PartialEq)]
+    struct SharedSettings {
+        volume: f32,
+        nickname: String,
+        keys: Vec<String>,
+    }
+
+    impl ConfigFile for SharedSettings {
+        const PATH: &'static str = "shared_settings.yaml";
+    }
+
+    #[test]
+    fn test_override_string_round_trips_changed_fields() {
+        with_test_dir(
+            &[(
+                "shared_settings.yaml",
+                "volume: 1.0\nnickname: player\nkeys: [w, a, s, d]\n",
+            )],
+            |_| {
+                let mut current = load_config_file::<SharedSettings>().unwrap();
+                current.volume = 0.25;
+                current.nickname = "it's me".to_string();
+
+                let assignment = config_as_override_string(&current).unwrap();
+                let (var, quoted) = assignment.split_once('=').unwrap();
+                assert_eq!(var, "CONFIG_SharedSettings");
+                assert!(!quoted.contains("keys"), "{}", quoted);
+
+                // Undo the shell quoting: '...' with embedded quotes written as '\''
+                let json = quoted
+                    .strip_prefix('\'')
+                    .and_then(|rest| rest.strip_suffix('\''))
+                    .unwrap()
+                    .replace(r"'\''", "'");
+                unsafe { std::env::set_var(var, &json) };
+                let reloaded = load_config_file::<SharedSettings>();
+                unsafe { std::env::remove_var(var) };
+
+                assert_eq!(reloaded.unwrap(), current);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod field_info_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::attributes::{Max, Min};
+    use bevy_config_file::{FieldInfo, config_field_info};
+    use serde_json::json;
+
+    #[derive(Reflect, Serialize)]
+    struct Shadows {
+        enabled: bool,
+        #[reflect(@Min(256.0), @Max(4096.0))]
+        resolution: u32,
+    }
+
+    #[derive(Reflect, Serialize)]
+    struct MenuSettings {
+        #[reflect(@Min(0.0), @Max(1.0))]
+        brightness: f32,
+        title: String,
+        shadows: Shadows,
+    }
+
+    #[test]
+    fn test_field_info_lists_leaf_fields() {
+        let settings = MenuSettings {
+            brightness: 0.5,
+            title: "Game".to_string(),
+            shadows: Shadows {
+                enabled: true,
+                resolution: 1024,
+            },
+        };
+
+        let fields = config_field_info(&settings);
+
+        assert_eq!(
+            fields,
+            vec![
+                FieldInfo {
+                    name: "brightness".to_string(),
+                    type_path: "f32",
+                    value: json!(0.5),
+                    min: Some(0.0),
+                    max: Some(1.0),
+                },
+                FieldInfo {
+                    name: "title".to_string(),
+                    type_path: "alloc::string::String",
+                    value: json!("Game"),
+                    min: None,
+                    max: None,
+                },
+                FieldInfo {
+                    name: "shadows.enabled".to_string(),
+                    type_path: "bool",
+                    value: json!(true),
+                    min: None,
+                    max: None,
+                },
+                FieldInfo {
+                    name: "shadows.resolution".to_string(),
+                    type_path: "u32",
+                    value: json!(1024),
+                    min: Some(256.0),
+                    max: Some(4096.0),
+                },
+            ]
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod telemetry_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{
+        ConfigCounts, ConfigTelemetry, clear_override, config_file_plugin, set_override,
+    };
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct MonitoredSettings {
+        tick_rate: u32,
+    }
+
+    impl ConfigFile for MonitoredSettings {
+        const PATH: &'static str = "monitored_settings.yaml";
+    }
+
+    #[test]
+    fn test_telemetry_counts_loads_reloads_and_errors() {
+        with_test_dir(&[("monitored_settings.yaml", "tick_rate: 30\n")], |_| {
+            let mut app = App::new();
+            app.set_error_handler(bevy::ecs::error::ignore);
+            app.add_plugins(config_file_plugin::<MonitoredSettings>);
+            app.update();
+
+            set_override::<MonitoredSettings>(r#"{"tick_rate": 60}"#);
+            app.update();
+            set_override::<MonitoredSettings>("{not json");
+            app.update();
+            clear_override::<MonitoredSettings>();
+            app.update();
+
+            let telemetry = app.world().resource::<ConfigTelemetry>();
+            let expected = ConfigCounts {
+                loads: 1,
+                reloads: 2,
+                errors: 1,
+            };
+            assert_eq!(telemetry.get::<MonitoredSettings>(), expected);
+            assert_eq!(telemetry.total(), expected);
+            assert_eq!(telemetry.iter().count(), 1);
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "encryption"))]
+mod encryption_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, encrypt_config, load_config_file};
+
+    const KEY: [u8; 32] = [0x2a; 32];
+    const KEY_HEX: &str = "2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a";
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct SealedSettings {
+        db_password: String,
+        port: u16,
+    }
+
+    impl ConfigFile for SealedSettings {
+        const PATH: &'static str = "sealed.yaml";
+        const ENCRYPTED: bool = true;
+    }
+
+    fn load_with_key(key: &str) -> Result<SealedSettings, LoadConfigError> {
+        unsafe { std::env::set_var("CONFIG_KEY", key) };
+        let result = load_config_file::<SealedSettings>();
+        unsafe { std::env::remove_var("CONFIG_KEY") };
+        result
+    }
+
+    #[test]
+    fn test_encrypted_file_round_trips() {
+        with_test_dir(&[], |dir| {
+            let bytes = encrypt_config("db_password: hunter2\nport: 5432\n", &KEY);
+            fs::write(dir.join("sealed.yaml"), &bytes).unwrap();
+            assert!(!String::from_utf8_lossy(&bytes).contains("hunter2"));
+
+            assert_eq!(
+                load_with_key(KEY_HEX).unwrap(),
+                SealedSettings {
+                    db_password: "hunter2".to_string(),
+                    port: 5432,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_wrong_key_fails_to_decrypt() {
+        with_test_dir(&[], |dir| {
+            let bytes = encrypt_config("db_password: hunter2\nport: 5432\n", &KEY);
+            fs::write(dir.join("sealed.yaml"), bytes).unwrap();
+
+            let wrong_key = KEY_HEX.replace('2', "3");
+            assert!(matches!(
+                load_with_key(&wrong_key),
+                Err(LoadConfigError::Decrypt(_))
+            ));
+            assert!(matches!(
+                load_with_key("not hex"),
+                Err(LoadConfigError::Decrypt(_))
+            ));
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod read_timeout_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+    use std::path::Path;
+    use std::time::Duration;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct MountedSettings {
+        shard: u32,
+    }
+
+    impl ConfigFile for MountedSettings {
+        const PATH: &'static str = "mounted.yaml";
+        const READ_TIMEOUT_MS: Option<u64> = Some(50);
+
+        fn read_file(path: &Path) -> std::io::Result<Vec<u8>> {
+            // Simulate a network mount holding the read
+            std::thread::sleep(Duration::from_millis(500));
+            fs::read(path)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct InMemorySettings {
+        shard: u32,
+    }
+
+    impl ConfigFile for InMemorySettings {
+        const PATH: &'static str = "in_memory.yaml";
+        const READ_TIMEOUT_MS: Option<u64> = Some(5_000);
+
+        fn read_file(_path: &Path) -> std::io::Result<Vec<u8>> {
+            Ok(b"shard: 3\n".to_vec())
+        }
+    }
+
+    #[test]
+    fn test_slow_read_times_out() {
+        with_test_dir(&[("mounted.yaml", "shard: 1\n")], |_| {
+            let result = load_config_file::<MountedSettings>();
+            match result {
+                Err(LoadConfigError::Timeout { path, timeout_ms }) => {
+                    assert_eq!(path, "mounted.yaml");
+                    assert_eq!(timeout_ms, 50);
+                }
+                other => panic!("expected a timeout, got {:?}", other),
+            }
+        });
+    }
+
+    #[test]
+    fn test_read_within_timeout_uses_custom_source() {
+        with_test_dir(&[], |_| {
+            assert_eq!(
+                load_config_file::<InMemorySettings>().unwrap(),
+                InMemorySettings { shard: 3 }
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod comment_range_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Camera {
+        zoom: f32,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct RangedSettings {
+        pan_speed: u32,
+        label: String,
+        camera: Camera,
+    }
+
+    impl ConfigFile for RangedSettings {
+        const PATH: &'static str = "ranged.yaml";
+        const CHECK_COMMENT_RANGES: bool = true;
+    }
+
+    const RANGED: &str = "\
+# Tuned by design
+pan_speed: 2000 # range: 0..5000
+label: \"fast # not a comment\" # max: 1
+camera:
+  zoom: 1.5 # min: 0.5, max: 4
+";
+
+    #[test]
+    fn test_range_comments_apply_outside_quotes() {
+        with_test_dir(&[("ranged.yaml", RANGED)], |_| {
+            let config = load_config_file::<RangedSettings>();
+            assert!(
+                matches!(config, Err(LoadConfigError::Validation(ref message)) if message.contains("label")),
+                "{:?}",
+                config
+            );
+
+            let without_label = RANGED.replace(" # max: 1", "");
+            fs::write("ranged.yaml", without_label).unwrap();
+            let config = load_config_file::<RangedSettings>().unwrap();
+            assert_eq!(config.pan_speed, 2000);
+            assert_eq!(config.label, "fast # not a comment");
+        });
+    }
+
+    #[test]
+    fn test_value_outside_commented_range_is_rejected() {
+        let out_of_range = RANGED
+            .replace("pan_speed: 2000", "pan_speed: 6000")
+            .replace(" # max: 1", "");
+        with_test_dir(&[("ranged.yaml", &out_of_range)], |_| {
+            let error = load_config_file::<RangedSettings>().unwrap_err();
+            assert!(matches!(error, LoadConfigError::Validation(_)));
+            assert!(error.to_string().contains("pan_speed = 6000"), "{}", error);
+        });
+    }
+
+    #[test]
+    fn test_override_outside_nested_commented_range_is_rejected() {
+        let ranged = RANGED.replace(" # max: 1", "");
+        with_test_dir(&[("ranged.yaml", &ranged)], |_| {
+            unsafe { std::env::set_var("CONFIG_RangedSettings", r#"{"camera": {"zoom": 0.1}}"#) };
+            let result = load_config_file::<RangedSettings>();
+            unsafe { std::env::remove_var("CONFIG_RangedSettings") };
+
+            let error = result.unwrap_err();
+            assert!(error.to_string().contains("camera.zoom"), "{}", error);
+        });
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct RangedAudio {
+        volume: f32,
+    }
+
+    impl ConfigFile for RangedAudio {
+        const PATH: &'static str = "ranged_sections.yaml";
+        const ROOT_KEY: Option<&'static str> = Some("audio");
+        const CHECK_COMMENT_RANGES: bool = true;
+    }
+
+    #[test]
+    fn test_range_comments_apply_within_root_key() {
+        let sections = "audio:\n  volume: 1.5 # range: 0..1\nvideo:\n  volume: 1.5\n";
+        with_test_dir(&[("ranged_sections.yaml", sections)], |_| {
+            let error = load_config_file::<RangedAudio>().unwrap_err();
+            assert!(error.to_string().contains("audio.volume = 1.5"), "{}", error);
+
+            fs::write("ranged_sections.yaml", sections.replace("1.5 #", "0.5 #")).unwrap();
+            assert_eq!(load_config_file::<RangedAudio>().unwrap().volume, 0.5);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod duplicate_key_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+    use std::collections::HashMap;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Wave {
+        enemy: String,
+        count: u32,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct WaveSettings {
+        spawn_rates: HashMap<String, f32>,
+        waves: Vec<Wave>,
+        intro: String,
+    }
+
+    impl ConfigFile for WaveSettings {
+        const PATH: &'static str = "waves.yaml";
+        const DENY_DUPLICATE_KEYS: bool = true;
+    }
+
+    const WAVES: &str = "\
+spawn_rates:
+  goblin: 1.5
+  orc: 0.5 # orc: 2.0
+waves:
+  - enemy: goblin
+    count: 10
+  - enemy: orc
+    count: 2
+intro: |
+  count: not a key
+  count: still not a key
+";
+
+    #[test]
+    fn test_repeated_keys_in_separate_mappings_are_allowed() {
+        with_test_dir(&[("waves.yaml", WAVES)], |_| {
+            let config = load_config_file::<WaveSettings>().unwrap();
+            assert_eq!(config.spawn_rates["orc"], 0.5);
+            assert_eq!(config.waves.len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_duplicated_nested_key_is_rejected() {
+        let duplicated = WAVES.replace("  orc: 0.5", "  orc: 0.5\n  goblin: 3.0");
+        with_test_dir(&[("waves.yaml", &duplicated)], |_| {
+            let error = load_config_file::<WaveSettings>().unwrap_err();
+            assert!(matches!(error, LoadConfigError::DuplicateKey(_)));
+            assert!(
+                error.to_string().contains("spawn_rates.goblin (line 4"),
+                "{}",
+                error
+            );
+        });
+    }
+
+    #[test]
+    fn test_duplicated_key_in_sequence_item_is_rejected() {
+        let duplicated = WAVES.replace("    count: 2", "    count: 2\n    count: 5");
+        with_test_dir(&[("waves.yaml", &duplicated)], |_| {
+            let error = load_config_file::<WaveSettings>().unwrap_err();
+            assert!(error.to_string().contains("waves.count"), "{}", error);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod env_substitution_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file, save_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct MachineSettings {
+        save_dir: String,
+        label: String,
+    }
+
+    impl ConfigFile for MachineSettings {
+        const PATH: &'static str = "machine.yaml";
+        const EXPAND_ENV_VARS: bool = true;
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct LenientMachineSettings {
+        save_dir: String,
+        label: String,
+    }
+
+    impl ConfigFile for LenientMachineSettings {
+        const PATH: &'static str = "machine.yaml";
+        const EXPAND_ENV_VARS: bool = true;
+        const ALLOW_MISSING_ENV_VARS: bool = true;
+    }
+
+    const YAML: &str = "save_dir: ${SUBST_SAVE_DIR}/saves\nlabel: \"${not a var}\"\n";
+
+    #[test]
+    fn test_tokens_are_replaced_before_parsing() {
+        with_test_dir(&[("machine.yaml", YAML)], |_| {
+            unsafe { std::env::set_var("SUBST_SAVE_DIR", "/home/player") };
+            let result = load_config_file::<MachineSettings>();
+            unsafe { std::env::remove_var("SUBST_SAVE_DIR") };
+
+            assert_eq!(
+                result.unwrap(),
+                MachineSettings {
+                    save_dir: "/home/player/saves".to_string(),
+                    label: "${not a var}".to_string(),
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_unset_variable_fails_unless_allowed() {
+        with_test_dir(&[("machine.yaml", YAML)], |_| {
+            let error = load_config_file::<MachineSettings>().unwrap_err();
+            assert!(
+                matches!(&error, LoadConfigError::MissingEnvVar { var, .. } if var == "SUBST_SAVE_DIR"),
+                "{:?}",
+                error
+            );
+
+            let lenient = load_config_file::<LenientMachineSettings>().unwrap();
+            assert_eq!(lenient.save_dir, "/saves");
+        });
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct ExpandedServer {
+        port: u16,
+    }
+
+    impl ConfigFile for ExpandedServer {
+        const PATH: &'static str = "services.yaml";
+        const ROOT_KEY: Option<&'static str> = Some("server");
+        const EXPAND_ENV_VARS: bool = true;
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct PatchedExpandedServer {
+        port: u16,
+    }
+
+    impl ConfigFile for PatchedExpandedServer {
+        const PATH: &'static str = "services.yaml";
+        const ROOT_KEY: Option<&'static str> = Some("server");
+        const EXPAND_ENV_VARS: bool = true;
+        const SAVE_CHANGES_ONLY: bool = true;
+    }
+
+    const SERVICES: &str = "server:\n  port: 80\ndatabase:\n  password: ${SUBST_DB_PASSWORD}\n";
+
+    fn saved_services(save: impl FnOnce()) -> String {
+        let mut saved = String::new();
+        with_test_dir(&[("services.yaml", SERVICES)], |_| {
+            unsafe { std::env::set_var("SUBST_DB_PASSWORD", "hunter2") };
+            save();
+            unsafe { std::env::remove_var("SUBST_DB_PASSWORD") };
+            saved = fs::read_to_string("services.yaml").unwrap();
+        });
+        saved
+    }
+
+    #[test]
+    fn test_saving_a_section_keeps_tokens_of_other_sections() {
+        let saved = saved_services(|| save_config_file(&ExpandedServer { port: 8080 }).unwrap());
+        assert!(saved.contains("${SUBST_DB_PASSWORD}"), "{}", saved);
+        assert!(!saved.contains("hunter2"), "{}", saved);
+        assert!(saved.contains("8080"), "{}", saved);
+
+        let saved =
+            saved_services(|| save_config_file(&PatchedExpandedServer { port: 8080 }).unwrap());
+        assert_eq!(saved, SERVICES.replace("80\n", "8080\n"));
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod fallback_path_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct FallbackSettings {
+        volume: f32,
+    }
+
+    impl ConfigFile for FallbackSettings {
+        const PATH: &'static str = "user/settings.yaml";
+        const FALLBACK_PATHS: &'static [&'static str] =
+            &["missing/settings.yaml", "assets/settings.yaml"];
+    }
+
+    #[test]
+    fn test_first_existing_fallback_is_used() {
+        with_test_dir(&[("assets/settings.yaml", "volume: 0.5\n")], |_| {
+            assert_eq!(
+                load_config_file::<FallbackSettings>().unwrap(),
+                FallbackSettings { volume: 0.5 }
+            );
+        });
+    }
+
+    #[test]
+    fn test_path_wins_over_fallbacks() {
+        with_test_dir(
+            &[
+                ("user/settings.yaml", "volume: 1.0\n"),
+                ("assets/settings.yaml", "volume: 0.5\n"),
+            ],
+            |_| {
+                assert_eq!(load_config_file::<FallbackSettings>().unwrap().volume, 1.0);
+            },
+        );
+    }
+
+    #[test]
+    fn test_no_existing_path_reports_io_error() {
+        with_test_dir(&[], |_| {
+            let error = load_config_file::<FallbackSettings>().unwrap_err();
+            assert!(matches!(error, LoadConfigError::Io(_)), "{:?}", error);
+        });
+    }
+
+    #[test]
+    fn test_no_env_loader_uses_fallback() {
+        with_test_dir(&[("assets/settings.yaml", "volume: 0.5\n")], |_| {
+            let config = bevy_config_file::load_config_file_no_env::<FallbackSettings>();
+            assert_eq!(config.unwrap().volume, 0.5);
+        });
+    }
+
+    #[test]
+    fn test_save_creates_path_instead_of_overwriting_fallback() {
+        with_test_dir(&[("assets/settings.yaml", "volume: 0.5\n")], |_| {
+            let mut settings = load_config_file::<FallbackSettings>().unwrap();
+            settings.volume = 0.75;
+            bevy_config_file::save_config_file(&settings).unwrap();
+
+            assert_eq!(
+                fs::read_to_string("assets/settings.yaml").unwrap(),
+                "volume: 0.5\n"
+            );
+            assert_eq!(load_config_file::<FallbackSettings>().unwrap().volume, 0.75);
+            assert!(std::path::Path::new("user/settings.yaml").exists());
+        });
+    }
+
+    #[test]
+    fn test_broken_path_does_not_fall_back() {
+        with_test_dir(
+            &[
+                ("user/settings.yaml", "volume: [broken\n"),
+                ("assets/settings.yaml", "volume: 0.5\n"),
+            ],
+            |_| {
+                let error = load_config_file::<FallbackSettings>().unwrap_err();
+                assert!(matches!(error, LoadConfigError::Yaml(_)), "{:?}", error);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod migration_tests {
+    use super::*;
+    use bevy_config_file::{JsonValue, LoadConfigError, load_config_file, save_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Window {
+        width: u32,
+        height: u32,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct DisplaySettings {
+        schema_version: u32,
+        window: Window,
+        vsync: bool,
+    }
+
+    impl ConfigFile for DisplaySettings {
+        const PATH: &'static str = "display.yaml";
+        const SCHEMA_VERSION: Option<u32> = Some(2);
+
+        fn migrate(value: &mut JsonValue, from_version: u32) {
+            // Version 1 kept the window size at the top level
+            if from_version == 1 {
+                let map = value.as_object_mut().unwrap();
+                let width = map.remove("window_width").unwrap();
+                let height = map.remove("window_height").unwrap();
+                map.insert(
+                    "window".to_string(),
+                    serde_json::json!({ "width": width, "height": height }),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_v1_file_is_migrated_to_current_shape() {
+        let v1 = "schema_version: 1\nwindow_width: 1280\nwindow_height: 720\nvsync: true\n";
+        with_test_dir(&[("display.yaml", v1)], |_| {
+            unsafe { std::env::set_var("CONFIG_DisplaySettings", r#"{"vsync": false}"#) };
+            let result = load_config_file::<DisplaySettings>();
+            unsafe { std::env::remove_var("CONFIG_DisplaySettings") };
+
+            assert_eq!(
+                result.unwrap(),
+                DisplaySettings {
+                    schema_version: 2,
+                    window: Window {
+                        width: 1280,
+                        height: 720
+                    },
+                    vsync: false,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_file_newer_than_schema_version_is_rejected() {
+        let v3 = "schema_version: 3\nwindow: { width: 1, height: 1 }\nvsync: true\n";
+        with_test_dir(&[("display.yaml", v3)], |_| {
+            let error = load_config_file::<DisplaySettings>().unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    LoadConfigError::SchemaVersion {
+                        found: 3,
+                        min: 0,
+                        max: 2
+                    }
+                ),
+                "{:?}",
+                error
+            );
+        });
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct RenamedAudio {
+        volume: f32,
+    }
+
+    impl ConfigFile for RenamedAudio {
+        const PATH: &'static str = "renamed_audio.yaml";
+        const SCHEMA_VERSION: Option<u32> = Some(1);
+        const SCHEMA_VERSION_KEY: &'static str = "__version";
+
+        fn migrate(value: &mut JsonValue, from_version: u32) {
+            // Version 0 called the volume `master_volume`
+            if from_version == 0 {
+                let map = value.as_object_mut().unwrap();
+                let volume = map.remove("master_volume").unwrap();
+                map.insert("volume".to_string(), volume);
+            }
+        }
+    }
+
+    #[test]
+    fn test_file_without_version_key_is_migrated_from_v0() {
+        with_test_dir(&[("renamed_audio.yaml", "master_volume: 0.5\n")], |_| {
+            assert_eq!(
+                load_config_file::<RenamedAudio>().unwrap(),
+                RenamedAudio { volume: 0.5 }
+            );
+        });
+    }
+
+    #[test]
+    fn test_saved_migrated_file_loads_without_migrating_again() {
+        with_test_dir(&[("renamed_audio.yaml", "master_volume: 0.5\n")], |_| {
+            let mut audio = load_config_file::<RenamedAudio>().unwrap();
+            audio.volume = 0.25;
+            save_config_file(&audio).unwrap();
+
+            let saved = fs::read_to_string("renamed_audio.yaml").unwrap();
+            assert!(saved.contains("__version: 1"), "{}", saved);
+            assert_eq!(load_config_file::<RenamedAudio>().unwrap(), audio);
+        });
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct BandedDisplay {
+        schema_version: u32,
+        window: Window,
+    }
+
+    impl ConfigFile for BandedDisplay {
+        const PATH: &'static str = "banded_display.yaml";
+        const SCHEMA_VERSION: Option<u32> = Some(3);
+        const MIN_SCHEMA_VERSION: u32 = 2;
+        const MAX_SCHEMA_VERSION: Option<u32> = Some(4);
+
+        fn migrate(value: &mut JsonValue, from_version: u32) {
+            // Version 2 called the window "screen"
+            if from_version == 2 {
+                let map = value.as_object_mut().unwrap();
+                let screen = map.remove("screen").unwrap();
+                map.insert("window".to_string(), screen);
+            }
+        }
+    }
+
+    #[test]
+    fn test_file_below_min_schema_version_is_rejected() {
+        let v1 = "schema_version: 1\nscreen: { width: 1, height: 1 }\n";
+        with_test_dir(&[("banded_display.yaml", v1)], |_| {
+            let error = load_config_file::<BandedDisplay>().unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    LoadConfigError::SchemaVersion {
+                        found: 1,
+                        min: 2,
+                        max: 4
+                    }
+                ),
+                "{:?}",
+                error
+            );
+        });
+    }
+
+    #[test]
+    fn test_files_within_schema_version_range_are_loaded() {
+        let v2 = "schema_version: 2\nscreen: { width: 800, height: 600 }\n";
+        with_test_dir(&[("banded_display.yaml", v2)], |_| {
+            assert_eq!(
+                load_config_file::<BandedDisplay>().unwrap(),
+                BandedDisplay {
+                    schema_version: 3,
+                    window: Window {
+                        width: 800,
+                        height: 600
+                    },
+                }
+            );
+        });
+
+        let v4 = "schema_version: 4\nwindow: { width: 1024, height: 768 }\n";
+        with_test_dir(&[("banded_display.yaml", v4)], |_| {
+            assert_eq!(
+                load_config_file::<BandedDisplay>().unwrap(),
+                BandedDisplay {
+                    schema_version: 4,
+                    window: Window {
+                        width: 1024,
+                        height: 768
+                    },
+                }
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod invariant_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct SpawnSettings {
+        min_enemies: u32,
+        max_enemies: u32,
+        min_delay: f32,
+        max_delay: f32,
+    }
+
+    impl ConfigFile for SpawnSettings {
+        const PATH: &'static str = "spawn.yaml";
+
+        fn validate_invariants(&self) -> Vec<String> {
+            let mut violations = Vec::new();
+            if self.min_enemies > self.max_enemies {
+                violations.push("min_enemies must not exceed max_enemies".to_string());
+            }
+            if self.min_delay > self.max_delay {
+                violations.push("min_delay must not exceed max_delay".to_string());
+            }
+            violations
+        }
+    }
+
+    #[test]
+    fn test_all_broken_invariants_are_reported() {
+        let spawn = "min_enemies: 5\nmax_enemies: 2\nmin_delay: 3.0\nmax_delay: 1.0\n";
+        with_test_dir(&[("spawn.yaml", spawn)], |_| {
+            match load_config_file::<SpawnSettings>() {
+                Err(LoadConfigError::InvariantViolations(violations)) => assert_eq!(
+                    violations,
+                    [
+                        "min_enemies must not exceed max_enemies",
+                        "min_delay must not exceed max_delay"
+                    ]
+                ),
+                other => panic!("expected InvariantViolations, got {:?}", other),
+            }
+        });
+    }
+
+    #[test]
+    fn test_override_can_break_an_invariant() {
+        let spawn = "min_enemies: 1\nmax_enemies: 2\nmin_delay: 0.5\nmax_delay: 1.0\n";
+        with_test_dir(&[("spawn.yaml", spawn)], |_| {
+            assert!(load_config_file::<SpawnSettings>().is_ok());
+
+            unsafe { std::env::set_var("CONFIG_SpawnSettings", r#"{"max_delay": 0.1}"#) };
+            let result = load_config_file::<SpawnSettings>();
+            unsafe { std::env::remove_var("CONFIG_SpawnSettings") };
+            let error = result.unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Invalid config: min_delay must not exceed max_delay"
+            );
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod validated_config_tests {
+    use super::*;
+    use bevy::ecs::error::{BevyError, ErrorContext};
+    use bevy::prelude::*;
+    use bevy_config_file::{
+        LoadConfigError, ReloadConfig, ValidatedConfig, config_file_validated_plugin,
+        load_config_file,
+    };
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct ZoomSettings {
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for ZoomSettings {
+        const PATH: &'static str = "zoom_settings.yaml";
+    }
+
+    impl ValidatedConfig for ZoomSettings {
+        fn validate(&self) -> Result<(), String> {
+            if self.zoom_speed < 0.0 {
+                return Err(format!(
+                    "zoom_speed must not be negative, found {}",
+                    self.zoom_speed
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    static REPORTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    fn record_error(error: BevyError, _ctx: ErrorContext) {
+        REPORTED.lock().unwrap().push(error.to_string());
+    }
+
+    #[test]
+    fn test_invalid_config_is_never_inserted() {
+        with_test_dir(&[("zoom_settings.yaml", "zoom_speed: -1.5\n")], |_| {
+            let mut app = App::new();
+            app.set_error_handler(record_error);
+            app.add_plugins(config_file_validated_plugin::<ZoomSettings>);
+            app.update();
+
+            assert!(app.world().get_resource::<ZoomSettings>().is_none());
+            let reported = REPORTED.lock().unwrap();
+            assert_eq!(reported.len(), 1, "{:?}", reported);
+            assert!(
+                reported[0].contains("zoom_speed must not be negative, found -1.5"),
+                "{}",
+                reported[0]
+            );
+            // Only the plugin's loads run the check
+            assert!(load_config_file::<ZoomSettings>().is_ok());
+        });
+    }
+
+    #[test]
+    fn test_valid_config_is_inserted() {
+        with_test_dir(&[("zoom_settings.yaml", "zoom_speed: 2\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_validated_plugin::<ZoomSettings>);
+            app.update();
+            assert_eq!(app.world().resource::<ZoomSettings>().zoom_speed, 2.0);
+        });
+    }
+
+    #[test]
+    fn test_invalid_reload_keeps_resource() {
+        with_test_dir(&[("zoom_settings.yaml", "zoom_speed: 2\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_validated_plugin::<ZoomSettings>);
+            app.update();
+
+            std::fs::write("zoom_settings.yaml", "zoom_speed: -3\n").unwrap();
+            let result = ReloadConfig::<ZoomSettings>::new().apply(app.world_mut());
+            assert!(
+                matches!(
+                    &result,
+                    Err(LoadConfigError::Validation(message))
+                        if message == "zoom_speed must not be negative, found -3"
+                ),
+                "{:?}",
+                result
+            );
+            assert_eq!(app.world().resource::<ZoomSettings>().zoom_speed, 2.0);
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "checksum"))]
+mod checksum_tests {
+    use super::*;
+    use bevy_config_file::{LoadConfigError, load_config_file, write_checksum_file};
+
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct ShippedSettings {
+        max_players: u32,
+    }
+
+    impl ConfigFile for ShippedSettings {
+        const PATH: &'static str = "shipped.yaml";
+        const VERIFY_CHECKSUM: bool = true;
+    }
+
+    #[test]
+    fn test_intact_file_loads() {
+        with_test_dir(&[("shipped.yaml", "max_players: 8\n")], |_| {
+            write_checksum_file("shipped.yaml").unwrap();
+            assert_eq!(
+                load_config_file::<ShippedSettings>().unwrap().max_players,
+                8
+            );
+        });
+    }
+
+    #[test]
+    fn test_tampered_file_is_rejected() {
+        with_test_dir(&[("shipped.yaml", "max_players: 8\n")], |_| {
+            write_checksum_file("shipped.yaml").unwrap();
+            fs::write("shipped.yaml", "max_players: 800\n").unwrap();
+
+            let error = load_config_file::<ShippedSettings>().unwrap_err();
+            assert!(
+                matches!(&error, LoadConfigError::Checksum(message) if message.contains("expects")),
+                "{:?}",
+                error
+            );
+        });
+    }
+
+    #[test]
+    fn test_missing_sidecar_is_rejected() {
+        with_test_dir(&[("shipped.yaml", "max_players: 8\n")], |_| {
+            let error = load_config_file::<ShippedSettings>().unwrap_err();
+            assert!(matches!(error, LoadConfigError::Checksum(_)), "{:?}", error);
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "dirs", target_os = "linux"))]
+mod platform_dir_tests {
+    use super::*;
+    use bevy_config_file::{load_config_file, save_config_file};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct UserSettings {
+        volume: f32,
+    }
+
+    impl ConfigFile for UserSettings {
+        const PATH: &'static str = "settings.yaml";
+        const USE_PLATFORM_DIR: bool = true;
+        const APP_NAME: &'static str = "my_game";
+    }
+
+    #[test]
+    fn test_reads_and_writes_in_platform_config_dir() {
+        with_test_dir(
+            &[
+                ("xdg/my_game/settings.yaml", "volume: 0.8\n"),
+                ("settings.yaml", "volume: 0.1\n"),
+            ],
+            |dir| {
+                // dirs reads the Linux config directory from XDG_CONFIG_HOME
+                unsafe { std::env::set_var("XDG_CONFIG_HOME", dir.join("xdg")) };
+                let loaded = load_config_file::<UserSettings>();
+                let saved = save_config_file(&UserSettings { volume: 0.3 });
+                let written = fs::read_to_string(dir.join("xdg/my_game/settings.yaml"));
+                unsafe { std::env::remove_var("XDG_CONFIG_HOME") };
+
+                assert_eq!(loaded.unwrap(), UserSettings { volume: 0.8 });
+                saved.unwrap();
+                assert!(written.unwrap().contains("volume: 0.3"));
+                assert_eq!(
+                    fs::read_to_string("settings.yaml").unwrap(),
+                    "volume: 0.1\n"
+                );
+            },
+        );
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "asset"))]
+mod asset_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{ConfigLoadFailed, config_file_asset_plugin};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct WebSettings {
+        volume: f32,
+    }
+
+    impl ConfigFile for WebSettings {
+        const PATH: &'static str = "assets/config/web.yaml";
+    }
+
+    #[derive(Resource, Default)]
+    struct Failures(Vec<String>);
+
+    fn record_failures(
+        mut failed: MessageReader<ConfigLoadFailed<WebSettings>>,
+        mut failures: ResMut<Failures>,
+    ) {
+        failures
+            .0
+            .extend(failed.read().map(|failure| failure.error.clone()));
+    }
+
+    /// Runs an app loading `WebSettings` from `dir`'s asset folder until the resource
+    /// appears or a load fails.
+    fn run_asset_app(dir: &std::path::Path) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                file_path: dir.join("assets").to_string_lossy().into_owned(),
+                ..default()
+            },
+            config_file_asset_plugin::<WebSettings>,
+        ))
+        .init_resource::<Failures>()
+        .add_systems(Update, record_failures);
+        for _ in 0..500 {
+            app.update();
+            if app.world().contains_resource::<WebSettings>()
+                || !app.world().resource::<Failures>().0.is_empty()
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        app
+    }
+
+    #[test]
+    fn test_resource_inserted_once_asset_loads() {
+        with_test_dir(&[("assets/config/web.yaml", "volume: 0.4\n")], |dir| {
+            unsafe { std::env::set_var("CONFIG_WebSettings", r#"{"volume": 0.6}"#) };
+            let app = run_asset_app(dir);
+            unsafe { std::env::remove_var("CONFIG_WebSettings") };
+
+            assert_eq!(app.world().resource::<WebSettings>().volume, 0.6);
+        });
+    }
+
+    #[test]
+    fn test_failed_load_writes_message() {
+        with_test_dir(&[("assets/config/web.yaml", "volume: [oops\n")], |dir| {
+            let app = run_asset_app(dir);
+
+            assert!(!app.world().contains_resource::<WebSettings>());
+            assert_eq!(app.world().resource::<Failures>().0.len(), 1);
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "field_docs"))]
+mod field_docs_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{LoadConfigError, config_file_plugin, load_config_file};
+
+    #[derive(Reflect, Debug, Serialize, Deserialize)]
+    struct Camera {
+        /// Zoom steps per scroll notch.
+        zoom_speed: f32,
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct DocumentedSettings {
+        /// Camera pan speed in pixels per second.
+        /// Try 1000 to 3000.
+        pan_speed: f32,
+        camera: Camera,
+    }
+
+    impl ConfigFile for DocumentedSettings {
+        const PATH: &'static str = "documented.yaml";
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct PatchedSettings {
+        /// Master volume from 0 to 1.
+        volume: f32,
+    }
+
+    impl ConfigFile for PatchedSettings {
+        const PATH: &'static str = "patched.yaml";
+        const MERGE_STRATEGY: bevy_config_file::MergeStrategy =
+            bevy_config_file::MergeStrategy::Patch;
+    }
+
+    fn load(content: &str) -> LoadConfigError {
+        let mut error = None;
+        with_test_dir(&[("documented.yaml", content)], |_| {
+            App::new().add_plugins(config_file_plugin::<DocumentedSettings>);
+            error = load_config_file::<DocumentedSettings>().err();
+        });
+        error.expect("the config should fail to load")
+    }
+
+    #[test]
+    fn test_missing_field_error_includes_doc_comment() {
+        let error = load("camera:\n  zoom_speed: 1.0\n");
+        assert!(
+            matches!(error, LoadConfigError::FieldHelp { .. }),
+            "{:?}",
+            error
+        );
+        assert!(
+            error.to_string().contains(
+                "help: pan_speed: Camera pan speed in pixels per second. Try 1000 to 3000."
+            ),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_mistyped_nested_field_error_includes_doc_comment() {
+        let error = load("pan_speed: 2000\ncamera:\n  zoom_speed: fast\n");
+        assert!(
+            error
+                .to_string()
+                .contains("help: camera.zoom_speed: Zoom steps per scroll notch."),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_typed_override_error_includes_doc_comment() {
+        with_test_dir(&[("patched.yaml", "volume: 0.5\n")], |_| {
+            App::new().add_plugins(config_file_plugin::<PatchedSettings>);
+            // Under `MergeStrategy::Patch`, `null` removes the field
+            let error = bevy_config_file::load_config_file_with_override::<PatchedSettings>(
+                &serde_json::json!({"volume": null}),
+            )
+            .expect_err("the config should fail to load");
+            assert!(
+                error
+                    .to_string()
+                    .contains("help: volume: Master volume from 0 to 1."),
+                "{}",
+                error
+            );
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "hot_reload"))]
+mod hot_reload_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{config_file_hot_reload_plugin, config_file_plugin};
+    use std::time::{Duration, Instant};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct FeelTuning {
+        pan_speed: f32,
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for FeelTuning {
+        const PATH: &'static str = "config/feel_tuning.yaml";
+    }
+
+    #[test]
+    fn test_file_change_reloads_resource_with_overrides() {
+        let files = [(
+            "config/feel_tuning.yaml",
+            "pan_speed: 1000\nzoom_speed: 1\n",
+        )];
+        with_test_dir(&files, |_| {
+            unsafe { std::env::set_var("CONFIG_FeelTuning", r#"{"zoom_speed": 3}"#) };
+            let mut app = App::new();
+            app.add_plugins(config_file_hot_reload_plugin::<FeelTuning>);
+            app.update();
+            assert_eq!(app.world().resource::<FeelTuning>().pan_speed, 1000.0);
+
+            // Editors often write a file more than once when saving
+            fs::write("config/feel_tuning.yaml", "pan_speed: 1500\n").unwrap();
+            fs::write(
+                "config/feel_tuning.yaml",
+                "pan_speed: 2000\nzoom_speed: 2\n",
+            )
+            .unwrap();
+
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while app.world().resource::<FeelTuning>().pan_speed == 1000.0
+                && Instant::now() < deadline
+            {
+                std::thread::sleep(Duration::from_millis(20));
+                app.update();
+            }
+            unsafe { std::env::remove_var("CONFIG_FeelTuning") };
+
+            assert_eq!(
+                app.world().resource::<FeelTuning>(),
+                &FeelTuning {
+                    pan_speed: 2000.0,
+                    zoom_speed: 3.0
+                }
+            );
+        });
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct FallbackTuning {
+        pan_speed: f32,
+    }
+
+    impl ConfigFile for FallbackTuning {
+        const PATH: &'static str = "user/fallback_tuning.yaml";
+        const FALLBACK_PATHS: &'static [&'static str] = &["config/fallback_tuning.yaml"];
+    }
+
+    #[test]
+    fn test_creating_missing_path_reloads_from_it() {
+        let files = [("config/fallback_tuning.yaml", "pan_speed: 1000\n")];
+        with_test_dir(&files, |_| {
+            fs::create_dir_all("user").unwrap();
+            let mut app = App::new();
+            app.add_plugins(config_file_hot_reload_plugin::<FallbackTuning>);
+            app.update();
+            assert_eq!(app.world().resource::<FallbackTuning>().pan_speed, 1000.0);
+
+            fs::write("user/fallback_tuning.yaml", "pan_speed: 2000\n").unwrap();
+
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while app.world().resource::<FallbackTuning>().pan_speed == 1000.0
+                && Instant::now() < deadline
+            {
+                std::thread::sleep(Duration::from_millis(20));
+                app.update();
+            }
+            assert_eq!(app.world().resource::<FallbackTuning>().pan_speed, 2000.0);
+        });
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct SplitTuning {
+        pan_speed: f32,
+        #[serde(default)]
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for SplitTuning {
+        const PATH: &'static str = "config/split_tuning.yaml";
+        const FIELD_DIR: Option<&'static str> = Some("config/split_tuning.d");
+    }
+
+    #[test]
+    fn test_new_field_file_reloads_resource() {
+        let files = [("config/split_tuning.d/pan_speed.yaml", "1000\n")];
+        with_test_dir(&files, |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_hot_reload_plugin::<SplitTuning>);
+            app.update();
+            assert_eq!(app.world().resource::<SplitTuning>().zoom_speed, 0.0);
+
+            fs::write("config/split_tuning.d/zoom_speed.yaml", "2\n").unwrap();
+
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while app.world().resource::<SplitTuning>().zoom_speed == 0.0
+                && Instant::now() < deadline
+            {
+                std::thread::sleep(Duration::from_millis(20));
+                app.update();
+            }
+            assert_eq!(
+                app.world().resource::<SplitTuning>(),
+                &SplitTuning {
+                    pan_speed: 1000.0,
+                    zoom_speed: 2.0
+                }
+            );
+        });
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct ModTuning {
+        pan_speed: f32,
+        zoom_speed: f32,
+    }
+
+    impl ConfigFile for ModTuning {
+        const PATH: &'static str = "config/mod_tuning.yaml";
+        const LAYER_PATHS: &'static [&'static str] =
+            &["config/mod_tuning.yaml", "config/mod_tuning.local.yaml"];
+    }
+
+    #[test]
+    fn test_creating_layer_file_reloads_resource() {
+        let files = [("config/mod_tuning.yaml", "pan_speed: 1000\nzoom_speed: 1\n")];
+        with_test_dir(&files, |_| {
+            let mut app = App::new();
+            app.add_plugins((
+                config_file_plugin::<ModTuning>,
+                config_file_hot_reload_plugin::<ModTuning>,
+            ));
+            app.update();
+            assert_eq!(app.world().resource::<ModTuning>().pan_speed, 1000.0);
+
+            fs::write("config/mod_tuning.local.yaml", "pan_speed: 2000\n").unwrap();
+
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while app.world().resource::<ModTuning>().pan_speed == 1000.0
+                && Instant::now() < deadline
+            {
+                std::thread::sleep(Duration::from_millis(20));
+                app.update();
+            }
+
+            assert_eq!(
+                app.world().resource::<ModTuning>(),
+                &ModTuning {
+                    pan_speed: 2000.0,
+                    zoom_speed: 1.0
+                }
+            );
+        });
+    }
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct DraftTuning {
+        pan_speed: f32,
+    }
+
+    impl ConfigFile for DraftTuning {
+        const PATH: &'static str = "config/draft_tuning.yaml";
+    }
+
+    #[test]
+    fn test_invalid_file_keeps_running_with_previous_value() {
+        let files = [("config/draft_tuning.yaml", "pan_speed: 1000\n")];
+        with_test_dir(&files, |_| {
+            let mut app = App::new();
+            app.add_plugins(config_file_hot_reload_plugin::<DraftTuning>);
+            app.update();
+
+            // A half-written save, with no error handler installed
+            fs::write("config/draft_tuning.yaml", "pan_speed: [10\n").unwrap();
+            let settle = Instant::now() + Duration::from_millis(500);
+            while Instant::now() < settle {
+                std::thread::sleep(Duration::from_millis(20));
+                app.update();
+            }
+            assert_eq!(app.world().resource::<DraftTuning>().pan_speed, 1000.0);
+
+            fs::write("config/draft_tuning.yaml", "pan_speed: 2000\n").unwrap();
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while app.world().resource::<DraftTuning>().pan_speed == 1000.0
+                && Instant::now() < deadline
+            {
+                std::thread::sleep(Duration::from_millis(20));
+                app.update();
+            }
+            assert_eq!(app.world().resource::<DraftTuning>().pan_speed, 2000.0);
+        });
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "dev"))]
+mod dev_hotkey_tests {
+    use super::*;
+    use bevy::input::ButtonState;
+    use bevy::input::InputPlugin;
+    use bevy::input::keyboard::{Key, KeyboardInput};
+    use bevy::prelude::*;
+    use bevy_config_file::{ConfigReloadHotkey, JsonValue, config_file_plugin_dev};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct EnemyTuning {
+        speed: f32,
+    }
+
+    impl ConfigFile for EnemyTuning {
+        const PATH: &'static str = "enemy_tuning.yaml";
+    }
+
+    fn press(app: &mut App, key_code: KeyCode, logical_key: Key) {
+        send_key(app, key_code, logical_key, ButtonState::Pressed);
+    }
+
+    fn send_key(app: &mut App, key_code: KeyCode, logical_key: Key, state: ButtonState) {
+        app.world_mut().write_message(KeyboardInput {
+            key_code,
+            logical_key,
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn test_hotkey_reloads_config() {
+        with_test_dir(&[("enemy_tuning.yaml", "speed: 1.0\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(InputPlugin);
+            app.insert_resource(ConfigReloadHotkey {
+                key: KeyCode::F5,
+                enabled: true,
+            });
+            app.add_plugins(config_file_plugin_dev::<EnemyTuning>);
+            app.update();
+
+            fs::write("enemy_tuning.yaml", "speed: 2.5\n").unwrap();
+            app.update();
+            assert_eq!(app.world().resource::<EnemyTuning>().speed, 1.0);
+
+            press(&mut app, KeyCode::F4, Key::F4);
+            assert_eq!(app.world().resource::<EnemyTuning>().speed, 1.0);
+
+            press(&mut app, KeyCode::F5, Key::F5);
+            assert_eq!(app.world().resource::<EnemyTuning>().speed, 2.5);
+        });
+    }
+
+    #[test]
+    fn test_disabled_hotkey_does_nothing() {
+        with_test_dir(&[("enemy_tuning.yaml", "speed: 1.0\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(InputPlugin);
+            app.insert_resource(ConfigReloadHotkey {
+                key: KeyCode::F5,
+                enabled: false,
+            });
+            app.add_plugins(config_file_plugin_dev::<EnemyTuning>);
+            app.update();
+
+            fs::write("enemy_tuning.yaml", "speed: 2.5\n").unwrap();
+            press(&mut app, KeyCode::F5, Key::F5);
+            assert_eq!(app.world().resource::<EnemyTuning>().speed, 1.0);
+        });
+    }
+
+    #[test]
+    fn test_hotkey_with_invalid_file_keeps_previous_value() {
+        with_test_dir(&[("enemy_tuning.yaml", "speed: 1.0\n")], |_| {
+            let mut app = App::new();
+            app.add_plugins(InputPlugin);
+            app.insert_resource(ConfigReloadHotkey {
+                key: KeyCode::F5,
+                enabled: true,
+            });
+            app.add_plugins(config_file_plugin_dev::<EnemyTuning>);
+            app.update();
+
+            fs::write("enemy_tuning.yaml", "speed: fast\n").unwrap();
+            press(&mut app, KeyCode::F5, Key::F5);
+            assert_eq!(app.world().resource::<EnemyTuning>().speed, 1.0);
+            send_key(&mut app, KeyCode::F5, Key::F5, ButtonState::Released);
+
+            fs::write("enemy_tuning.yaml", "speed: 2.5\n").unwrap();
+            press(&mut app, KeyCode::F5, Key::F5);
+            assert_eq!(app.world().resource::<EnemyTuning>().speed, 2.5);
+        });
+    }
+
+    static AUDITED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct AuditedTuning {
+        speed: f32,
+        health: u32,
+    }
+
+    impl ConfigFile for AuditedTuning {
+        const PATH: &'static str = "audited_tuning.yaml";
+
+        fn on_override(field: &str, _old: &JsonValue, _new: &JsonValue) {
+            AUDITED.lock().unwrap().push(field.to_string());
+        }
+    }
+
+    #[test]
+    fn test_hotkey_loads_the_file_once_and_logs_the_loaded_changes() {
+        with_test_dir(&[("audited_tuning.yaml", "speed: 1.0\nhealth: 10\n")], |_| {
+            unsafe { std::env::set_var("CONFIG_AuditedTuning", r#"{"speed": 3.0}"#) };
+            let mut app = App::new();
+            app.add_plugins(InputPlugin);
+            app.insert_resource(ConfigReloadHotkey {
+                key: KeyCode::F5,
+                enabled: true,
+            });
+            app.add_plugins(config_file_plugin_dev::<AuditedTuning>);
+            app.update();
+            AUDITED.lock().unwrap().clear();
+
+            fs::write("audited_tuning.yaml", "speed: 1.0\nhealth: 20\n").unwrap();
+            #[cfg(feature = "logging")]
+            let logs = CapturedLogs::default();
+            #[cfg(feature = "logging")]
+            let _guard = logs.set_default();
+            press(&mut app, KeyCode::F5, Key::F5);
+            unsafe { std::env::remove_var("CONFIG_AuditedTuning") };
+
+            assert_eq!(app.world().resource::<AuditedTuning>().health, 20);
+            assert_eq!(*AUDITED.lock().unwrap(), ["speed"]);
+            #[cfg(feature = "logging")]
+            assert!(
+                logs.lines()
+                    .iter()
+                    .any(|line| line.contains("from hotkey; changed health: 10 -> 20")),
+                "{:?}",
+                logs.lines()
+            );
+        });
+    }
+}
+
 #[cfg(feature = "yaml")]
-mod yaml_tests {
+mod sections_tests {
     use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::config_sections;
 
-    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq)]
-    struct TestConfig {
-        value: i32,
-        name: String,
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct SectionAudio {
+        volume: f32,
+        muted: bool,
     }
 
-    impl ConfigFile for TestConfig {
-        const PATH: &'static str = "config.yaml";
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct SectionVideo {
+        vsync: bool,
     }
 
-    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq)]
-    struct TestYmlConfig {
-        value: i32,
-        name: String,
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize, PartialEq)]
+    #[reflect(Resource)]
+    struct SectionInput {
+        sensitivity: f32,
     }
 
-    impl ConfigFile for TestYmlConfig {
-        const PATH: &'static str = "config.yml";
+    config_sections! {
+        fn settings_plugin("settings.yaml", env = "CONFIG_SectionSettings") {
+            audio: SectionAudio,
+            video: SectionVideo,
+            input: SectionInput,
+        }
     }
 
+    const SETTINGS: &str = "\
+audio:
+  volume: 0.8
+  muted: false
+video:
+  vsync: true
+input:
+  sensitivity: 1.5
+";
+
     #[test]
-    fn test_load_valid_yml_config() {
-        run_config_test::<TestYmlConfig, _>(
-            Some("value: 42\nname: test\n"),
-            vec![],
-            |app, load_result| {
-                assert!(load_result.is_ok());
-                let config = app.world().get_resource::<TestYmlConfig>().unwrap();
-                assert_eq!(config.value, 42);
-                assert_eq!(config.name, "test");
-            },
-        );
+    fn test_sections_load_into_separate_resources() {
+        with_test_dir(&[("settings.yaml", SETTINGS)], |_| {
+            let mut app = App::new();
+            app.add_plugins(settings_plugin);
+            app.update();
+
+            let world = app.world();
+            assert_eq!(
+                world.resource::<SectionAudio>(),
+                &SectionAudio {
+                    volume: 0.8,
+                    muted: false,
+                }
+            );
+            assert_eq!(
+                world.resource::<SectionVideo>(),
+                &SectionVideo { vsync: true }
+            );
+            assert_eq!(
+                world.resource::<SectionInput>(),
+                &SectionInput { sensitivity: 1.5 }
+            );
+        });
     }
 
     #[test]
-    fn test_load_valid_config() {
-        run_config_test::<TestConfig, _>(
-            Some("value: 42\nname: test\n"),
-            vec![],
-            |app, load_result| {
-                assert!(load_result.is_ok());
+    fn test_document_override_targets_combined_file() {
+        with_test_dir(&[("settings.yaml", SETTINGS)], |_| {
+            let document = r#"{"audio": {"muted": true}, "input": {"sensitivity": 3.0}}"#;
+            unsafe { std::env::set_var("CONFIG_SectionSettings", document) };
+            let mut app = App::new();
+            app.add_plugins(settings_plugin);
+            app.update();
+            unsafe { std::env::remove_var("CONFIG_SectionSettings") };
 
-                let config = app.world().get_resource::<TestConfig>();
-                assert!(config.is_some());
-                let config = config.unwrap();
-                assert_eq!(config.value, 42);
-                assert_eq!(config.name, "test");
-            },
-        );
+            let world = app.world();
+            assert_eq!(
+                world.resource::<SectionAudio>(),
+                &SectionAudio {
+                    volume: 0.8,
+                    muted: true,
+                }
+            );
+            assert_eq!(
+                world.resource::<SectionVideo>(),
+                &SectionVideo { vsync: true }
+            );
+            assert_eq!(world.resource::<SectionInput>().sensitivity, 3.0);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod override_shape_tests {
+    use super::*;
+    use bevy::prelude::*;
+    use bevy_config_file::{LoadConfigError, config_file_plugin, load_config_file};
+
+    #[derive(Resource, Reflect, Debug, Serialize, Deserialize)]
+    #[reflect(Resource)]
+    struct ShapedSettings {
+        volume: f32,
+        max_players: u32,
+        video: ShapedVideo,
+        save_dir: std::path::PathBuf,
+    }
+
+    #[derive(Reflect, Debug, Serialize, Deserialize)]
+    struct ShapedVideo {
+        vsync: bool,
+    }
+
+    impl ConfigFile for ShapedSettings {
+        const PATH: &'static str = "shaped.yaml";
+    }
+
+    fn load_with_override(json: &str) -> Result<ShapedSettings, LoadConfigError> {
+        // Adding the plugin records the type's reflection info without loading it
+        App::new().add_plugins(config_file_plugin::<ShapedSettings>);
+        unsafe { std::env::set_var("CONFIG_ShapedSettings", json) };
+        let result = load_config_file::<ShapedSettings>();
+        unsafe { std::env::remove_var("CONFIG_ShapedSettings") };
+        result
     }
 
+    const SHAPED: &str = "volume: 1.0\nmax_players: 8\nvideo:\n  vsync: true\nsave_dir: saves\n";
+
     #[test]
-    fn test_load_missing_config_file() {
-        run_config_test::<TestConfig, _>(
-            None, // No config file written
-            vec![],
-            |app, load_result| {
-                assert!(load_result.is_err());
-                let config = app.world().get_resource::<TestConfig>();
-                assert!(config.is_none());
-            },
-        );
+    fn test_mismatched_override_field_is_reported_specifically() {
+        with_test_dir(&[("shaped.yaml", SHAPED)], |_| {
+            let result = load_with_override(r#"{"volume": 0.5, "max_players": "lots"}"#);
+
+            match result {
+                Err(LoadConfigError::IncompatibleOverride {
+                    override_source,
+                    problems,
+                }) => {
+                    assert_eq!(override_source, "CONFIG_ShapedSettings");
+                    assert_eq!(
+                        problems,
+                        vec!["max_players should be an integer (u32), found a string"]
+                    );
+                }
+                other => panic!("expected an incompatible override, got {:?}", other),
+            }
+        });
     }
 
     #[test]
-    fn test_load_invalid_yaml() {
-        run_config_test::<TestConfig, _>(
-            Some("invalid: yaml: content:\n  - this is bad\n  missing bracket"),
-            vec![],
-            |app, load_result| {
-                assert!(load_result.is_err());
-                let config = app.world().get_resource::<TestConfig>();
-                assert!(config.is_none());
-            },
-        );
+    fn test_unknown_and_nested_override_fields_are_reported() {
+        with_test_dir(&[("shaped.yaml", SHAPED)], |_| {
+            let json = r#"{"volum": 0.5, "video": {"vsync": "yes"}, "max_players": {"$mul": 2}}"#;
+            let error = load_with_override(json).unwrap_err();
+            let message = error.to_string();
+            assert!(message.contains("volum is not a field"), "{}", message);
+            assert!(
+                message.contains("video.vsync should be a boolean"),
+                "{}",
+                message
+            );
+            assert!(!message.contains("max_players"), "{}", message);
+
+            let config = load_with_override(r#"{"max_players": {"$mul": 2}}"#).unwrap();
+            assert_eq!(config.max_players, 16);
+        });
     }
 
     #[test]
-    fn test_load_with_env_override() {
-        run_config_test::<TestConfig, _>(
-            Some("value: 42\nname: test\n"),
-            vec![("CONFIG_TestConfig", r#"{"value": 100}"#)],
-            |app, load_result| {
-                assert!(load_result.is_ok());
+    fn test_path_override_field_must_be_a_string() {
+        with_test_dir(&[("shaped.yaml", SHAPED)], |_| {
+            let message = load_with_override(r#"{"save_dir": 3}"#)
+                .unwrap_err()
+                .to_string();
+            assert!(
+                message.contains("save_dir should be a string"),
+                "{}",
+                message
+            );
 
-                let config = app.world().get_resource::<TestConfig>();
-                assert!(config.is_some());
-                let config = config.unwrap();
-                assert_eq!(config.value, 100); // Overridden value
-                assert_eq!(config.name, "test"); // Original value
+            let config = load_with_override(r#"{"save_dir": "backups"}"#).unwrap();
+            assert_eq!(config.save_dir, std::path::PathBuf::from("backups"));
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod app_id_tests {
+    use super::*;
+    use bevy_config_file::load_config_file;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct TenantSettings {
+        max_players: u32,
+    }
+
+    impl ConfigFile for TenantSettings {
+        const PATH: &'static str = "tenant.yaml";
+        const APP_ID_VAR: Option<&'static str> = Some("TEST_GAME_ID");
+    }
+
+    #[test]
+    fn test_app_id_is_inserted_into_override_var() {
+        with_test_dir(&[("tenant.yaml", "max_players: 8\n")], |_| {
+            unsafe {
+                std::env::set_var("TEST_GAME_ID", "foo");
+                std::env::set_var("CONFIG_foo_TenantSettings", r#"{"max_players": 64}"#);
+                std::env::set_var("CONFIG_TenantSettings", r#"{"max_players": 2}"#);
+            }
+            let with_id = load_config_file::<TenantSettings>();
+            unsafe { std::env::remove_var("TEST_GAME_ID") };
+            let without_id = load_config_file::<TenantSettings>();
+            unsafe {
+                std::env::remove_var("CONFIG_foo_TenantSettings");
+                std::env::remove_var("CONFIG_TenantSettings");
+            }
+
+            assert_eq!(with_id.unwrap().max_players, 64);
+            assert_eq!(without_id.unwrap().max_players, 2);
+        });
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod pretty_tests {
+    use bevy::prelude::*;
+    use bevy_config_file::attributes::Secret;
+    use bevy_config_file::config_pretty_string;
+
+    #[derive(Reflect)]
+    struct Shadows {
+        enabled: bool,
+        resolution: u32,
+    }
+
+    #[derive(Reflect)]
+    struct OverlaySettings {
+        brightness: f32,
+        title: String,
+        shadows: Shadows,
+        #[reflect(@Secret)]
+        api_key: String,
+        #[reflect(@Secret)]
+        credentials: Credentials,
+        fov: Option<f32>,
+    }
+
+    #[derive(Reflect)]
+    struct Credentials {
+        user: String,
+        password: String,
+    }
+
+    #[test]
+    fn test_pretty_string_aligns_and_indents_nested_fields() {
+        let settings = OverlaySettings {
+            brightness: 0.5,
+            title: "Game".to_string(),
+            shadows: Shadows {
+                enabled: true,
+                resolution: 1024,
+            },
+            api_key: "hunter2".to_string(),
+            credentials: Credentials {
+                user: "admin".to_string(),
+                password: "hunter3".to_string(),
             },
+            fov: Some(90.0),
+        };
+
+        assert_eq!(
+            config_pretty_string(&settings),
+            "\
+brightness:  0.5
+title:       Game
+shadows:
+  enabled:    true
+  resolution: 1024
+api_key:     ***
+credentials: ***
+fov:         Some(90.0)
+"
         );
     }
+}
+
+#[cfg(feature = "yaml")]
+mod nested_override_tests {
+    use super::*;
+    use bevy_config_file::{load_config_file, merge_json};
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Mixer {
+        volume: f32,
+        balance: f32,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Audio {
+        mixer: Mixer,
+        device: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct GroupedSettings {
+        audio: Audio,
+        language: String,
+    }
+
+    impl ConfigFile for GroupedSettings {
+        const PATH: &'static str = "grouped.yaml";
+    }
 
     #[test]
-    fn test_load_with_invalid_env_override() {
-        run_config_test::<TestConfig, _>(
-            Some("value: 42\nname: test\n"),
-            vec![("CONFIG_TestConfig", r#"{"value": invalid json}"#)],
-            |app, load_result| {
-                assert!(load_result.is_err());
-                let config = app.world().get_resource::<TestConfig>();
-                assert!(config.is_none());
-            },
+    fn test_nested_override_keeps_sibling_fields() {
+        let file = "\
+audio:
+  mixer:
+    volume: 1.0
+    balance: 0.25
+  device: speakers
+language: en
+";
+        with_test_dir(&[("grouped.yaml", file)], |_| {
+            let json = r#"{"audio": {"mixer": {"volume": 0.5}}}"#;
+            unsafe { std::env::set_var("CONFIG_GroupedSettings", json) };
+            let result = load_config_file::<GroupedSettings>();
+            unsafe { std::env::remove_var("CONFIG_GroupedSettings") };
+
+            assert_eq!(
+                result.unwrap(),
+                GroupedSettings {
+                    audio: Audio {
+                        mixer: Mixer {
+                            volume: 0.5,
+                            balance: 0.25,
+                        },
+                        device: "speakers".to_string(),
+                    },
+                    language: "en".to_string(),
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_merge_json_replaces_leaves_and_merges_objects() {
+        let mut base = json!({
+            "audio": {"mixer": {"volume": 1.0, "balance": 0.25}, "tracks": [1, 2]},
+            "language": "en",
+        });
+        merge_json(
+            &mut base,
+            json!({"audio": {"mixer": {"volume": 0.5}, "tracks": [3]}, "debug": true}),
+        );
+
+        assert_eq!(
+            base,
+            json!({
+                "audio": {"mixer": {"volume": 0.5, "balance": 0.25}, "tracks": [3]},
+                "language": "en",
+                "debug": true,
+            })
         );
     }
 }
@@ -183,7 +6951,7 @@ mod yaml_tests {
 mod json_tests {
     use super::*;
 
-    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq)]
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
     struct TestJsonConfig {
         value: i32,
         name: String,
@@ -262,7 +7030,7 @@ mod json_tests {
 mod ron_tests {
     use super::*;
 
-    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq)]
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
     struct TestRonConfig {
         value: i32,
         name: String,
@@ -337,30 +7105,118 @@ mod ron_tests {
     }
 }
 
+#[cfg(feature = "toml")]
+mod toml_tests {
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Audio {
+        volume: f32,
+        muted: bool,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct Video {
+        width: u32,
+        height: u32,
+    }
+
+    #[derive(Resource, Debug, Serialize, Deserialize, PartialEq, Reflect)]
+    struct TestTomlConfig {
+        name: String,
+        audio: Audio,
+        video: Video,
+    }
+
+    impl ConfigFile for TestTomlConfig {
+        const PATH: &'static str = "settings.toml";
+    }
+
+    const SETTINGS: &str = r#"
+name = "test"
+
+[audio]
+volume = 0.8
+muted = false
+
+[video]
+width = 1920
+height = 1080
+"#;
+
+    #[test]
+    fn test_load_valid_toml_config() {
+        run_config_test::<TestTomlConfig, _>(Some(SETTINGS), vec![], |app, load_result| {
+            assert!(load_result.is_ok());
+            let config = app.world().get_resource::<TestTomlConfig>().unwrap();
+            assert_eq!(
+                config,
+                &TestTomlConfig {
+                    name: "test".to_string(),
+                    audio: Audio {
+                        volume: 0.8,
+                        muted: false,
+                    },
+                    video: Video {
+                        width: 1920,
+                        height: 1080,
+                    },
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_invalid_toml_config() {
+        run_config_test::<TestTomlConfig, _>(
+            Some("name = \"test\"\n[audio\n"),
+            vec![],
+            |app, load_result| {
+                assert!(load_result.is_err());
+                assert!(app.world().get_resource::<TestTomlConfig>().is_none());
+            },
+        );
+    }
+
+    #[test]
+    fn test_toml_config_with_env_override() {
+        run_config_test::<TestTomlConfig, _>(
+            Some(SETTINGS),
+            vec![("CONFIG_TestTomlConfig", r#"{"audio": {"muted": true}}"#)],
+            |app, load_result| {
+                assert!(load_result.is_ok());
+                let config = app.world().get_resource::<TestTomlConfig>().unwrap();
+                assert!(config.audio.muted);
+                assert_eq!(config.audio.volume, 0.8);
+            },
+        );
+    }
+}
+
 mod unsupported_format_tests {
     use super::*;
     use bevy_config_file::{load_config_file, LoadConfigError};
 
-    #[derive(Debug, Serialize, Deserialize)]
-    struct TestTomlConfig {
+    #[derive(Debug, Serialize, Deserialize, Reflect)]
+    struct TestIniConfig {
         value: i32,
     }
 
-    impl ConfigFile for TestTomlConfig {
-        const PATH: &'static str = "config.toml";
+    impl ConfigFile for TestIniConfig {
+        const PATH: &'static str = "config.ini";
     }
 
     #[test]
     fn test_unsupported_format_returns_error() {
         let _lock = TEST_MUTEX.lock().unwrap();
         let test_dir = TempDir::new().unwrap();
-        fs::write(test_dir.path().join("config.toml"), "value = 42").unwrap();
+        fs::write(test_dir.path().join("config.ini"), "value = 42").unwrap();
         let original_dir = std::env::current_dir().unwrap();
         std::env::set_current_dir(test_dir.path()).unwrap();
 
-        let result = load_config_file::<TestTomlConfig>();
+        let result = load_config_file::<TestIniConfig>();
         match result {
-            Err(LoadConfigError::UnsupportedFormat(ext)) => assert_eq!(ext, "toml"),
+            Err(LoadConfigError::UnsupportedFormat(ext)) => assert_eq!(ext, "ini"),
             other => panic!("expected UnsupportedFormat, got {:?}", other),
         }
 